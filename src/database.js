@@ -450,6 +450,14 @@ export class SwarmDB {
     return stmt.get(name);
   }
 
+  /**
+   * Update a topic's key and auto-join flag
+   */
+  updateTopic(name, topicKey, autoJoin) {
+    const stmt = this.db.prepare('UPDATE topics SET topic_key = ?, auto_join = ? WHERE name = ?');
+    return stmt.run(topicKey, autoJoin ? 1 : 0, name);
+  }
+
   /**
    * Get topic by key
    */
@@ -88,6 +88,10 @@ export class DownloadSession extends EventEmitter {
     
     this.running = false;
     this.loopCount = 0;
+
+    // Set by `stop()`; a stopped session never completes.
+    this._stopRequested = false;
+    this.stopped = false;
     
     this.fileId = null;
     
@@ -152,7 +156,13 @@ export class DownloadSession extends EventEmitter {
     // If the swarm already had connections before this session started,
     // we won't receive peer:connected events for them. Bootstrap from network state.
     this.bootstrapExistingPeers();
-    
+
+    if (this._stopRequested) {
+      this.stopped = true;
+      await this._shutdown();
+      return;
+    }
+
     this.running = true;
     this.downloadLoop();
   }
@@ -1001,20 +1011,22 @@ export class DownloadSession extends EventEmitter {
     }
   }
 
-  async onComplete() {
-    console.log(`\n🎉 Download complete!`);
-    console.log(`   File: ${this.outputPath}`);
-    console.log(`   Size: ${this._formatBytes(this.fileSize)}`);
-
-    // Stop the loop and prevent any late writes/timeouts from mutating state.
+  /**
+   * Stops the loop and prevents any late writes/timeouts from mutating state: outstanding
+   * protocol requests are cancelled and the output file is closed.
+   */
+  async _shutdown() {
     this.running = false;
 
     this._clearAllChunkTimeouts();
-    
+    for (const timeout of this._subtreeTimeouts.values()) {
+      clearTimeout(timeout);
+    }
+    this._subtreeTimeouts.clear();
+
     // Close logger
     this.logger?.close();
 
-    // Cancel all outstanding protocol requests to avoid post-success timeouts.
     for (const requestId of this._activeRequestIds) {
       try {
         this.protocol.cancelRequest(requestId);
@@ -1028,6 +1040,27 @@ export class DownloadSession extends EventEmitter {
     this.detachProtocolHandlers();
 
     await this.closeOutputFile();
+  }
+
+  /**
+   * Stops an unfinished download. Verified chunks stay in the output file, so starting a new
+   * session for the same file picks up where this one left off.
+   */
+  async stop() {
+    // A session still starting up checks this flag before entering the loop.
+    this._stopRequested = true;
+    if (!this.running) return;
+    this.stopped = true;
+    await this._shutdown();
+    this.emit('stopped');
+  }
+
+  async onComplete() {
+    console.log(`\n🎉 Download complete!`);
+    console.log(`   File: ${this.outputPath}`);
+    console.log(`   Size: ${this._formatBytes(this.fileSize)}`);
+
+    await this._shutdown();
 
     // Final verification: recompute Merkle root from the file bytes.
     try {
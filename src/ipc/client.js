@@ -36,8 +36,13 @@ export function createRpcClient(sock) {
       if (msg && msg.type === 'res' && msg.id && pending.has(msg.id)) {
         const { resolve, reject } = pending.get(msg.id)
         pending.delete(msg.id)
-        if (msg.ok) resolve(msg.result)
-        else reject(new Error(msg?.error?.message || 'RPC error'))
+        if (msg.ok) {
+          resolve(msg.result)
+        } else {
+          const err = new Error(msg?.error?.message || 'RPC error')
+          err.code = msg?.error?.code
+          reject(err)
+        }
         continue
       }
 
@@ -188,7 +188,8 @@ export class IpcServer extends EventEmitter {
       const result = await this._dispatch(msg.method, msg.params || {}, sock)
       writeLine(sock, { id, type: 'res', ok: true, result })
     } catch (e) {
-      writeLine(sock, { id, type: 'res', ok: false, error: { code: 'error', message: e?.message || String(e) } })
+      const code = typeof e?.code === 'string' ? e.code : 'error'
+      writeLine(sock, { id, type: 'res', ok: false, error: { code, message: e?.message || String(e) } })
     }
   }
 
@@ -227,14 +228,35 @@ export class IpcServer extends EventEmitter {
 
       case 'downloads.resume':
         {
-          const res = await this.node.downloadsResumeAll(params?.topic ?? null)
-          this._broadcast('state', { event: 'state.downloads', data: { op: 'resume', topic: params?.topic ?? null } })
+          // With an `id`, resumes that download only; otherwise every incomplete one (optionally per topic).
+          const id = params?.id ?? null
+          const res = id !== null
+            ? await this.node.downloadsResume(id)
+            : await this.node.downloadsResumeAll(params?.topic ?? null)
+          this._broadcast('state', { event: 'state.downloads', data: { op: 'resume', topic: params?.topic ?? null, id } })
+          return res
+        }
+
+      case 'downloads.pause':
+        {
+          const res = await this.node.downloadsPause(params?.id)
+          this._broadcast('state', { event: 'state.downloads', data: { op: 'pause', id: params?.id ?? null } })
+          return res
+        }
+
+      case 'downloads.cancel':
+        {
+          const res = await this.node.downloadsCancel(params?.id)
+          this._broadcast('state', { event: 'state.downloads', data: { op: 'cancel', id: params?.id ?? null } })
           return res
         }
 
       case 'topic.list':
         return await this.node.listTopics()
 
+      case 'topic.peers':
+        return this.node.topicPeers(params?.name)
+
       case 'topic.join':
         await this.node.joinTopic(params?.name)
         this._broadcast('state', { event: 'state.topics', data: { op: 'join', name: params?.name ?? null } })
@@ -252,6 +274,13 @@ export class IpcServer extends EventEmitter {
           return res
         }
 
+      case 'topic.update':
+        {
+          const res = await this.node.updateTopic(params?.name, params?.autoJoin, params?.password)
+          this._broadcast('state', { event: 'state.topics', data: { op: 'update', name: params?.name ?? null } })
+          return res
+        }
+
       case 'topic.rm':
         {
           const res = await this.node.deleteTopic(params?.name)
@@ -277,7 +306,7 @@ export class IpcServer extends EventEmitter {
 
       case 'files.remove':
         {
-          const res = this.node.filesRemove(params?.path)
+          const res = this.node.filesRemove(params?.path, { deleteFromDisk: params?.delete === true })
           this._broadcast('state', { event: 'state.files', data: { op: 'remove', path: params?.path ?? null } })
           return res
         }
@@ -35,7 +35,7 @@ export class SwarmNetwork extends EventEmitter {
     // Track active topics and connections
     // topics: topicKeyHex -> { discovery, name, key, connections: Map<peerId, conn> }
     this.topics = new Map();
-    // peerConnections: peerId -> { conn, topics: Set<topicKeyHex> }
+    // peerConnections: peerId -> { conn, topics: Set<topicKeyHex>, lastSeen: ms of last connect/data }
     this.peerConnections = new Map();
 
     this.setupSwarmHandlers();
@@ -164,6 +164,7 @@ export class SwarmNetwork extends EventEmitter {
 
       const peerConn = this.peerConnections.get(peerId);
       peerConn.conn = conn;
+      peerConn.lastSeen = Date.now();
 
       for (const t of attributedTopicKeys) {
         const topicKeyHex = t.toString('hex');
@@ -195,6 +196,9 @@ export class SwarmNetwork extends EventEmitter {
     conn.on('data', (data) => {
       const peerConn = this.peerConnections.get(peerId);
       const topics = peerConn ? Array.from(peerConn.topics) : [];
+      if (peerConn) {
+        peerConn.lastSeen = Date.now();
+      }
 
       // Emit only once per chunk. If topics are known, include them as metadata.
       // Protocol currently ignores topicKey on peer:data anyway, and duplicating
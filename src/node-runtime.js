@@ -2,12 +2,82 @@ import { EventEmitter } from 'events'
 import fs from 'fs'
 import path from 'path'
 
+/**
+ * Removes `dir` and its subdirectories bottom-up wherever they are empty. Symlinks are not
+ * followed. Returns true when `dir` itself was removed.
+ */
+function pruneEmptyDirs(dir) {
+  let entries
+  try {
+    entries = fs.readdirSync(dir, { withFileTypes: true })
+  } catch {
+    return false
+  }
+  let remaining = entries.length
+  for (const e of entries) {
+    if (e.isDirectory() && pruneEmptyDirs(path.join(dir, e.name))) remaining--
+  }
+  if (remaining > 0) return false
+  try {
+    fs.rmdirSync(dir)
+    return true
+  } catch {
+    return false
+  }
+}
+
+// Reported as `version` by node.status so clients can detect an outdated daemon.
+const PACKAGE_VERSION = (() => {
+  try {
+    return JSON.parse(fs.readFileSync(new URL('../package.json', import.meta.url), 'utf8')).version ?? null
+  } catch {
+    return null
+  }
+})()
+
+/** `host:port` addresses the swarm's DHT node is reachable on: the bound socket, then the public one. */
+function listenAddresses(swarm) {
+  const dht = swarm?.dht
+  if (!dht) return []
+  const out = []
+  try {
+    const local = dht.address?.()
+    if (local?.host && local?.port) out.push(`${local.host}:${local.port}`)
+  } catch {
+    // not bound yet
+  }
+  if (dht.host && dht.port) {
+    const pub = `${dht.host}:${dht.port}`
+    if (!out.includes(pub)) out.push(pub)
+  }
+  return out
+}
+
+/** An Error with a machine-readable `code`, passed through to IPC clients as `error.code`. */
+function codedError(code, message) {
+  const e = new Error(message)
+  e.code = code
+  return e
+}
+
+/** `host:port` of a Hyperswarm connection, or null when the transport does not expose it. */
+function remoteAddress(conn) {
+  const host = conn?.rawStream?.remoteHost
+  if (!host) return null
+  const port = conn.rawStream.remotePort
+  return port ? `${host}:${port}` : host
+}
+
 export class NodeRuntime extends EventEmitter {
   constructor(swarmfs) {
     super()
     this.swarmfs = swarmfs
     this.started = false
     this._networkHandlersInstalled = false
+    // Running downloads by `_downloadKey` as `{ session }` (null until the session exists),
+    // and ids of downloads paused this run.
+    this._sessions = new Map()
+    this._paused = new Set()
   }
 
   async start() {
@@ -25,6 +95,7 @@ export class NodeRuntime extends EventEmitter {
 
     this._installNetworkHandlersIfNeeded()
     this.started = true
+    this.startedAt = Date.now()
   }
 
   async stop() {
@@ -47,7 +118,13 @@ export class NodeRuntime extends EventEmitter {
       }
     }
 
+    // Identity and addresses exist once the network is up (after the first topic join).
+    const swarm = this.swarmfs?.network?.swarm
     return {
+      version: PACKAGE_VERSION,
+      uptimeSec: this.startedAt ? Math.floor((Date.now() - this.startedAt) / 1000) : 0,
+      peerId: swarm?.keyPair?.publicKey ? swarm.keyPair.publicKey.toString('hex') : null,
+      listenAddresses: listenAddresses(swarm),
       dbOpen,
       networkRunning,
       protocolRunning,
@@ -110,6 +187,19 @@ export class NodeRuntime extends EventEmitter {
     return await this.swarmfs.createTopic(n, aj, pw)
   }
 
+  /** Changes auto-join and, for a non-empty password, the key; a blank password keeps the key. */
+  async updateTopic(name, autoJoin = true, password = null) {
+    this.swarmfs.open()
+    const n = String(name || '')
+    if (!n) throw new Error('name required')
+    if (!this.swarmfs.db.getTopic(n)) throw codedError('not_found', `Topic "${n}" not found`)
+    const aj = autoJoin !== false
+    const pw = password ? String(password) : null
+    const res = await this.swarmfs.updateTopic(n, aj, pw)
+    this._installNetworkHandlersIfNeeded()
+    return res
+  }
+
   async deleteTopic(name) {
     this.swarmfs.open()
     const n = String(name || '')
@@ -117,6 +207,26 @@ export class NodeRuntime extends EventEmitter {
     return await this.swarmfs.deleteTopic(n)
   }
 
+  /** Peers connected on a joined topic; an unjoined topic has none. */
+  topicPeers(name) {
+    this.swarmfs.open()
+    const n = String(name || '')
+    if (!n) throw new Error('name required')
+    const topic = this.swarmfs.db.getTopic(n)
+    if (!topic) throw codedError('not_found', `Topic "${n}" not found`)
+
+    const net = this.swarmfs.network
+    const joined = net?.topics?.get(topic.topic_key)
+    if (!joined) return { joined: false, peers: [] }
+
+    const peers = Array.from(joined.connections, ([peerId, conn]) => ({
+      id: peerId,
+      address: remoteAddress(conn),
+      lastSeen: net.peerConnections.get(peerId)?.lastSeen ?? null
+    }))
+    return { joined: true, peers }
+  }
+
   async browseTopic(name, timeout = 5000) {
     this.swarmfs.open()
     const n = String(name || '')
@@ -127,7 +237,11 @@ export class NodeRuntime extends EventEmitter {
 
   downloadsList() {
     this.swarmfs.open()
-    return this.swarmfs.db.getAllDownloads()
+    return this.swarmfs.db.getAllDownloads().map((d) => ({
+      ...d,
+      paused: this._paused.has(d.id),
+      active: this._sessions.has(this._downloadKey(d.topic_name, d.merkle_root, d.output_path))
+    }))
   }
 
   async downloadsResumeAll(topicName = null) {
@@ -142,12 +256,72 @@ export class NodeRuntime extends EventEmitter {
         continue
       }
 
-      this._startDownloadAsync(t, root, out)
-      results.push({ ok: true, topic: t, merkleRoot: root, outputPath: out, started: true })
+      this._paused.delete(d.id)
+      const started = !this._sessions.has(this._downloadKey(t, root, out))
+      if (started) this._startDownloadAsync(t, root, out)
+      results.push({ ok: true, topic: t, merkleRoot: root, outputPath: out, started })
     }
     return { ok: true, results }
   }
 
+  _findDownload(id) {
+    const n = Number(id)
+    const d = Number.isFinite(n) ? this.swarmfs.db.getAllDownloads().find((x) => x.id === n) : null
+    if (!d) throw codedError('not_found', `download ${id} not found`)
+    return d
+  }
+
+  _findIncompleteDownload(id) {
+    const d = this._findDownload(id)
+    if (d.completed_at) throw codedError('completed', `download ${id} already completed`)
+    return d
+  }
+
+  async _stopSession(d) {
+    const key = this._downloadKey(d.topic_name, d.merkle_root, d.output_path)
+    const entry = this._sessions.get(key)
+    this._sessions.delete(key)
+    // A download still fetching metadata stops itself once its session is created.
+    if (entry?.session) await entry.session.stop()
+  }
+
+  /** Stops one download, keeping its verified chunks for `downloadsResume`. */
+  async downloadsPause(id) {
+    this.swarmfs.open()
+    const d = this._findIncompleteDownload(id)
+    this._paused.add(d.id)
+    await this._stopSession(d)
+    return { ok: true, id: d.id, paused: true }
+  }
+
+  /** Restarts one download; a no-op when it is already running. */
+  async downloadsResume(id) {
+    this.swarmfs.open()
+    const d = this._findIncompleteDownload(id)
+    this._paused.delete(d.id)
+    const started = !this._sessions.has(this._downloadKey(d.topic_name, d.merkle_root, d.output_path))
+    if (started) this._startDownloadAsync(d.topic_name, d.merkle_root, d.output_path)
+    return { ok: true, id: d.id, started }
+  }
+
+  /**
+   * Stops one download and drops it from the list. An unfinished download's partial output
+   * file is untracked and deleted; a completed download's file is left alone.
+   */
+  async downloadsCancel(id) {
+    this.swarmfs.open()
+    const d = this._findDownload(id)
+    await this._stopSession(d)
+    this._paused.delete(d.id)
+    this.swarmfs.db.deleteDownloads([d.id])
+    const deleted = !d.completed_at
+    if (deleted) {
+      this.swarmfs.removeFile(d.output_path)
+      fs.rmSync(d.output_path, { force: true })
+    }
+    return { ok: true, id: d.id, deleted }
+  }
+
   async downloadsStart(topicName, merkleRoot, outputPath) {
     this.swarmfs.open()
     const t = String(topicName || '')
@@ -163,6 +337,9 @@ export class NodeRuntime extends EventEmitter {
 
   _startDownloadAsync(topic, merkleRoot, outputPath) {
     const absOutputPath = path.resolve(String(outputPath || ''))
+    const key = this._downloadKey(topic, merkleRoot, absOutputPath)
+    const entry = { session: null }
+    this._sessions.set(key, entry)
     void (async () => {
       try {
         this.swarmfs.open()
@@ -176,9 +353,18 @@ export class NodeRuntime extends EventEmitter {
           onProgress: (info) => this._emitDownloadProgress(topic, merkleRoot, absOutputPath, info),
           onComplete: (info) => this._emitDownloadComplete(topic, merkleRoot, absOutputPath, info),
           onError: (err) => this._emitDownloadError(topic, merkleRoot, absOutputPath, err),
+          onSession: (session) => {
+            if (this._sessions.get(key) === entry) {
+              entry.session = session
+            } else {
+              void session.stop()
+            }
+          },
         })
       } catch (e) {
         this._emitDownloadError(topic, merkleRoot, absOutputPath, e)
+      } finally {
+        if (this._sessions.get(key) === entry) this._sessions.delete(key)
       }
     })()
   }
@@ -289,7 +475,7 @@ export class NodeRuntime extends EventEmitter {
     return { ok: true, results }
   }
 
-  filesRemove(filePath) {
+  filesRemove(filePath, { deleteFromDisk = false } = {}) {
     this.swarmfs.open()
     const p = String(filePath || '')
     if (!p) throw new Error('path required')
@@ -305,10 +491,29 @@ export class NodeRuntime extends EventEmitter {
     this.swarmfs.db.removeTopicSharesByPath(abs)
     if (file) {
       this.swarmfs.removeFile(abs)
-      return { removed: true, type: 'file' }
+      if (deleteFromDisk) fs.rmSync(abs, { force: true })
+      return { removed: true, type: 'file', deleted: deleteFromDisk }
     }
     this.swarmfs.removeDirectory(abs)
-    return { removed: true, type: 'directory' }
+    if (!deleteFromDisk) return { removed: true, type: 'directory', deleted: false }
+
+    // Only files SwarmFS tracks are deleted; anything else under the directory stays on disk,
+    // along with the directories that still hold it.
+    const prefix = abs.endsWith(path.sep) ? abs : abs + path.sep
+    const tracked = this.swarmfs.db.getAllFiles().filter((f) => f.path.startsWith(prefix))
+    for (const f of tracked) {
+      this.swarmfs.db.removeTopicSharesByPath(f.path)
+      this.swarmfs.removeFile(f.path)
+      fs.rmSync(f.path, { force: true })
+    }
+    // Tracked subdirectories lost all their tracked files above; untrack them and their shares.
+    for (const d of this.swarmfs.db.getAllDirectories()) {
+      if (!d.path.startsWith(prefix)) continue
+      this.swarmfs.db.removeTopicSharesByPath(d.path)
+      this.swarmfs.removeDirectory(d.path)
+    }
+    const deleted = pruneEmptyDirs(abs)
+    return { removed: true, type: 'directory', deleted, deletedFiles: tracked.length }
   }
 
   _installNetworkHandlersIfNeeded() {
@@ -541,13 +541,7 @@ export class SwarmFS {
       throw new Error(`Topic "${name}" already exists`);
     }
 
-    const secret = (typeof password === 'string' && password.length > 0)
-      ? password
-      : SwarmFS.DEFAULT_TOPIC_SALT
-
-    // Generate topic key from (name + secret) (deterministic)
-    // Using a SwarmFS-specific salt prevents collisions with other Hyperswarm apps.
-    const topicKey = blake3.hashHex(`${secret}\n${name}`)
+    const topicKey = SwarmFS.topicKeyFor(name, password)
 
     // Add to database
     const topicId = this.db.addTopic(name, topicKey, autoJoin);
@@ -560,6 +554,50 @@ export class SwarmFS {
     };
   }
 
+  /**
+   * Generate topic key from (name + secret) (deterministic)
+   * Using a SwarmFS-specific salt prevents collisions with other Hyperswarm apps.
+   */
+  static topicKeyFor(name, password = null) {
+    const secret = (typeof password === 'string' && password.length > 0)
+      ? password
+      : SwarmFS.DEFAULT_TOPIC_SALT
+    return blake3.hashHex(`${secret}\n${name}`)
+  }
+
+  /**
+   * Update a topic's auto-join flag; a non-empty password also replaces its key.
+   * A joined topic is rejoined under the new key.
+   */
+  async updateTopic(name, autoJoin, password = null) {
+    const topic = this.db.getTopic(name);
+    if (!topic) {
+      throw new Error(`Topic "${name}" not found`);
+    }
+
+    const topicKey = (typeof password === 'string' && password.length > 0)
+      ? SwarmFS.topicKeyFor(name, password)
+      : topic.topic_key;
+    const rekeyed = topicKey !== topic.topic_key;
+    const rejoin = rekeyed && !!this.network?.topics?.has(topic.topic_key);
+
+    if (rejoin) {
+      await this.leaveTopic(name);
+    }
+    this.db.updateTopic(name, topicKey, autoJoin);
+    if (rejoin) {
+      await this.joinTopic(name);
+    }
+
+    return {
+      id: topic.id,
+      name,
+      topicKey,
+      autoJoin,
+      rekeyed
+    };
+  }
+
   /**
    * List all topics
    */
@@ -948,6 +986,10 @@ export class SwarmFS {
 
   session.fileId = outputFileId;
 
+  if (typeof options.onSession === 'function') {
+    options.onSession(session);
+  }
+
   // Forward progress events
   if (typeof options.onProgress === 'function') {
     session.on('progress', (info) => {
@@ -972,6 +1014,9 @@ export class SwarmFS {
 
   // Start download
   await session.start();
+  if (session.stopped) {
+    return { path: absoluteOutputPath, stopped: true };
+  }
 
   // Wait for completion
   return new Promise((resolve, reject) => {
@@ -989,6 +1034,10 @@ export class SwarmFS {
     session.on('error', (error) => {
       reject(error);
     });
+
+    session.on('stopped', () => {
+      resolve({ path: absoluteOutputPath, stopped: true });
+    });
   });
 }
 }
@@ -1,6 +1,8 @@
 import { test, before, after } from 'node:test'
 import assert from 'node:assert/strict'
 import { spawn } from 'node:child_process'
+import fs from 'node:fs'
+import os from 'node:os'
 import path from 'node:path'
 
 import { getDataDir } from '../src/config.js'
@@ -90,6 +92,20 @@ test('multi-client status works', async () => {
   assert.equal(typeof b.dbOpen, 'boolean')
 })
 
+test('node.status reports version, uptime, peer id and listen addresses', async () => {
+  const pkg = JSON.parse(fs.readFileSync(path.join(REPO_ROOT, 'package.json'), 'utf8'))
+  const sock = await connectIpc(endpoint)
+  const client = createRpcClient(sock)
+  const res = await client.rpc('node.status', {})
+  sock.destroy()
+
+  assert.equal(res.version, pkg.version)
+  assert.equal(typeof res.uptimeSec, 'number')
+  assert.ok(res.peerId === null || /^[0-9a-f]{64}$/.test(res.peerId))
+  assert.ok(Array.isArray(res.listenAddresses))
+  assert.ok(res.listenAddresses.every((a) => typeof a === 'string'))
+})
+
 test('event subscription yields network.stats events (may be null if no topics joined)', async () => {
   const sock = await connectIpc(endpoint)
   const client = createRpcClient(sock)
@@ -108,3 +124,96 @@ test('event subscription yields network.stats events (may be null if no topics j
   // We should see at least one stats tick, unless the daemon crashed.
   assert.ok(events.some((e) => e && e.event === 'network.stats'))
 })
+
+test('files.remove with delete only deletes tracked files and prunes empty dirs', async () => {
+  const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'swarmfs-remove-'))
+  fs.mkdirSync(path.join(dir, 'sub'))
+  fs.writeFileSync(path.join(dir, 'a.txt'), 'a')
+  fs.writeFileSync(path.join(dir, 'sub', 'b.txt'), 'b')
+
+  const sock = await connectIpc(endpoint)
+  const client = createRpcClient(sock)
+  try {
+    await client.rpc('files.add', { paths: [dir] })
+    // Added after tracking, so SwarmFS does not own it.
+    fs.writeFileSync(path.join(dir, 'notes.txt'), 'keep me')
+
+    const res = await client.rpc('files.remove', { path: dir, delete: true })
+    assert.equal(res.type, 'directory')
+    assert.equal(res.deletedFiles, 2)
+    assert.equal(res.deleted, false)
+    assert.ok(!fs.existsSync(path.join(dir, 'a.txt')))
+    assert.ok(!fs.existsSync(path.join(dir, 'sub')))
+    assert.equal(fs.readFileSync(path.join(dir, 'notes.txt'), 'utf8'), 'keep me')
+
+    const listed = await client.rpc('files.list', {})
+    assert.ok(!listed.files.some((f) => f.path.startsWith(dir + path.sep)))
+    assert.ok(!listed.dirs.some((d) => d.path === dir || d.path === path.join(dir, 'sub')))
+  } finally {
+    sock.destroy()
+    fs.rmSync(dir, { recursive: true, force: true })
+  }
+})
+
+test('per-download pause/resume/cancel report unknown ids as not_found', async () => {
+  const sock = await connectIpc(endpoint)
+  const client = createRpcClient(sock)
+  try {
+    for (const method of ['downloads.pause', 'downloads.resume', 'downloads.cancel']) {
+      await assert.rejects(client.rpc(method, { id: -1 }), (e) => e.code === 'not_found')
+    }
+    const listed = await client.rpc('downloads.list', {})
+    assert.ok(Array.isArray(listed))
+  } finally {
+    sock.destroy()
+  }
+})
+
+test('topic.update changes autoJoin, keeps the key for a blank password and broadcasts', async () => {
+  const name = `update-test-${process.pid}-${Date.now()}`
+  const sock = await connectIpc(endpoint)
+  const client = createRpcClient(sock)
+  const events = []
+  client.onEvent((evt) => events.push(evt))
+  try {
+    await client.rpc('events.subscribe', { channels: ['state'] })
+    const created = await client.rpc('topic.create', { name, autoJoin: true, password: null })
+
+    const kept = await client.rpc('topic.update', { name, autoJoin: false, password: null })
+    assert.equal(kept.topicKey, created.topicKey)
+    assert.equal(kept.autoJoin, false)
+    assert.equal(kept.rekeyed, false)
+
+    const rekeyed = await client.rpc('topic.update', { name, autoJoin: false, password: 'hunter2' })
+    assert.notEqual(rekeyed.topicKey, created.topicKey)
+    assert.equal(rekeyed.rekeyed, true)
+
+    const overview = await client.rpc('network.overview', {})
+    const row = overview.topics.find((t) => t.name === name)
+    assert.equal(row.autoJoin, false)
+    assert.equal(row.topicKey, rekeyed.topicKey)
+
+    await sleep(100)
+    assert.ok(events.some((e) => e.event === 'state.topics' && e.data?.op === 'update' && e.data?.name === name))
+
+    await assert.rejects(client.rpc('topic.update', { name: `${name}-missing` }), (e) => e.code === 'not_found')
+  } finally {
+    await client.rpc('topic.rm', { name }).catch(() => {})
+    sock.destroy()
+  }
+})
+
+test('topic.peers lists no peers for an unjoined topic and rejects unknown ones', async () => {
+  const name = `peers-test-${process.pid}-${Date.now()}`
+  const sock = await connectIpc(endpoint)
+  const client = createRpcClient(sock)
+  try {
+    await client.rpc('topic.create', { name, autoJoin: false, password: null })
+    const res = await client.rpc('topic.peers', { name })
+    assert.deepEqual(res, { joined: false, peers: [] })
+    await assert.rejects(client.rpc('topic.peers', { name: `${name}-missing` }), (e) => e.code === 'not_found')
+  } finally {
+    await client.rpc('topic.rm', { name }).catch(() => {})
+    sock.destroy()
+  }
+})
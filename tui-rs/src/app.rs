@@ -1,20 +1,126 @@
+use crate::config::TuiSettings;
 use crate::ipc::{DaemonEvent, IpcClient};
-use crate::tabs::TabId;
+use crate::palette::CommandPalette;
+use crate::tabs::common::{now_ms, value_as_u64};
+use crate::tabs::{TabId, UiCommand};
+use crate::widgets::{ConfirmDialog, RowJump};
 use anyhow::Result;
 use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often `node.status` is re-fetched while the TUI is running.
+pub const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Oldest daemon version this TUI was built against; older daemons get a warning banner.
+/// 0.0.2 added `node.status` identity fields, `topic.update`, `topic.peers` and per-download
+/// pause/resume/cancel.
+pub const MIN_DAEMON_VERSION: &str = "0.0.2";
+
+/// Intervals cycled by the auto-refresh key; `None` is off.
+pub const AUTO_REFRESH_STEPS: [Option<Duration>; 4] = [
+    None,
+    Some(Duration::from_secs(2)),
+    Some(Duration::from_secs(5)),
+    Some(Duration::from_secs(10)),
+];
+
+/// `network.stats` arrive about once a second; this long without one means the stream stalled.
+pub const STATS_STALE_AFTER_MS: i64 = 15_000;
+
+/// Overall connectivity shown as a colored dot in the tab bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    /// IPC and events up, at least one peer.
+    Good,
+    /// Connected, but no peers or the stats stream went quiet.
+    Degraded,
+    /// IPC connection or event stream down.
+    Down,
+}
+
+/// `stats_age_ms` is `None` until the first `network.stats` event.
+pub fn connection_health(connected: bool, peers: Option<u64>, stats_age_ms: Option<i64>) -> Health {
+    if !connected {
+        Health::Down
+    } else if peers.unwrap_or(0) == 0 || stats_age_ms.is_some_and(|a| a > STATS_STALE_AFTER_MS) {
+        Health::Degraded
+    } else {
+        Health::Good
+    }
+}
+
+/// How long a toast stays in the footer.
+pub const TOAST_TTL: Duration = Duration::from_secs(4);
+const TOASTS_MAX: usize = 8;
+
+/// Transient feedback shown in the footer, e.g. "topic key copied".
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub level: LogLevel,
+    pub at: Instant,
+}
 
 pub struct App {
     pub should_quit: bool,
     pub active_tab: TabId,
 
     pub status_json: serde_json::Value,
+    /// Parsed view of `status_json`; `None` until the first successful fetch.
+    pub node_status: Option<NodeStatus>,
+    pub status_fetched_at: Option<Instant>,
+    // In-flight background `node.status` fetch.
+    status_rx: Option<Receiver<Result<serde_json::Value, String>>>,
+    /// Daemon version warning shown above the tabs until dismissed with `X`.
+    pub version_banner: Option<String>,
+    pub version_banner_dismissed: bool,
 
     pub logs: VecDeque<LogEntry>,
     pub logs_max: usize,
 
+    /// Pending confirmation for a destructive command.
+    pub confirm: ConfirmDialog<UiCommand>,
+    /// `:` command palette.
+    pub palette: CommandPalette,
+
+    /// Distinct recent warnings/errors awaiting acknowledgement.
+    pub attention: Vec<AttentionItem>,
+    pub attention_max: usize,
+
+    /// Recent toasts, oldest first; expired ones are dropped when the footer is drawn.
+    pub toasts: VecDeque<Toast>,
+
+    /// Numeric "go to row" entry shared by the table tabs; shown in the footer while typing.
+    pub row_jump: RowJump,
+
     pub network: NetworkState,
 
     pub ui: UiState,
+
+    pub settings: TuiSettings,
+
+    /// Monitoring mode: commands that change daemon state are refused.
+    pub read_only: bool,
+
+    /// False while the IPC connection to the daemon is being re-established.
+    pub ipc_connected: bool,
+    /// When the current daemon connection was established; `None` while disconnected.
+    pub connected_since: Option<Instant>,
+
+    /// Periodic refresh of the active tab; `None` when off.
+    pub auto_refresh: Option<Duration>,
+    pub last_refresh: Instant,
+
+    /// Root of the repo whose daemon we are connected to.
+    pub repo_root: PathBuf,
+    /// IPC endpoint (socket or pipe path) of that repo's daemon.
+    pub endpoint: String,
+    /// Repos offered by the repo switcher; re-read each time it opens.
+    pub repos: Vec<PathBuf>,
 }
 
 impl App {
@@ -23,24 +129,159 @@ impl App {
             should_quit: false,
             active_tab: TabId::Network,
             status_json: serde_json::Value::Null,
+            node_status: None,
+            status_fetched_at: None,
+            status_rx: None,
+            version_banner: None,
+            version_banner_dismissed: false,
             logs: VecDeque::new(),
             logs_max: 5000,
+            confirm: ConfirmDialog::default(),
+            palette: CommandPalette::default(),
+            attention: Vec::new(),
+            attention_max: 50,
             network: NetworkState::default(),
+            toasts: VecDeque::new(),
+            row_jump: RowJump::default(),
             ui: UiState::default(),
+            settings: TuiSettings::default(),
+            read_only: false,
+            ipc_connected: true,
+            connected_since: Some(Instant::now()),
+            auto_refresh: None,
+            last_refresh: Instant::now(),
+            repo_root: PathBuf::new(),
+            endpoint: String::new(),
+            repos: Vec::new(),
+        }
+    }
+
+    /// Shows `message` in the footer for [`TOAST_TTL`]. Use for transient feedback; persistent
+    /// failures belong in a tab's error line.
+    pub fn toast(&mut self, level: LogLevel, message: impl Into<String>) {
+        if self.toasts.len() == TOASTS_MAX {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            message: message.into(),
+            level,
+            at: Instant::now(),
+        });
+    }
+
+    /// Newest unexpired toast and how many others are still live.
+    pub fn current_toast(&mut self) -> Option<(&Toast, usize)> {
+        self.toasts.retain(|t| t.at.elapsed() < TOAST_TTL);
+        let live = self.toasts.len();
+        self.toasts.back().map(|t| (t, live - 1))
+    }
+
+    pub fn open_repo_switcher(&mut self) {
+        self.repos = crate::config::configured_repos(&self.repo_root);
+        self.ui.repos_selected = self.repos.iter().position(|r| *r == self.repo_root).unwrap_or(0);
+        self.ui.repos_open = true;
+    }
+
+    /// Drops daemon state cached from the previous repo after switching endpoints.
+    pub fn reset_for_repo(&mut self, repo_root: PathBuf, endpoint: String) {
+        self.repo_root = repo_root;
+        self.endpoint = endpoint;
+        self.status_json = serde_json::Value::Null;
+        self.node_status = None;
+        self.status_fetched_at = None;
+        // A fetch still running against the old daemon is dropped.
+        self.status_rx = None;
+        self.version_banner = None;
+        self.version_banner_dismissed = false;
+        self.network = NetworkState::default();
+        self.connected_since = Some(Instant::now());
+    }
+
+    /// Tracks the IPC link; a reconnect restarts the connection clock.
+    pub fn set_ipc_connected(&mut self, connected: bool) {
+        self.ipc_connected = connected;
+        if !connected {
+            self.connected_since = None;
+        } else if self.connected_since.is_none() {
+            self.connected_since = Some(Instant::now());
+        }
+    }
+
+    pub fn cycle_auto_refresh(&mut self) {
+        let pos = AUTO_REFRESH_STEPS
+            .iter()
+            .position(|s| *s == self.auto_refresh)
+            .unwrap_or(0);
+        self.auto_refresh = AUTO_REFRESH_STEPS[(pos + 1) % AUTO_REFRESH_STEPS.len()];
+        self.last_refresh = Instant::now();
+    }
+
+    /// True once the auto-refresh interval has elapsed; restarts the interval when it fires.
+    pub fn take_auto_refresh_due(&mut self) -> bool {
+        match self.auto_refresh {
+            Some(every) if self.last_refresh.elapsed() >= every => {
+                self.last_refresh = Instant::now();
+                true
+            }
+            _ => false,
         }
     }
 
     pub fn set_active_tab(&mut self, tab: TabId) {
         self.active_tab = tab;
+        self.row_jump.clear();
     }
 
     pub fn push_log(&mut self, entry: LogEntry) {
+        if matches!(entry.level, LogLevel::Warn | LogLevel::Error) {
+            self.note_attention(&entry);
+        }
         self.logs.push_back(entry);
         while self.logs.len() > self.logs_max {
             self.logs.pop_front();
         }
     }
 
+    fn note_attention(&mut self, entry: &LogEntry) {
+        // Repeats of the same message bump the existing item to the end instead of piling up.
+        if let Some(pos) = self
+            .attention
+            .iter()
+            .position(|a| a.level == entry.level && a.message == entry.message)
+        {
+            let mut item = self.attention.remove(pos);
+            item.count += 1;
+            item.last_ts = entry.ts;
+            self.attention.push(item);
+            return;
+        }
+
+        self.attention.push(AttentionItem {
+            level: entry.level.clone(),
+            message: entry.message.clone(),
+            count: 1,
+            last_ts: entry.ts,
+        });
+        while self.attention.len() > self.attention_max {
+            self.attention.remove(0);
+        }
+    }
+
+    pub fn acknowledge_attention(&mut self, idx: usize) {
+        if idx < self.attention.len() {
+            self.attention.remove(idx);
+        }
+        self.ui.attention_selected = self
+            .ui
+            .attention_selected
+            .min(self.attention.len().saturating_sub(1));
+    }
+
+    pub fn acknowledge_all_attention(&mut self) {
+        self.attention.clear();
+        self.ui.attention_selected = 0;
+    }
+
     pub fn on_daemon_event(&mut self, evt: DaemonEvent) {
         match evt {
             DaemonEvent::Log(e) => {
@@ -57,16 +298,120 @@ impl App {
     pub fn refresh_basics(&mut self, ipc: &mut IpcClient) -> Result<()> {
         // Keep this small and safe; tabs can request additional refreshes.
         if let Ok(v) = ipc.rpc("node.status", serde_json::json!({})) {
-            self.status_json = v;
+            self.apply_status(v);
         }
+        self.status_fetched_at = Some(Instant::now());
         Ok(())
     }
+
+    fn apply_status(&mut self, v: serde_json::Value) {
+        self.node_status = NodeStatus::try_from(v.clone()).ok();
+        self.status_json = v;
+        if !self.version_banner_dismissed {
+            self.version_banner = self.node_status.as_ref().and_then(version_warning);
+        }
+    }
+
+    /// Connected peers from the latest stats, falling back to `node.status`.
+    pub fn peer_count(&self) -> Option<u64> {
+        self.network
+            .stats
+            .as_ref()
+            .map(|s| s.peers)
+            .or_else(|| self.node_status.as_ref().and_then(|s| s.peer_count))
+    }
+
+    pub fn health(&self) -> Health {
+        let stats_age = (self.network.stats_at_ms > 0).then(|| now_ms() as i64 - self.network.stats_at_ms);
+        connection_health(self.ipc_connected, self.peer_count(), stats_age)
+    }
+
+    /// Applies a finished background `node.status` fetch, and starts the next one on a worker
+    /// once `STATUS_REFRESH_INTERVAL` has passed since the last attempt.
+    pub fn refresh_status_if_due(&mut self) {
+        if let Some(rx) = &self.status_rx {
+            match rx.try_recv() {
+                Ok(Ok(v)) => self.apply_status(v),
+                Ok(Err(_)) | Err(TryRecvError::Disconnected) => {}
+                Err(TryRecvError::Empty) => return,
+            }
+            self.status_rx = None;
+        }
+
+        let due = self
+            .status_fetched_at
+            .is_none_or(|t| t.elapsed() >= STATUS_REFRESH_INTERVAL);
+        if !due || !self.ipc_connected {
+            return;
+        }
+        self.status_fetched_at = Some(Instant::now());
+        let endpoint = self.endpoint.clone();
+        let (tx, rx) = mpsc::channel();
+        self.status_rx = Some(rx);
+        thread::spawn(move || {
+            let res = IpcClient::connect(endpoint)
+                .and_then(|mut c| c.rpc("node.status", serde_json::json!({})))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(res);
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Other(String),
+}
+
+impl LogLevel {
+    /// Parses a daemon level string case-insensitively, accepting common aliases
+    /// (`warning`, `err`). Unknown values are preserved in `Other`.
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "trace" => LogLevel::Trace,
+            "debug" => LogLevel::Debug,
+            "info" => LogLevel::Info,
+            "warn" | "warning" => LogLevel::Warn,
+            "error" | "err" => LogLevel::Error,
+            _ => LogLevel::Other(s.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Other(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AttentionItem {
+    pub level: LogLevel,
+    pub message: String,
+    /// How many times this exact message was logged since it was last acknowledged.
+    pub count: usize,
+    pub last_ts: i64,
 }
 
 #[derive(Debug, Clone)]
 pub struct LogEntry {
     pub ts: i64,
-    pub level: String,
+    pub level: LogLevel,
     pub message: String,
 }
 
@@ -76,11 +421,7 @@ impl TryFrom<serde_json::Value> for LogEntry {
     fn try_from(v: serde_json::Value) -> Result<Self, Self::Error> {
         Ok(Self {
             ts: v.get("ts").and_then(|x| x.as_i64()).unwrap_or(0),
-            level: v
-                .get("level")
-                .and_then(|x| x.as_str())
-                .unwrap_or("info")
-                .to_string(),
+            level: LogLevel::parse(v.get("level").and_then(|x| x.as_str()).unwrap_or("info")),
             message: v
                 .get("message")
                 .and_then(|x| x.as_str())
@@ -90,23 +431,240 @@ impl TryFrom<serde_json::Value> for LogEntry {
     }
 }
 
-#[derive(Default)]
+/// Parses "1.2.3", "v1.2", "1.2.3-beta+sha" into (major, minor, patch); missing parts are 0.
+pub fn parse_version(s: &str) -> Option<(u64, u64, u64)> {
+    let s = s.trim();
+    let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    let core = s.split(['-', '+', ' ']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+/// Banner text when the daemon reports a version older than [`MIN_DAEMON_VERSION`], or none at
+/// all (daemons before 0.0.2 did not report one). Unparseable versions are given the benefit of
+/// the doubt.
+pub fn version_warning(status: &NodeStatus) -> Option<String> {
+    let Some(reported) = status.version.as_deref() else {
+        return Some(format!(
+            "daemon does not report a version (older than {}); some features may not work",
+            MIN_DAEMON_VERSION
+        ));
+    };
+    let min = parse_version(MIN_DAEMON_VERSION)?;
+    (parse_version(reported)? < min).then(|| {
+        format!(
+            "daemon {} is older than {} (this TUI's minimum); some features may not work",
+            reported, MIN_DAEMON_VERSION
+        )
+    })
+}
+
+/// Typed view of the `node.status` RPC result.
+///
+/// `peerId` is null and `listenAddresses` empty until the daemon's network is up; daemons older
+/// than [`MIN_DAEMON_VERSION`] send none of version, uptime, peer id or addresses.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeStatus {
+    pub peer_id: Option<String>,
+    pub version: Option<String>,
+    pub uptime_secs: Option<u64>,
+    pub listen_addresses: Vec<String>,
+    pub data_dir: Option<String>,
+    pub db_open: bool,
+    pub network_running: bool,
+    pub protocol_running: bool,
+    pub peer_count: Option<u64>,
+}
+
+impl TryFrom<serde_json::Value> for NodeStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(v: serde_json::Value) -> Result<Self, Self::Error> {
+        if !v.is_object() {
+            anyhow::bail!("node.status: expected an object");
+        }
+        let str_field = |k: &str| v.get(k).and_then(|x| x.as_str()).map(|s| s.to_string());
+        let flag = |k: &str| v.get(k).and_then(|x| x.as_bool()).unwrap_or(false);
+
+        let listen_addresses = v
+            .get("listenAddresses")
+            .and_then(|x| x.as_array())
+            .map(|a| a.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            peer_id: str_field("peerId"),
+            version: str_field("version"),
+            uptime_secs: v.get("uptimeSec").and_then(value_as_u64),
+            listen_addresses,
+            data_dir: str_field("dataDir"),
+            db_open: flag("dbOpen"),
+            network_running: flag("networkRunning"),
+            protocol_running: flag("protocolRunning"),
+            peer_count: v
+                .get("networkStats")
+                .and_then(|s| s.get("peerCount"))
+                .and_then(value_as_u64),
+        })
+    }
+}
+
+/// Typed view of a `network.stats` payload.
+///
+/// `peerCount` is required; anything without it is treated as an unknown shape and shown as raw
+/// JSON. Byte counters are only reported by some daemon builds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkStats {
+    pub peers: u64,
+    pub topics_joined: u64,
+    pub connections: Option<u64>,
+    pub bytes_up: Option<u64>,
+    pub bytes_down: Option<u64>,
+}
+
+impl TryFrom<serde_json::Value> for NetworkStats {
+    type Error = anyhow::Error;
+
+    fn try_from(v: serde_json::Value) -> Result<Self, Self::Error> {
+        let peers = v
+            .get("peerCount")
+            .or_else(|| v.get("peers"))
+            .and_then(value_as_u64)
+            .ok_or_else(|| anyhow::anyhow!("network.stats: missing peerCount"))?;
+        let count = |keys: &[&str]| keys.iter().find_map(|k| v.get(*k).and_then(value_as_u64));
+        // `activeTopics` is the list of joined topic names; older daemons only send `topics`.
+        let topics_joined = v
+            .get("activeTopics")
+            .and_then(|x| x.as_array())
+            .map(|a| a.len() as u64)
+            .or_else(|| count(&["topics"]))
+            .unwrap_or(0);
+
+        Ok(Self {
+            peers,
+            topics_joined,
+            connections: count(&["connections"]),
+            bytes_up: count(&["bytesUp", "bytesSent", "uploaded"]),
+            bytes_down: count(&["bytesDown", "bytesReceived", "downloaded"]),
+        })
+    }
+}
+
+/// Samples kept for the Network tab's stats sparkline (the daemon emits one per second).
+pub const STATS_HISTORY_MAX: usize = 120;
+
+/// Numeric `network.stats` fields the sparkline can track, in cycling order.
+pub const STATS_METRICS: &[&str] = &["peerCount", "connections", "topics"];
+
 pub struct NetworkState {
     pub stats_json: Option<serde_json::Value>,
+    /// `stats_json` parsed into the common fields; `None` for unknown shapes.
+    pub stats: Option<NetworkStats>,
+    /// When `stats` was received, for deriving rates from the byte counters.
+    pub stats_at_ms: i64,
+    /// `(up, down)` bytes per second between the last two samples with byte counters.
+    pub bandwidth: Option<(f64, f64)>,
+    /// `(unix ms, value)` samples of `stats_metric`, oldest first.
+    pub stats_history: VecDeque<(i64, f64)>,
+    pub stats_metric: &'static str,
+}
+
+impl Default for NetworkState {
+    fn default() -> Self {
+        Self {
+            stats_json: None,
+            stats: None,
+            stats_at_ms: 0,
+            bandwidth: None,
+            stats_history: VecDeque::new(),
+            stats_metric: STATS_METRICS[0],
+        }
+    }
 }
 
 impl NetworkState {
     pub fn on_event(&mut self, evt: &crate::ipc::NetworkEvent) {
         if let crate::ipc::NetworkEvent::Stats(v) = evt {
+            let now = now_ms() as i64;
+            if let Some(sample) = stats_metric_value(v, self.stats_metric) {
+                self.push_sample(now, sample);
+            }
+            if self.stats_json.as_ref() == Some(v) {
+                // Unchanged counters mean nothing moved since the last sample.
+                if self.bandwidth.is_some() {
+                    self.bandwidth = Some((0.0, 0.0));
+                }
+                self.stats_at_ms = now;
+                return;
+            }
+            let stats = NetworkStats::try_from(v.clone()).ok();
+            self.bandwidth = match (&self.stats, &stats) {
+                (Some(prev), Some(cur)) => bandwidth_between(prev, cur, now - self.stats_at_ms),
+                _ => None,
+            };
+            self.stats = stats;
+            self.stats_at_ms = now;
             self.stats_json = Some(v.clone());
         }
     }
+
+    fn push_sample(&mut self, ts_ms: i64, value: f64) {
+        if self.stats_history.len() == STATS_HISTORY_MAX {
+            self.stats_history.pop_front();
+        }
+        self.stats_history.push_back((ts_ms, value));
+    }
+
+    /// Tracks the next entry of [`STATS_METRICS`]; samples of the old metric are dropped.
+    pub fn cycle_stats_metric(&mut self) {
+        let i = STATS_METRICS.iter().position(|m| *m == self.stats_metric).unwrap_or(0);
+        self.stats_metric = STATS_METRICS[(i + 1) % STATS_METRICS.len()];
+        self.stats_history.clear();
+    }
+}
+
+/// Upload/download rates from two byte-counter samples `elapsed_ms` apart. Counter resets
+/// (a daemon restart) yield `None` rather than a negative rate.
+fn bandwidth_between(prev: &NetworkStats, cur: &NetworkStats, elapsed_ms: i64) -> Option<(f64, f64)> {
+    if elapsed_ms <= 0 {
+        return None;
+    }
+    let secs = elapsed_ms as f64 / 1000.0;
+    let rate = |a: Option<u64>, b: Option<u64>| Some(b?.checked_sub(a?)? as f64 / secs);
+    Some((rate(prev.bytes_up, cur.bytes_up)?, rate(prev.bytes_down, cur.bytes_down)?))
+}
+
+/// Reads the metric `key` from a `network.stats` payload. Arrays count as their length.
+pub fn stats_metric_value(stats: &serde_json::Value, key: &str) -> Option<f64> {
+    let v = stats.get(key)?;
+    if let Some(arr) = v.as_array() {
+        return Some(arr.len() as f64);
+    }
+    v.as_f64().or_else(|| value_as_u64(v).map(|n| n as f64))
 }
 
 #[derive(Default)]
 pub struct UiState {
     // Populated on each draw pass.
     pub tab_hitboxes: Vec<TabHitbox>,
+
+    // Attention (warnings/errors) overlay.
+    pub attention_open: bool,
+    pub attention_selected: usize,
+
+    // Node status overlay.
+    pub status_open: bool,
+
+    // Key binding help overlay.
+    pub help_open: bool,
+    pub help_scroll: usize,
+
+    // Repo switcher overlay.
+    pub repos_open: bool,
+    pub repos_selected: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -117,3 +675,253 @@ pub struct TabHitbox {
     pub y0: u16,
     pub y1: u16,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_stats_parses_daemon_payload() {
+        let stats = NetworkStats::try_from(serde_json::json!({
+            "topics": 3,
+            "peerCount": 4,
+            "connections": 6,
+            "activeTopics": ["a", "b"],
+            "topicsDetails": [{ "name": "a", "peers": 4 }, { "name": "b", "peers": 2 }]
+        }))
+        .unwrap();
+        assert_eq!(
+            stats,
+            NetworkStats { peers: 4, topics_joined: 2, connections: Some(6), bytes_up: None, bytes_down: None }
+        );
+
+        let stats = NetworkStats::try_from(serde_json::json!({ "peers": "2", "bytesSent": 10, "bytesReceived": 20 }))
+            .unwrap();
+        assert_eq!((stats.peers, stats.bytes_up, stats.bytes_down), (2, Some(10), Some(20)));
+
+        assert!(NetworkStats::try_from(serde_json::json!({ "foo": 1 })).is_err());
+        assert!(NetworkStats::try_from(serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn reconnect_restarts_the_connection_clock() {
+        let mut app = App::new();
+        let first = app.connected_since.unwrap();
+        app.set_ipc_connected(true);
+        assert_eq!(app.connected_since, Some(first));
+        app.set_ipc_connected(false);
+        assert_eq!(app.connected_since, None);
+        app.set_ipc_connected(true);
+        assert!(app.connected_since.is_some_and(|t| t >= first));
+    }
+
+    #[test]
+    fn bandwidth_is_derived_from_byte_counters() {
+        let at = |up, down| NetworkStats { bytes_up: Some(up), bytes_down: Some(down), ..Default::default() };
+        assert_eq!(bandwidth_between(&at(0, 1000), &at(2048, 5000), 2000), Some((1024.0, 2000.0)));
+        assert_eq!(bandwidth_between(&at(100, 0), &at(50, 0), 1000), None);
+        assert_eq!(bandwidth_between(&NetworkStats::default(), &at(1, 1), 1000), None);
+    }
+
+    #[test]
+    fn stats_history_tracks_metric_and_stays_bounded() {
+        use crate::ipc::NetworkEvent;
+        let mut net = NetworkState::default();
+        for n in 0..STATS_HISTORY_MAX + 5 {
+            net.on_event(&NetworkEvent::Stats(serde_json::json!({ "peerCount": n, "topics": 2 })));
+        }
+        assert_eq!(net.stats_history.len(), STATS_HISTORY_MAX);
+        assert_eq!(net.stats_history.front().map(|s| s.1), Some(5.0));
+
+        net.cycle_stats_metric();
+        assert_eq!(net.stats_metric, "connections");
+        assert!(net.stats_history.is_empty());
+        net.on_event(&NetworkEvent::Stats(serde_json::json!({ "peerCount": 1 })));
+        assert!(net.stats_history.is_empty());
+
+        // Identical repeats still extend the history.
+        net.on_event(&NetworkEvent::Stats(serde_json::json!({ "peerCount": 1, "connections": 3 })));
+        net.on_event(&NetworkEvent::Stats(serde_json::json!({ "peerCount": 1, "connections": 3 })));
+        assert_eq!(net.stats_history.len(), 2);
+
+        assert_eq!(stats_metric_value(&serde_json::json!({ "activeTopics": ["a", "b"] }), "activeTopics"), Some(2.0));
+    }
+
+    #[test]
+    fn connection_health_levels() {
+        assert_eq!(connection_health(false, Some(3), Some(0)), Health::Down);
+        assert_eq!(connection_health(true, Some(3), Some(1000)), Health::Good);
+        assert_eq!(connection_health(true, Some(3), None), Health::Good);
+        assert_eq!(connection_health(true, Some(0), Some(1000)), Health::Degraded);
+        assert_eq!(connection_health(true, None, None), Health::Degraded);
+        assert_eq!(connection_health(true, Some(3), Some(STATS_STALE_AFTER_MS + 1)), Health::Degraded);
+    }
+
+    #[test]
+    fn toasts_show_newest_and_expire() {
+        let mut app = App::new();
+        assert!(app.current_toast().is_none());
+        app.toast(LogLevel::Info, "first");
+        app.toast(LogLevel::Warn, "second");
+        let (t, others) = app.current_toast().unwrap();
+        assert_eq!((t.message.as_str(), others), ("second", 1));
+
+        app.toasts.back_mut().unwrap().at -= TOAST_TTL;
+        app.toasts.front_mut().unwrap().at -= TOAST_TTL;
+        assert!(app.current_toast().is_none());
+    }
+
+    #[test]
+    fn log_level_parses_case_insensitively() {
+        assert_eq!(LogLevel::parse("WARN"), LogLevel::Warn);
+        assert_eq!(LogLevel::parse("Info"), LogLevel::Info);
+    }
+
+    #[test]
+    fn log_level_accepts_aliases() {
+        assert_eq!(LogLevel::parse("warning"), LogLevel::Warn);
+        assert_eq!(LogLevel::parse("err"), LogLevel::Error);
+    }
+
+    #[test]
+    fn log_level_keeps_unknown_values() {
+        let lvl = LogLevel::parse("fatal");
+        assert_eq!(lvl, LogLevel::Other("fatal".to_string()));
+        assert_eq!(lvl.to_string(), "fatal");
+    }
+
+    #[test]
+    fn log_entry_defaults_to_info() {
+        let e = LogEntry::try_from(serde_json::json!({"message": "hi"})).unwrap();
+        assert_eq!(e.level, LogLevel::Info);
+    }
+
+    fn entry(level: &str, message: &str) -> LogEntry {
+        LogEntry::try_from(serde_json::json!({"level": level, "message": message})).unwrap()
+    }
+
+    #[test]
+    fn attention_collects_warnings_and_dedupes() {
+        let mut app = App::new();
+        app.push_log(entry("info", "hello"));
+        app.push_log(entry("warn", "disk low"));
+        app.push_log(entry("error", "boom"));
+        app.push_log(entry("warn", "disk low"));
+
+        assert_eq!(app.attention.len(), 2);
+        assert_eq!(app.attention[0].message, "boom");
+        assert_eq!(app.attention[1].message, "disk low");
+        assert_eq!(app.attention[1].count, 2);
+    }
+
+    #[test]
+    fn attention_acknowledge_removes_single_item() {
+        let mut app = App::new();
+        app.push_log(entry("warn", "a"));
+        app.push_log(entry("warn", "b"));
+        app.ui.attention_selected = 1;
+        app.acknowledge_attention(1);
+
+        assert_eq!(app.attention.len(), 1);
+        assert_eq!(app.attention[0].message, "a");
+        assert_eq!(app.ui.attention_selected, 0);
+    }
+
+    #[test]
+    fn node_status_parses_current_daemon_shape() {
+        let v = serde_json::json!({
+            "dbOpen": true,
+            "networkRunning": true,
+            "protocolRunning": false,
+            "dataDir": "/tmp/swarmfs",
+            "networkStats": {"peerCount": 3.0}
+        });
+        let s = NodeStatus::try_from(v).unwrap();
+        assert!(s.db_open && s.network_running && !s.protocol_running);
+        assert_eq!(s.data_dir.as_deref(), Some("/tmp/swarmfs"));
+        assert_eq!(s.peer_count, Some(3));
+        assert_eq!(s.peer_id, None);
+        assert!(s.listen_addresses.is_empty());
+    }
+
+    #[test]
+    fn node_status_reads_identity_and_uptime() {
+        let v = serde_json::json!({
+            "peerId": "abc",
+            "version": "0.0.1",
+            "uptimeSec": 90,
+            "listenAddresses": ["0.0.0.0:4000", 7]
+        });
+        let s = NodeStatus::try_from(v).unwrap();
+        assert_eq!(s.peer_id.as_deref(), Some("abc"));
+        assert_eq!(s.version.as_deref(), Some("0.0.1"));
+        assert_eq!(s.uptime_secs, Some(90));
+        assert_eq!(s.listen_addresses, vec!["0.0.0.0:4000".to_string()]);
+
+        // Only the names the daemon sends are read.
+        let s = NodeStatus::try_from(serde_json::json!({"nodeId": "abc", "uptimeMs": 90_500})).unwrap();
+        assert_eq!((s.peer_id, s.uptime_secs), (None, None));
+    }
+
+    #[test]
+    fn node_status_rejects_non_objects() {
+        assert!(NodeStatus::try_from(serde_json::Value::Null).is_err());
+    }
+
+    #[test]
+    fn parse_version_is_lenient_and_warning_flags_old_or_missing_versions() {
+        assert_eq!(parse_version("v1.2.3-beta+abc"), Some((1, 2, 3)));
+        assert_eq!(parse_version(" 2.1 "), Some((2, 1, 0)));
+        assert_eq!(parse_version("dev"), None);
+        assert_eq!(parse_version("1.x"), None);
+
+        let status = |v: Option<&str>| NodeStatus { version: v.map(|s| s.to_string()), ..Default::default() };
+        assert!(version_warning(&status(Some("0.0.0"))).is_some());
+        assert!(version_warning(&status(Some(MIN_DAEMON_VERSION))).is_none());
+        assert!(version_warning(&status(Some("garbage"))).is_none());
+        assert!(version_warning(&status(None)).is_some());
+    }
+
+    #[test]
+    fn bundled_daemon_status_passes_the_version_check() {
+        // The daemon reports package.json's version in `node.status`.
+        let pkg: serde_json::Value = serde_json::from_str(include_str!("../../package.json")).unwrap();
+        let v = serde_json::json!({
+            "version": pkg["version"],
+            "uptimeSec": 12,
+            "peerId": null,
+            "listenAddresses": [],
+            "dbOpen": true,
+            "networkRunning": false,
+            "protocolRunning": false,
+            "dataDir": "/tmp/swarmfs",
+            "networkStats": null
+        });
+        let s = NodeStatus::try_from(v).unwrap();
+        assert_eq!(s.version.as_deref(), pkg["version"].as_str());
+        assert_eq!(version_warning(&s), None);
+
+        // The shape daemons sent before reporting a version.
+        let old = serde_json::json!({
+            "dbOpen": true,
+            "networkRunning": false,
+            "protocolRunning": false,
+            "dataDir": "/tmp/swarmfs",
+            "networkStats": null
+        });
+        assert!(version_warning(&NodeStatus::try_from(old).unwrap()).is_some());
+    }
+
+    #[test]
+    fn auto_refresh_cycles_through_steps_and_back_to_off() {
+        let mut app = App::new();
+        let mut seen = Vec::new();
+        for _ in 0..AUTO_REFRESH_STEPS.len() {
+            app.cycle_auto_refresh();
+            seen.push(app.auto_refresh);
+        }
+        assert_eq!(seen[0], Some(Duration::from_secs(2)));
+        assert_eq!(seen.last().copied(), Some(None));
+        assert!(!app.take_auto_refresh_due());
+    }
+}
@@ -0,0 +1,137 @@
+//! Non-interactive modes (`--status`, `--json`) that run before the terminal enters raw mode.
+
+use crate::app::NodeStatus;
+use crate::ipc::{is_read_method, IpcClient, LONG_RPC_TIMEOUT};
+use crate::ui::format_uptime;
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// `--status`: prints a short daemon summary from `node.status` and `network.overview`.
+pub fn run_status(endpoint: &str) -> Result<()> {
+    let mut ipc = IpcClient::connect(endpoint.to_string())?;
+    let status = NodeStatus::try_from(ipc.rpc("node.status", serde_json::json!({}))?)?;
+    let overview = ipc.rpc("network.overview", serde_json::json!({}))?;
+    print!("{}", status_summary(&status, &overview));
+    Ok(())
+}
+
+/// Parses `--json <method> [params-json]` out of the argument list.
+///
+/// Returns `Ok(None)` when `--json` is absent. Params default to `{}` and must be a JSON object or
+/// array, so a typo is reported before anything is sent to the daemon.
+pub fn parse_json_args(args: &[String]) -> Result<Option<(String, Value)>> {
+    let Some(pos) = args.iter().position(|a| a == "--json") else {
+        return Ok(None);
+    };
+    let Some(method) = args.get(pos + 1).filter(|m| !m.starts_with("--")) else {
+        anyhow::bail!("--json: missing <method>");
+    };
+    let params = match args.get(pos + 2).filter(|p| !p.starts_with("--")) {
+        None => serde_json::json!({}),
+        Some(raw) => {
+            let v: Value = serde_json::from_str(raw).with_context(|| format!("--json: invalid params {}", raw))?;
+            if !(v.is_object() || v.is_array()) {
+                anyhow::bail!("--json: params must be a JSON object or array");
+            }
+            v
+        }
+    };
+    Ok(Some((method.clone(), params)))
+}
+
+/// Methods that do real work daemon-side (hashing, joining, browsing peers).
+const LONG_METHODS: &[&str] = &["files.add", "files.verify", "topic.join", "browse.topic"];
+
+/// `--json`: performs one RPC and prints the raw result. In read-only mode only methods that
+/// leave daemon state alone are sent.
+pub fn run_json(endpoint: &str, method: &str, params: Value, read_only: bool) -> Result<()> {
+    if read_only && !is_read_method(method) {
+        anyhow::bail!("--json: {} changes daemon state; refused in read-only mode", method);
+    }
+    let mut ipc = IpcClient::connect(endpoint.to_string())?;
+    let v = if LONG_METHODS.contains(&method) {
+        ipc.rpc_with_timeout(method, params, LONG_RPC_TIMEOUT)?
+    } else {
+        ipc.rpc(method, params)?
+    };
+    println!("{}", serde_json::to_string_pretty(&v)?);
+    Ok(())
+}
+
+fn status_summary(status: &NodeStatus, overview: &Value) -> String {
+    let unknown = || "?".to_string();
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let topics = overview.get("topics").and_then(|x| x.as_array());
+    let joined = topics
+        .map(|a| a.iter().filter(|t| t.get("joined").and_then(|x| x.as_bool()) == Some(true)).count())
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    out.push_str(&format!("peer id:  {}\n", status.peer_id.clone().unwrap_or_else(unknown)));
+    out.push_str(&format!("version:  {}\n", status.version.clone().unwrap_or_else(unknown)));
+    out.push_str(&format!("uptime:   {}\n", status.uptime_secs.map(format_uptime).unwrap_or_else(unknown)));
+    out.push_str(&format!(
+        "running:  db {} | network {} | protocol {}\n",
+        yes_no(status.db_open),
+        yes_no(status.network_running),
+        yes_no(status.protocol_running)
+    ));
+    out.push_str(&format!(
+        "peers:    {}\n",
+        status.peer_count.map(|n| n.to_string()).unwrap_or_else(unknown)
+    ));
+    out.push_str(&format!("topics:   {} joined / {} known\n", joined, topics.map_or(0, |a| a.len())));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn status_summary_counts_joined_topics() {
+        let status = NodeStatus {
+            version: Some("1.2.0".to_string()),
+            uptime_secs: Some(125),
+            network_running: true,
+            peer_count: Some(3),
+            ..Default::default()
+        };
+        let overview = json!({"topics": [
+            {"name": "a", "joined": true},
+            {"name": "b", "joined": false},
+        ]});
+        let out = status_summary(&status, &overview);
+        assert!(out.contains("peer id:  ?\n"));
+        assert!(out.contains("uptime:   2m 5s\n"));
+        assert!(out.contains("db no | network yes | protocol no"));
+        assert!(out.contains("topics:   1 joined / 2 known\n"));
+    }
+
+    #[test]
+    fn json_refuses_state_changes_in_read_only_mode_before_connecting() {
+        let err = run_json("/nonexistent/swarmfs.sock", "topic.rm", json!({"name": "x"}), true).unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{}", err);
+        // Reads get as far as connecting.
+        let err = run_json("/nonexistent/swarmfs.sock", "node.status", json!({}), true).unwrap_err();
+        assert!(!err.to_string().contains("read-only"), "{}", err);
+    }
+
+    #[test]
+    fn parse_json_args_validates_params() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(parse_json_args(&args(&["--status"])).unwrap().is_none());
+        assert_eq!(
+            parse_json_args(&args(&["--json", "node.status"])).unwrap(),
+            Some(("node.status".to_string(), json!({})))
+        );
+        assert_eq!(
+            parse_json_args(&args(&["--json", "topic.join", r#"{"name":"a"}"#])).unwrap(),
+            Some(("topic.join".to_string(), json!({"name": "a"})))
+        );
+        assert!(parse_json_args(&args(&["--json"])).is_err());
+        assert!(parse_json_args(&args(&["--json", "x", "{oops"])).is_err());
+        assert!(parse_json_args(&args(&["--json", "x", "3"])).is_err());
+    }
+}
@@ -1,4 +1,9 @@
+use crate::file_picker::DEFAULT_DOUBLE_CLICK_MS;
+use crate::keybinds::KeyBindings;
+use crate::theme::Theme;
+use crate::widgets::DEFAULT_SCROLL_STEP;
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{fs, path::{Path, PathBuf}};
 
@@ -17,24 +22,254 @@ pub fn find_repo_root(start: &Path) -> Result<PathBuf> {
     }
 }
 
-pub fn load_config(repo_root: &Path) -> Result<Value> {
+/// Typed view of `swarmfs.config.json`. Only the keys the TUI relies on are validated; the
+/// rest of the document is kept in `raw` for optional sections such as `tui` and `theme`.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub data_dir: String,
+    pub chunk_size: Option<u64>,
+    pub ignore_patterns: Vec<String>,
+    /// Other repo roots offered by the repo switcher, relative to this repo's root.
+    pub repos: Vec<String>,
+    pub raw: Value,
+}
+
+impl TryFrom<Value> for Config {
+    type Error = anyhow::Error;
+
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        if !v.is_object() {
+            anyhow::bail!("the top level must be a JSON object");
+        }
+        let data_dir = match v.get("dataDir") {
+            None | Some(Value::Null) => "./swarmfs-data".to_string(),
+            Some(Value::String(s)) if s.trim().is_empty() => anyhow::bail!("dataDir must not be empty"),
+            Some(Value::String(s)) => s.clone(),
+            Some(_) => anyhow::bail!("dataDir must be a string"),
+        };
+        let chunk_size = match v.get("chunkSize") {
+            None | Some(Value::Null) => None,
+            Some(x) => match x.as_u64() {
+                Some(n) if n > 0 => Some(n),
+                _ => anyhow::bail!("chunkSize must be a positive integer"),
+            },
+        };
+        let ignore_patterns = match v.get("ignorePatterns") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(a)) => a
+                .iter()
+                .map(|p| p.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| anyhow::anyhow!("ignorePatterns must be an array of strings"))?,
+            Some(_) => anyhow::bail!("ignorePatterns must be an array of strings"),
+        };
+        let repos = match v.get("repos") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(a)) => a
+                .iter()
+                .map(|p| p.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| anyhow::anyhow!("repos must be an array of paths"))?,
+            Some(_) => anyhow::bail!("repos must be an array of paths"),
+        };
+        for key in ["tui", "theme"] {
+            if v.get(key).is_some_and(|x| !x.is_object() && !x.is_null()) {
+                anyhow::bail!("{} must be an object", key);
+            }
+        }
+        Ok(Self {
+            data_dir,
+            chunk_size,
+            ignore_patterns,
+            repos,
+            raw: v,
+        })
+    }
+}
+
+pub fn load_config(repo_root: &Path) -> Result<Config> {
     let cfg_path = repo_root.join("swarmfs.config.json");
-    let data = fs::read_to_string(&cfg_path).with_context(|| format!("read {:?}", cfg_path))?;
-    let v: Value = serde_json::from_str(&data).context("parse swarmfs.config.json")?;
-    Ok(v)
+    let data = fs::read_to_string(&cfg_path).with_context(|| format!("cannot read {}", cfg_path.display()))?;
+    let v: Value = serde_json::from_str(&data)
+        .map_err(|e| anyhow::anyhow!("{} is not valid JSON: {}", cfg_path.display(), e))?;
+    Config::try_from(v).with_context(|| format!("invalid {}", cfg_path.display()))
 }
 
-pub fn resolve_data_dir(repo_root: &Path, cfg: &Value) -> Result<PathBuf> {
-    let data_dir = cfg
-        .get("dataDir")
-        .and_then(|v| v.as_str())
-        .unwrap_or("./swarmfs-data");
+const SCROLL_STEP_MAX: u64 = 50;
 
-    let p = PathBuf::from(data_dir);
+/// TUI-only settings, read from the optional `"tui"` object in `swarmfs.config.json`.
+#[derive(Debug, Clone)]
+pub struct TuiSettings {
+    /// Stop a tab's background work (verify, browse, join/leave) when switching away from it.
+    pub cancel_on_tab_switch: bool,
+    /// Initial file picker popup size, as a percentage of the content area.
+    pub picker_size_percent: u16,
+    /// Max gap between the clicks of a double-click in the file picker, in milliseconds.
+    pub double_click_ms: u64,
+    /// Initial auto-refresh interval for the active tab in seconds; 0 disables it.
+    pub auto_refresh_secs: u64,
+    /// Rows (or log lines) moved per mouse-wheel notch.
+    pub scroll_step: usize,
+    /// Start in read-only mode, as with `--read-only`.
+    pub read_only: bool,
+    /// Global key bindings (`tui.keybinds`).
+    pub keybinds: KeyBindings,
+    /// Color palette, from the top-level `theme` object.
+    pub theme: Theme,
+}
+
+impl Default for TuiSettings {
+    fn default() -> Self {
+        Self {
+            cancel_on_tab_switch: false,
+            picker_size_percent: 80,
+            double_click_ms: DEFAULT_DOUBLE_CLICK_MS,
+            auto_refresh_secs: 0,
+            scroll_step: DEFAULT_SCROLL_STEP,
+            read_only: false,
+            keybinds: KeyBindings::default(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl TuiSettings {
+    pub fn from_config(cfg: &Value) -> Self {
+        let tui = cfg.get("tui");
+        Self {
+            cancel_on_tab_switch: tui
+                .and_then(|t| t.get("cancelOnTabSwitch"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            picker_size_percent: tui
+                .and_then(|t| t.get("pickerSizePercent"))
+                .and_then(|v| v.as_u64())
+                .map(|p| p.min(u16::MAX as u64) as u16)
+                .unwrap_or(80),
+            double_click_ms: tui
+                .and_then(|t| t.get("doubleClickMs"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_DOUBLE_CLICK_MS),
+            auto_refresh_secs: tui
+                .and_then(|t| t.get("autoRefreshSecs"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0),
+            scroll_step: tui
+                .and_then(|t| t.get("scrollStep"))
+                .and_then(|v| v.as_u64())
+                .map(|n| n.clamp(1, SCROLL_STEP_MAX) as usize)
+                .unwrap_or(DEFAULT_SCROLL_STEP),
+            read_only: tui
+                .and_then(|t| t.get("readOnly"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            keybinds: KeyBindings::from_config(tui.and_then(|t| t.get("keybinds"))),
+            theme: Theme::from_config(cfg.get("theme")),
+        }
+    }
+}
+
+/// Loads TUI settings, falling back to defaults if the config is missing or invalid.
+pub fn load_tui_settings(repo_root: &Path) -> TuiSettings {
+    match load_config(repo_root) {
+        Ok(cfg) => TuiSettings::from_config(&cfg.raw),
+        Err(_) => TuiSettings::default(),
+    }
+}
+
+pub fn resolve_data_dir(repo_root: &Path, cfg: &Config) -> PathBuf {
+    let p = PathBuf::from(&cfg.data_dir);
     if p.is_absolute() {
-        Ok(p)
+        p
     } else {
-        Ok(repo_root.join(p))
+        repo_root.join(p)
+    }
+}
+
+/// Current `SavedUiState::version`. Version 1 absorbed the separate `tui-*.json` list files.
+pub const UI_STATE_VERSION: u32 = 1;
+
+/// Window state carried between runs in `<dataDir>/tui-state.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SavedUiState {
+    /// Format version; files written before versioning read as 0.
+    pub version: u32,
+    /// Tab title, e.g. "Files".
+    pub active_tab: Option<String>,
+    pub files_sort: Option<String>,
+    pub files_sort_desc: bool,
+    pub files_split: Option<u16>,
+    pub network_split: Option<u16>,
+    /// Files tab re-add menu, newest first.
+    pub recent_adds: Vec<String>,
+    /// Directories entered in the file picker, newest first.
+    pub recent_dirs: Vec<String>,
+    /// Pinned file paths and topic names, in their manual order.
+    pub pinned_files: Vec<String>,
+    pub pinned_topics: Vec<String>,
+    /// Completed verify runs, oldest first.
+    pub verify_history: Vec<VerifyRecord>,
+}
+
+/// Outcome counts of one finished `files.verify` run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerifyRecord {
+    pub ts: i64,
+    pub ok: u64,
+    pub failed: u64,
+}
+
+fn ui_data_dir(repo_root: &Path) -> Option<PathBuf> {
+    let cfg = load_config(repo_root).ok()?;
+    Some(resolve_data_dir(repo_root, &cfg))
+}
+
+/// Best effort: a missing or corrupt state file yields the defaults. State from before version 1
+/// picks up the lists that used to live in their own files.
+pub fn load_ui_state(repo_root: &Path) -> SavedUiState {
+    let Some(dir) = ui_data_dir(repo_root) else {
+        return SavedUiState::default();
+    };
+    let mut state: SavedUiState = fs::read_to_string(dir.join("tui-state.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    if state.version < UI_STATE_VERSION {
+        migrate_list_files(&dir, &mut state);
+    }
+    state
+}
+
+fn migrate_list_files(dir: &Path, state: &mut SavedUiState) {
+    fn read<T: serde::de::DeserializeOwned>(dir: &Path, name: &str) -> Vec<T> {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+    state.recent_dirs = read(dir, "tui-recent-dirs.json");
+    state.pinned_files = read(dir, "tui-pinned-files.json");
+    state.pinned_topics = read(dir, "tui-pinned-topics.json");
+    state.verify_history = read(dir, "tui-verify-history.json");
+    state.version = UI_STATE_VERSION;
+}
+
+/// Best effort; failures are ignored. Written to a temp file and renamed into place, so a crash
+/// mid-write leaves the previous state intact.
+pub fn save_ui_state(repo_root: &Path, state: &SavedUiState) {
+    let Some(path) = ui_data_dir(repo_root).map(|d| d.join("tui-state.json")) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(s) = serde_json::to_string_pretty(state) {
+        let tmp = path.with_extension("json.tmp");
+        if fs::write(&tmp, s).is_ok() && fs::rename(&tmp, &path).is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
     }
 }
 
@@ -79,8 +314,103 @@ pub fn get_ipc_endpoint(repo_root: &Path) -> Result<(PathBuf, PathBuf, String)>
         return Ok((repo_root.to_path_buf(), PathBuf::new(), v));
     }
 
+    let (data_dir, endpoint) = repo_endpoint(repo_root)?;
+    Ok((repo_root.to_path_buf(), data_dir, endpoint))
+}
+
+/// Data dir and IPC endpoint of a repo, ignoring `SWARMFS_IPC_ENDPOINT`.
+pub fn repo_endpoint(repo_root: &Path) -> Result<(PathBuf, String)> {
     let cfg = load_config(repo_root)?;
-    let data_dir = resolve_data_dir(repo_root, &cfg)?;
+    let data_dir = resolve_data_dir(repo_root, &cfg);
     let endpoint = ipc_endpoint(&data_dir);
-    Ok((repo_root.to_path_buf(), data_dir, endpoint))
+    Ok((data_dir, endpoint))
+}
+
+/// Repos offered by the repo switcher: `repo_root` first, then the `SWARMFS_REPOS` path list,
+/// then the config's `repos` array. Duplicates are dropped.
+pub fn configured_repos(repo_root: &Path) -> Vec<PathBuf> {
+    let mut out = vec![repo_root.to_path_buf()];
+    if let Some(list) = std::env::var_os("SWARMFS_REPOS") {
+        out.extend(std::env::split_paths(&list).filter(|p| !p.as_os_str().is_empty()));
+    }
+    if let Ok(cfg) = load_config(repo_root) {
+        out.extend(cfg.repos.iter().map(|r| repo_root.join(r)));
+    }
+    let mut seen = std::collections::HashSet::new();
+    out.retain(|p| seen.insert(p.canonicalize().unwrap_or_else(|_| p.clone())));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn err(v: Value) -> String {
+        Config::try_from(v).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn validates_expected_keys() {
+        let cfg = Config::try_from(json!({ "chunkSize": 262144, "ignorePatterns": [".git"] })).unwrap();
+        assert_eq!(cfg.data_dir, "./swarmfs-data");
+        assert_eq!(cfg.chunk_size, Some(262144));
+        assert_eq!(err(json!({ "dataDir": 5 })), "dataDir must be a string");
+        assert_eq!(err(json!({ "chunkSize": -1 })), "chunkSize must be a positive integer");
+        assert_eq!(err(json!({ "ignorePatterns": [1] })), "ignorePatterns must be an array of strings");
+        assert_eq!(err(json!({ "tui": true })), "tui must be an object");
+        assert_eq!(err(json!({ "repos": "../other" })), "repos must be an array of paths");
+        assert_eq!(err(json!([])), "the top level must be a JSON object");
+    }
+
+    #[test]
+    fn ui_state_round_trips_and_tolerates_corruption() {
+        let root = std::env::temp_dir().join(format!("swarmfs-tui-state-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("swarmfs.config.json"), r#"{ "dataDir": "./data" }"#).unwrap();
+
+        let empty = SavedUiState { version: UI_STATE_VERSION, ..Default::default() };
+        assert_eq!(load_ui_state(&root), empty);
+        let state = SavedUiState {
+            version: UI_STATE_VERSION,
+            active_tab: Some("Files".to_string()),
+            files_sort: Some("size".to_string()),
+            files_sort_desc: true,
+            files_split: Some(70),
+            network_split: None,
+            recent_adds: vec!["/data/set".to_string()],
+            pinned_topics: vec!["team".to_string()],
+            verify_history: vec![VerifyRecord { ts: 1, ok: 3, failed: 1 }],
+            ..Default::default()
+        };
+        save_ui_state(&root, &state);
+        assert_eq!(load_ui_state(&root), state);
+        assert!(!root.join("data/tui-state.json.tmp").exists());
+
+        fs::write(root.join("data/tui-state.json"), "{ not json").unwrap();
+        assert_eq!(load_ui_state(&root), empty);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn unversioned_ui_state_imports_the_old_list_files() {
+        let root = std::env::temp_dir().join(format!("swarmfs-tui-migrate-{}", std::process::id()));
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(root.join("swarmfs.config.json"), r#"{ "dataDir": "./data" }"#).unwrap();
+        fs::write(root.join("data/tui-state.json"), r#"{ "activeTab": "Network" }"#).unwrap();
+        fs::write(root.join("data/tui-pinned-files.json"), r#"["/a", "/b"]"#).unwrap();
+        fs::write(root.join("data/tui-verify-history.json"), r#"[{ "ts": 5, "ok": 2, "failed": 0 }]"#).unwrap();
+
+        let state = load_ui_state(&root);
+        assert_eq!(state.version, UI_STATE_VERSION);
+        assert_eq!(state.active_tab.as_deref(), Some("Network"));
+        assert_eq!(state.pinned_files, ["/a", "/b"]);
+        assert_eq!(state.verify_history, [VerifyRecord { ts: 5, ok: 2, failed: 0 }]);
+        assert!(state.recent_dirs.is_empty());
+
+        // Once saved as the current version the old files are no longer consulted.
+        save_ui_state(&root, &SavedUiState { version: UI_STATE_VERSION, ..Default::default() });
+        assert!(load_ui_state(&root).pinned_files.is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
 }
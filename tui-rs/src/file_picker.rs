@@ -1,19 +1,23 @@
-use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
     Frame,
 };
-use std::collections::BTreeSet;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use crate::widgets::{
-    handle_scrollbar_down, handle_scrollbar_drag, render_scrollbar, MultiSelectState,
-    MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec, TextInput, TextInputAction,
+    handle_scrollbar_down, handle_scrollbar_drag, render_scrollbar, render_table_viewport, scroll_viewport,
+    MultiSelectState, format_bytes_short, MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec, TextInput,
+    TextInputAction, DEFAULT_SCROLL_STEP,
 };
+use crate::fuzzy;
+use crate::theme::theme;
+use crate::tabs::draw_empty_state;
 
 /// Action emitted by the picker.
 ///
@@ -42,6 +46,29 @@ struct PickerItem {
     size: Option<u64>,
 }
 
+/// File/dir counts and summed file size for a set of picker items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct DirSummary {
+    files: usize,
+    dirs: usize,
+    bytes: u64,
+}
+
+impl DirSummary {
+    fn of<'a>(items: impl Iterator<Item = &'a PickerItem>) -> Self {
+        let mut s = Self::default();
+        for it in items {
+            if it.is_dir {
+                s.dirs += 1;
+            } else {
+                s.files += 1;
+                s.bytes += it.size.unwrap_or(0);
+            }
+        }
+        s
+    }
+}
+
 #[derive(Debug, Clone)]
 struct VisibleItem {
     item_idx: usize,
@@ -67,6 +94,7 @@ pub struct FilePicker {
 
     // Mouse UX: detect double-click.
     last_click: Option<(usize, Instant)>,
+    double_click: Duration,
 
     // Scrollbar mouse drag.
     scrollbar_drag: bool,
@@ -76,6 +104,9 @@ pub struct FilePicker {
 
     // Cached viewport size (in rows) from the last draw. Used for page-up/page-down jumps.
     last_viewport_rows: usize,
+    // Rows per mouse-wheel notch, and whether the wheel moved the view away from the selection.
+    scroll_step: usize,
+    viewport_detached: bool,
 
     /// Selected paths remain selected even if they disappear due to filtering.
     selection: MultiSelectState<PathBuf>,
@@ -84,8 +115,52 @@ pub struct FilePicker {
     drag_select_start: Option<usize>,
 
     query: TextInput,
+
+    /// Popup size as a percentage of the parent area (both axes).
+    size_percent: u16,
+
+    /// Recently entered directories, most recent first.
+    recent: Vec<PathBuf>,
+    /// The table lists `recent` instead of the contents of `cwd`.
+    show_recent: bool,
+    /// List entries matched by .gitignore (and `.git` itself). Reset on each `open()`.
+    show_ignored: bool,
+    /// List dotfiles. Unlike `show_ignored`, this sticks across `open()` calls.
+    show_hidden: bool,
+
+    /// Lowercased extensions (no dot) files must have to be listed; empty = all. Dirs always pass.
+    ext_filter: Vec<String>,
+    /// `:` input for `ext_filter`; drawn in place of the search box while editing.
+    ext_input: TextInput,
+    ext_editing: bool,
+
+    /// Ctrl+L path bar, drawn in place of the search box while editing.
+    path_input: TextInput,
+    path_editing: bool,
+    /// Why the last path bar submission was rejected.
+    path_error: Option<String>,
+
+    /// Totals for everything listed in `cwd`, and for the filtered `visible` subset.
+    total_summary: DirSummary,
+    visible_summary: DirSummary,
+
+    // Table-focus type-ahead: typed prefix and when it was last extended.
+    typeahead: String,
+    typeahead_at: Option<Instant>,
 }
 
+const RECENT_DIRS_MAX: usize = 20;
+
+/// Idle time after which the type-ahead prefix starts over.
+const TYPEAHEAD_RESET: Duration = Duration::from_millis(800);
+
+/// Default max gap between the two clicks of a double-click.
+pub const DEFAULT_DOUBLE_CLICK_MS: u64 = 400;
+
+const PICKER_SIZE_MIN: u16 = 40;
+const PICKER_SIZE_MAX: u16 = 100;
+const PICKER_SIZE_STEP: u16 = 5;
+
 impl FilePicker {
     pub fn new(cwd: PathBuf) -> Self {
         let mut table_state = TableState::default();
@@ -99,28 +174,173 @@ impl FilePicker {
             visible: Vec::new(),
             table_state,
             last_click: None,
+            double_click: Duration::from_millis(DEFAULT_DOUBLE_CLICK_MS),
             scrollbar_drag: false,
             scrollbar_grab: None,
             last_viewport_rows: 10,
+            scroll_step: DEFAULT_SCROLL_STEP,
+            viewport_detached: false,
             selection: MultiSelectState::default(),
             drag_select_start: None,
             query: TextInput::new(),
+            size_percent: 80,
+            recent: Vec::new(),
+            show_recent: false,
+            show_ignored: false,
+            show_hidden: false,
+            ext_filter: Vec::new(),
+            ext_input: TextInput::new(),
+            ext_editing: false,
+            path_input: TextInput::new(),
+            path_editing: false,
+            path_error: None,
+            total_summary: DirSummary::default(),
+            visible_summary: DirSummary::default(),
+            typeahead: String::new(),
+            typeahead_at: None,
+        }
+    }
+
+    /// Recently entered directories, most recent first, for saving with the window state.
+    pub fn recent_dirs(&self) -> Vec<String> {
+        self.recent.iter().map(|p| p.to_string_lossy().into_owned()).collect()
+    }
+
+    pub fn set_recent_dirs(&mut self, dirs: Vec<String>) {
+        self.recent = dirs.into_iter().map(PathBuf::from).take(RECENT_DIRS_MAX).collect();
+    }
+
+    fn note_recent(&mut self, dir: &Path) {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        self.recent.retain(|p| p != &dir);
+        self.recent.insert(0, dir);
+        self.recent.truncate(RECENT_DIRS_MAX);
+    }
+
+    /// Navigates into `dir`, leaving the recent list if it was shown.
+    fn enter_dir(&mut self, dir: PathBuf) {
+        self.note_recent(&dir);
+        self.cwd = dir;
+        self.show_recent = false;
+        self.reload_items();
+    }
+
+    /// Jumps to the directory typed in the path bar; `~` expands to `$HOME` and relative
+    /// paths resolve against `cwd`. Anything that is not a directory keeps the bar open.
+    fn submit_path(&mut self) {
+        let raw = self.path_input.value().trim();
+        let expanded = match raw.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => std::env::var_os("HOME")
+                .map(|h| PathBuf::from(h).join(rest.trim_start_matches('/')))
+                .unwrap_or_else(|| PathBuf::from(raw)),
+            _ => PathBuf::from(raw),
+        };
+        let target = self.cwd.join(expanded);
+        if target.is_dir() {
+            self.path_editing = false;
+            self.path_error = None;
+            self.query.clear();
+            self.enter_dir(target.canonicalize().unwrap_or(target));
+        } else if target.exists() {
+            self.path_error = Some(format!("not a directory: {}", target.display()));
+        } else {
+            self.path_error = Some(format!("no such directory: {}", target.display()));
         }
     }
 
+    fn toggle_recent(&mut self) {
+        self.show_recent = !self.show_recent;
+        self.query.clear();
+        self.reload_items();
+    }
+
+    pub fn set_size_percent(&mut self, percent: u16) {
+        self.size_percent = percent.clamp(PICKER_SIZE_MIN, PICKER_SIZE_MAX);
+    }
+
+    pub fn set_double_click_ms(&mut self, ms: u64) {
+        self.double_click = Duration::from_millis(ms.max(1));
+    }
+
+    pub fn set_scroll_step(&mut self, rows: usize) {
+        self.scroll_step = rows.max(1);
+    }
+
+    /// Popup rect and its search/table/footer chunks. Shared by draw and mouse hit-testing.
+    fn layout(&self, area: Rect) -> (Rect, Vec<Rect>) {
+        let popup = centered_rect(self.size_percent, self.size_percent, area);
+        let inner = Rect {
+            x: popup.x.saturating_add(1),
+            y: popup.y.saturating_add(1),
+            width: popup.width.saturating_sub(2),
+            height: popup.height.saturating_sub(2),
+        };
+        // Drop the footer first when the popup gets too short to fit everything.
+        let footer_rows = if inner.height >= 11 { 3 } else { 0 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(5),
+                Constraint::Length(footer_rows),
+            ])
+            .split(inner);
+        (popup, chunks.to_vec())
+    }
+
     pub fn is_open(&self) -> bool {
         self.open
     }
 
+    /// Directory currently listed (also while the recent list is shown).
+    pub fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+
     pub fn open(&mut self, cwd: PathBuf) {
         self.open = true;
         self.focus = Focus::Search;
         self.cwd = cwd;
+        self.show_recent = false;
+        self.show_ignored = false;
+        self.path_editing = false;
+        self.path_error = None;
         self.query.clear();
         self.selection.clear();
         self.reload_items();
     }
 
+    /// Opens in `target`'s parent with `target` focused. If the path no longer exists, opens the
+    /// nearest existing ancestor instead.
+    pub fn reveal(&mut self, target: &Path) {
+        let target = std::path::absolute(target).unwrap_or_else(|_| target.to_path_buf());
+        let mut dir = target.parent().map(Path::to_path_buf).unwrap_or_else(|| target.clone());
+        while !dir.is_dir() {
+            if !dir.pop() {
+                dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+                break;
+            }
+        }
+        self.open(dir);
+        // The entry may be filtered out as a dotfile or by .gitignore; reveal it anyway.
+        if !self.focus_path(&target) && target.exists() {
+            self.show_hidden = true;
+            self.show_ignored = true;
+            self.reload_items();
+            self.focus_path(&target);
+        }
+    }
+
+    fn focus_path(&mut self, target: &Path) -> bool {
+        let Some(i) = self.visible.iter().position(|vi| self.items[vi.item_idx].path == target) else {
+            return false;
+        };
+        self.table_state.select(Some(i));
+        self.selection.set_anchor(Some(i));
+        self.focus = Focus::Table;
+        true
+    }
+
     pub fn close(&mut self) {
         self.open = false;
         self.focus = Focus::Search;
@@ -156,35 +376,24 @@ impl FilePicker {
 
         // The background layer is critical: without it, terminals keep old characters
         // for cells we don't explicitly paint in the new frame.
-        let popup = centered_rect(80, 80, area);
+        let (popup, picker_chunks) = self.layout(area);
         f.render_widget(Clear, popup);
         f.render_widget(
             Block::default()
-                .style(Style::default().bg(Color::Black))
+                .style(Style::default().bg(theme().surface))
                 .borders(Borders::ALL)
-                .title("Add"),
+                .title(if self.show_recent {
+                    format!("Add - recent directories ({}%)", self.size_percent)
+                } else {
+                    format!("Add ({}%)", self.size_percent)
+                }),
             popup,
         );
 
-        let inner = Rect {
-            x: popup.x.saturating_add(1),
-            y: popup.y.saturating_add(1),
-            width: popup.width.saturating_sub(2),
-            height: popup.height.saturating_sub(2),
-        };
-
         // Layout inside popup:
         // - Search input
         // - Table
-        // - Footer (selected counter + short help)
-        let picker_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(5),
-                Constraint::Length(3),
-            ])
-            .split(inner);
+        // - Footer (selected counter + short help), hidden when the popup is too short
 
         // Approximate number of visible rows inside the table:
         // -2 for the table block borders.
@@ -195,13 +404,32 @@ impl FilePicker {
             .max(1) as usize;
 
         let search_style = if self.focus == Focus::Search {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
 
         let _ = search_style;
-        self.query.draw(f, picker_chunks[0], "Search", self.focus == Focus::Search);
+        if self.path_editing {
+            let title = match &self.path_error {
+                Some(e) => format!("Go to path - {}", e),
+                None => "Go to path (Enter jump, Esc cancel)".to_string(),
+            };
+            self.path_input.draw(f, picker_chunks[0], &title, true);
+        } else if self.ext_editing {
+            self.ext_input
+                .draw(f, picker_chunks[0], "Extensions (e.g. jpg,png; Enter apply)", true);
+        } else {
+            self.query.draw(f, picker_chunks[0], "Search", self.focus == Focus::Search);
+        }
+
+        let show_scrollbar = self.visible.len() > self.last_viewport_rows;
+        let mut table_area = picker_chunks[1];
+        if show_scrollbar {
+            table_area.width = table_area.width.saturating_sub(1);
+        }
+        // Borders, the Sel/Type/Size columns and the spacing between the 4 columns.
+        let label_width = table_area.width.saturating_sub(2 + 4 + 2 + 8 + 3).max(1) as usize;
 
         let rows = self.visible.iter().map(|vi| {
             let it = &self.items[vi.item_idx];
@@ -216,7 +444,8 @@ impl FilePicker {
                 .size
                 .map(format_bytes_short)
                 .unwrap_or_else(|| "-".to_string());
-            let label = render_highlighted_label(&it.label, &vi.match_indices);
+            let (label, match_indices) = fuzzy::truncate(&it.label, &vi.match_indices, label_width);
+            let label = fuzzy::highlight(&label, &match_indices);
             Row::new(vec![
                 Cell::from(mark),
                 Cell::from(typ),
@@ -226,7 +455,7 @@ impl FilePicker {
         });
 
         let table_style = if self.focus == Focus::Table {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
@@ -240,24 +469,34 @@ impl FilePicker {
                 Constraint::Min(10),
             ],
         )
-            .style(Style::default().bg(Color::Black))
+            .style(Style::default().bg(theme().surface))
             .block(
                 Block::default()
-                    .title(self.cwd.to_string_lossy())
+                    .title(if self.ext_filter.is_empty() {
+                        self.cwd.to_string_lossy().to_string()
+                    } else {
+                        format!("{} [ext: {}]", self.cwd.to_string_lossy(), self.ext_filter.join(","))
+                    })
+                    .title_bottom(self.summary_label())
                     .borders(Borders::ALL)
-                    .style(Style::default().bg(Color::Black))
+                    .style(Style::default().bg(theme().surface))
                     .border_style(table_style),
             )
-            .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+            .row_highlight_style(theme().highlight());
+
+        render_table_viewport(
+            f,
+            table,
+            table_area,
+            &mut self.table_state,
+            self.viewport_detached,
+            self.last_viewport_rows,
+        );
 
-        let show_scrollbar = self.visible.len() > self.last_viewport_rows;
-        let mut table_area = picker_chunks[1];
-        if show_scrollbar {
-            table_area.width = table_area.width.saturating_sub(1);
+        if let Some(msg) = self.empty_message() {
+            draw_empty_state(f, table_area.inner(Margin { vertical: 1, horizontal: 1 }), &msg);
         }
 
-        f.render_stateful_widget(table, table_area, &mut self.table_state);
-
         let table_ctrl = MultiSelectTableController::new(TableHitTestSpec {
             checkbox_width: 4,
             ..TableHitTestSpec::bordered(0)
@@ -272,29 +511,106 @@ impl FilePicker {
 
         let footer = Paragraph::new(Line::from(vec![
             Span::raw(format!("Selected: {}  ", self.selection.selected().len())),
-            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::styled("/", theme().accent()),
             Span::raw(" focus search  "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::styled("Enter", theme().accent()),
             Span::raw(" confirm/cd  "),
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
+            Span::styled("Backspace", theme().accent()),
             Span::raw(" up  "),
-            Span::styled("Tab", Style::default().fg(Color::Yellow)),
+            Span::styled("Tab", theme().accent()),
             Span::raw(" toggle  "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::styled("+/-", theme().accent()),
+            Span::raw(" resize  "),
+            Span::styled("r", theme().accent()),
+            Span::raw(" recent  "),
+            Span::styled("Ctrl+L", theme().accent()),
+            Span::raw(" path  "),
+            Span::styled(":", theme().accent()),
+            Span::raw(" ext filter  "),
+            Span::styled(".", theme().accent()),
+            Span::raw(if self.show_hidden { " hidden: shown  " } else { " hidden: off  " }),
+            Span::styled("i", theme().accent()),
+            Span::raw(if self.show_ignored { " ignored: shown  " } else { " ignored: hidden  " }),
+            Span::styled("Esc", theme().accent()),
             Span::raw(" cancel"),
         ]))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .style(Style::default().bg(Color::Black)),
+                .style(Style::default().bg(theme().surface)),
         );
         f.render_widget(footer, picker_chunks[2]);
     }
 
+    /// Pastes into the path bar, extension filter or search box, whichever is being edited.
+    pub fn on_paste(&mut self, text: &str) {
+        if !self.open {
+            return;
+        }
+        if self.path_editing {
+            if self.path_input.insert_str(text) {
+                self.path_error = None;
+            }
+        } else if self.ext_editing {
+            if self.ext_input.insert_str(text) {
+                self.set_ext_filter_from_input();
+            }
+        } else if self.focus == Focus::Search && self.query.insert_str(text) {
+            self.recompute_visible();
+        }
+    }
+
     pub fn on_key(&mut self, key: KeyEvent) -> PickerAction {
         if !self.open {
             return PickerAction::None;
         }
+        self.viewport_detached = false;
+
+        if self.path_editing {
+            match self.path_input.handle_key(key) {
+                TextInputAction::Submit => self.submit_path(),
+                TextInputAction::Cancel => {
+                    self.path_editing = false;
+                    self.path_error = None;
+                }
+                TextInputAction::Changed => self.path_error = None,
+                TextInputAction::None => {}
+            }
+            return PickerAction::None;
+        }
+
+        if key.code == KeyCode::Char('l') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.path_input.set(self.cwd.to_string_lossy().to_string());
+            self.path_error = None;
+            self.path_editing = true;
+            return PickerAction::None;
+        }
+
+        if self.ext_editing {
+            match self.ext_input.handle_key(key) {
+                TextInputAction::Changed => self.set_ext_filter_from_input(),
+                TextInputAction::Submit => self.ext_editing = false,
+                TextInputAction::Cancel => {
+                    self.ext_input.clear();
+                    self.set_ext_filter_from_input();
+                    self.ext_editing = false;
+                }
+                TextInputAction::None => {}
+            }
+            return PickerAction::None;
+        }
+
+        // Cursor movement and word delete belong to the search box while it has focus.
+        let edit_key = matches!(
+            key.code,
+            KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End | KeyCode::Delete
+        ) || (key.code == KeyCode::Char('w') && key.modifiers.contains(KeyModifiers::CONTROL));
+        if self.focus == Focus::Search && edit_key {
+            if matches!(self.query.handle_key(key), TextInputAction::Changed) {
+                self.recompute_visible();
+            }
+            return PickerAction::None;
+        }
 
         match key.code {
             KeyCode::Esc => {
@@ -306,6 +622,10 @@ impl FilePicker {
                         return PickerAction::None;
                     }
                 }
+                if self.show_recent {
+                    self.toggle_recent();
+                    return PickerAction::None;
+                }
                 return PickerAction::Cancel;
             }
 
@@ -354,8 +674,7 @@ impl FilePicker {
                 if self.focus == Focus::Table {
                     if let Some(it) = self.current_item() {
                         if it.is_dir {
-                            self.cwd = it.path.clone();
-                            self.reload_items();
+                            self.enter_dir(it.path.clone());
                         }
                     }
                 }
@@ -367,6 +686,40 @@ impl FilePicker {
                 return PickerAction::None;
             }
 
+            KeyCode::Char('r') if self.focus == Focus::Table => {
+                self.toggle_recent();
+                return PickerAction::None;
+            }
+
+            // Table focus only, so dots can still be typed into the query.
+            KeyCode::Char('.') if self.focus == Focus::Table => {
+                self.show_hidden = !self.show_hidden;
+                self.reload_items();
+                return PickerAction::None;
+            }
+
+            KeyCode::Char(':') if self.focus == Focus::Table => {
+                self.ext_input.set(self.ext_filter.join(","));
+                self.ext_editing = true;
+                return PickerAction::None;
+            }
+
+            KeyCode::Char('i') if self.focus == Focus::Table => {
+                self.show_ignored = !self.show_ignored;
+                self.reload_items();
+                return PickerAction::None;
+            }
+
+            // Resize the popup (table focus only, so the query can still contain +/-).
+            KeyCode::Char('+') | KeyCode::Char('=') if self.focus == Focus::Table => {
+                self.set_size_percent(self.size_percent.saturating_add(PICKER_SIZE_STEP));
+                return PickerAction::None;
+            }
+            KeyCode::Char('-') if self.focus == Focus::Table => {
+                self.set_size_percent(self.size_percent.saturating_sub(PICKER_SIZE_STEP));
+                return PickerAction::None;
+            }
+
             KeyCode::Enter => {
                 match self.focus {
                     Focus::Search => {
@@ -380,8 +733,7 @@ impl FilePicker {
                         // Enter on a file confirms.
                         if let Some(it) = self.current_item() {
                             if it.is_dir {
-                                self.cwd = it.path.clone();
-                                self.reload_items();
+                                self.enter_dir(it.path.clone());
                                 return PickerAction::None;
                             }
                         }
@@ -403,12 +755,14 @@ impl FilePicker {
 
             KeyCode::Char(c) => {
                 if self.focus == Focus::Search {
-                    let _ = c;
                     if matches!(self.query.handle_key(key), TextInputAction::Changed) {
                         self.recompute_visible();
                     }
                     return PickerAction::None;
                 }
+                // Keys without a table binding above jump by label prefix.
+                self.typeahead_jump(c);
+                return PickerAction::None;
             }
             _ => {}
         }
@@ -421,21 +775,7 @@ impl FilePicker {
             return PickerAction::None;
         }
 
-        let popup = centered_rect(80, 80, area);
-        let inner = Rect {
-            x: popup.x.saturating_add(1),
-            y: popup.y.saturating_add(1),
-            width: popup.width.saturating_sub(2),
-            height: popup.height.saturating_sub(2),
-        };
-        let picker_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(5),
-                Constraint::Length(3),
-            ])
-            .split(inner);
+        let (popup, picker_chunks) = self.layout(area);
 
         let table_ctrl = MultiSelectTableController::new(TableHitTestSpec {
             checkbox_width: 4,
@@ -449,6 +789,7 @@ impl FilePicker {
 
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.viewport_detached = false;
                 if let Some(metrics) = scrollbar_metrics {
                     if crate::widgets::contains(metrics.scrollbar_col, mouse.column, mouse.row) {
                         match handle_scrollbar_down(metrics, mouse.row) {
@@ -501,16 +842,16 @@ impl FilePicker {
                         let is_double = self
                             .last_click
                             .map(|(prev_idx, t)| {
-                                prev_idx == idx && now.duration_since(t) <= Duration::from_millis(400)
+                                prev_idx == idx && now.duration_since(t) <= self.double_click
                             })
                             .unwrap_or(false);
-                        self.last_click = Some((idx, now));
+                        // A third click starts a new pair instead of firing again.
+                        self.last_click = if is_double { None } else { Some((idx, now)) };
 
                         if is_double {
                             if let Some(it) = self.current_item() {
                                 if it.is_dir {
-                                    self.cwd = it.path.clone();
-                                    self.reload_items();
+                                    self.enter_dir(it.path.clone());
                                     return PickerAction::None;
                                 }
                             }
@@ -566,18 +907,14 @@ impl FilePicker {
                 self.drag_select_start = None;
             }
 
-            MouseEventKind::ScrollDown => {
-                if contains(picker_chunks[1], mouse.column, mouse.row) {
-                    self.move_selection(1);
-                    self.last_click = None;
-                }
-            }
-
-            MouseEventKind::ScrollUp => {
-                if contains(picker_chunks[1], mouse.column, mouse.row) {
-                    self.move_selection(-1);
-                    self.last_click = None;
-                }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp
+                if contains(picker_chunks[1], mouse.column, mouse.row) =>
+            {
+                let step = self.scroll_step as isize;
+                let delta = if mouse.kind == MouseEventKind::ScrollUp { -step } else { step };
+                scroll_viewport(&mut self.table_state, delta, self.last_viewport_rows, self.visible.len());
+                self.viewport_detached = true;
+                self.last_click = None;
             }
 
             _ => {}
@@ -598,6 +935,43 @@ impl FilePicker {
         self.selection.set_anchor(Some(next));
     }
 
+    /// Extends the type-ahead prefix with `c` and selects the next visible item whose label
+    /// starts with it. A fresh single-character prefix starts searching after the current row,
+    /// so repeating a letter cycles through matches.
+    fn typeahead_jump(&mut self, c: char) {
+        let now = Instant::now();
+        if self
+            .typeahead_at
+            .is_none_or(|t| now.duration_since(t) > TYPEAHEAD_RESET)
+        {
+            self.typeahead.clear();
+        }
+        self.typeahead_at = Some(now);
+        let lc: String = c.to_lowercase().collect();
+        if self.typeahead != lc {
+            self.typeahead.push_str(&lc);
+        }
+
+        let n = self.visible.len();
+        if n == 0 {
+            return;
+        }
+        let cur = self.table_state.selected().unwrap_or(0);
+        let start = if self.typeahead.chars().count() == 1 { cur + 1 } else { cur };
+        let prefix = self.typeahead.clone();
+        let hit = (0..n).map(|i| (start + i) % n).find(|&i| {
+            self.items[self.visible[i].item_idx]
+                .label
+                .to_lowercase()
+                .starts_with(&prefix)
+        });
+        if let Some(i) = hit {
+            self.table_state.select(Some(i));
+            self.selection.set_anchor(Some(i));
+            self.last_click = None;
+        }
+    }
+
     fn current_item(&self) -> Option<&PickerItem> {
         let sel = self.table_state.selected()?;
         let vi = self.visible.get(sel)?;
@@ -617,6 +991,10 @@ impl FilePicker {
     }
 
     fn go_up(&mut self) {
+        if self.show_recent {
+            self.toggle_recent();
+            return;
+        }
         if let Some(parent) = self.cwd.parent().map(Path::to_path_buf) {
             self.cwd = parent;
             self.reload_items();
@@ -626,9 +1004,31 @@ impl FilePicker {
     fn reload_items(&mut self) {
         let mut items: Vec<PickerItem> = Vec::new();
 
-        if let Ok(rd) = std::fs::read_dir(&self.cwd) {
+        if self.show_recent {
+            // Prune directories that no longer exist; keep MRU order.
+            self.recent.retain(|p| p.is_dir());
+            items = self
+                .recent
+                .iter()
+                .map(|p| PickerItem {
+                    path: p.clone(),
+                    label: format!("{}/", sanitize_label(&p.to_string_lossy())),
+                    is_dir: true,
+                    size: None,
+                })
+                .collect();
+        } else if let Ok(rd) = std::fs::read_dir(&self.cwd) {
+            let kept = (!self.show_ignored).then(|| not_ignored_children(&self.cwd));
             for e in rd.flatten() {
                 let p = e.path();
+                if !self.show_hidden && e.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                if let Some(kept) = &kept {
+                    if e.file_name() == ".git" || !kept.contains(&p) {
+                        continue;
+                    }
+                }
                 let name = sanitize_label(&e.file_name().to_string_lossy());
 
                 // Note: we use `symlink_metadata` so we can still display entries even
@@ -656,9 +1056,12 @@ impl FilePicker {
         }
 
         // Stable base ordering when query is empty.
-        items.sort_by(|a, b| a.label.to_lowercase().cmp(&b.label.to_lowercase()));
+        if !self.show_recent {
+            items.sort_by_key(|it| it.label.to_lowercase());
+        }
 
         self.items = items;
+        self.total_summary = DirSummary::of(self.items.iter());
         self.recompute_visible();
         self.last_click = None;
 
@@ -671,7 +1074,61 @@ impl FilePicker {
         }
     }
 
+    fn set_ext_filter_from_input(&mut self) {
+        self.ext_filter = parse_ext_filter(self.ext_input.value());
+        self.recompute_visible();
+    }
+
+    /// Why the table is empty: a genuinely empty directory vs. a query or filter matching nothing.
+    fn empty_message(&self) -> Option<String> {
+        if !self.visible.is_empty() {
+            return None;
+        }
+        let q = self.query.value();
+        Some(if self.items.is_empty() {
+            "empty directory".to_string()
+        } else if !q.is_empty() {
+            format!("No matches for '{}'", q)
+        } else {
+            format!("No files matching [ext: {}]", self.ext_filter.join(","))
+        })
+    }
+
+    fn passes_ext_filter(&self, it: &PickerItem) -> bool {
+        if it.is_dir || self.ext_filter.is_empty() {
+            return true;
+        }
+        it.path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| self.ext_filter.contains(&e))
+    }
+
     fn recompute_visible(&mut self) {
+        self.rank_visible();
+        self.visible_summary = DirSummary::of(self.visible.iter().map(|vi| &self.items[vi.item_idx]));
+        self.clamp_selection();
+    }
+
+    /// " 12 files, 3 dirs, 4.5M ", or "visible/total" pairs while a filter hides entries.
+    fn summary_label(&self) -> String {
+        let (v, t) = (self.visible_summary, self.total_summary);
+        if v == t {
+            return format!(" {} files, {} dirs, {} ", t.files, t.dirs, format_bytes_short(t.bytes));
+        }
+        format!(
+            " files {}/{}, dirs {}/{}, {}/{} ",
+            v.files,
+            t.files,
+            v.dirs,
+            t.dirs,
+            format_bytes_short(v.bytes),
+            format_bytes_short(t.bytes)
+        )
+    }
+
+    /// Rebuilds `visible` from `items`, `ext_filter` and the fuzzy `query`.
+    fn rank_visible(&mut self) {
         let q = self.query.value().trim();
 
         if q.is_empty() {
@@ -679,6 +1136,7 @@ impl FilePicker {
                 .items
                 .iter()
                 .enumerate()
+                .filter(|(_, it)| self.passes_ext_filter(it))
                 .map(|(item_idx, _)| VisibleItem {
                     item_idx,
                     score: 0,
@@ -690,7 +1148,10 @@ impl FilePicker {
 
         let mut vis: Vec<VisibleItem> = Vec::new();
         for (item_idx, it) in self.items.iter().enumerate() {
-            if let Some((score, match_indices)) = subseq_score(&it.label, q) {
+            if !self.passes_ext_filter(it) {
+                continue;
+            }
+            if let Some((score, match_indices)) = fuzzy::score(&it.label, q) {
                 vis.push(VisibleItem {
                     item_idx,
                     score,
@@ -707,8 +1168,10 @@ impl FilePicker {
         });
 
         self.visible = vis;
+    }
 
-        // Keep the current selection index in range.
+    /// Keeps the current selection index in range.
+    fn clamp_selection(&mut self) {
         if let Some(sel) = self.table_state.selected() {
             if sel >= self.visible.len() {
                 if self.visible.is_empty() {
@@ -717,6 +1180,8 @@ impl FilePicker {
                     self.table_state.select(Some(self.visible.len() - 1));
                 }
             }
+        } else if !self.visible.is_empty() {
+            self.table_state.select(Some(0));
         }
     }
 }
@@ -757,84 +1222,26 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     horizontal_layout[1]
 }
 
-/// Compute a simple fzf-like subsequence score.
-///
-/// Returns `None` if `query` is not a subsequence of `label`.
-///
-/// Scoring (intentionally simple):
-/// - +10 for each matched character
-/// - +15 for each consecutive match (bonus)
-/// - -position of first match (prefer earlier)
-///
-/// Also returns match indices for highlighting.
-fn subseq_score(label: &str, query: &str) -> Option<(i64, Vec<usize>)> {
-    let label_chars: Vec<char> = label.chars().collect();
-    let q_chars: Vec<char> = query.chars().collect();
-
-    if q_chars.is_empty() {
-        return Some((0, Vec::new()));
-    }
-
-    let mut match_indices: Vec<usize> = Vec::with_capacity(q_chars.len());
-
-    let mut li = 0;
-    for qc in q_chars.iter() {
-        let mut found = None;
-        while li < label_chars.len() {
-            if label_chars[li].to_ascii_lowercase() == qc.to_ascii_lowercase() {
-                found = Some(li);
-                li += 1;
-                break;
-            }
-            li += 1;
-        }
-        let idx = found?;
-        match_indices.push(idx);
-    }
-
-    let mut score: i64 = 0;
-    score += 10 * match_indices.len() as i64;
-
-    // Consecutive bonus.
-    for w in match_indices.windows(2) {
-        if w[1] == w[0] + 1 {
-            score += 15;
-        }
-    }
-
-    // Prefer early matches.
-    score -= match_indices[0] as i64;
-
-    Some((score, match_indices))
+/// Parses "jpg, .PNG,*.txt" into `["jpg", "png", "txt"]`.
+fn parse_ext_filter(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(|e| e.trim().trim_start_matches('*').trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
 }
 
-fn render_highlighted_label(label: &str, match_indices: &[usize]) -> Line<'static> {
-    // NOTE: table cells support rich text via `Line` (which is made of multiple `Span`s).
-    // This keeps the UI fzf-like: matched characters are highlighted.
-    if match_indices.is_empty() {
-        return Line::from(Span::styled(
-            label.to_string(),
-            Style::default().fg(Color::Gray),
-        ));
-    }
-
-    let matches: BTreeSet<usize> = match_indices.iter().copied().collect();
-    let mut spans: Vec<Span> = Vec::new();
-
-    for (i, ch) in label.chars().enumerate() {
-        if matches.contains(&i) {
-            spans.push(Span::styled(
-                ch.to_string(),
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        } else {
-            spans.push(Span::styled(ch.to_string(), Style::default().fg(Color::Gray)));
-        }
-    }
-
-    Line::from(spans)
+/// Direct children of `dir` that .gitignore rules (including parent dirs, `.git/info/exclude`
+/// and the global excludes file) do not exclude.
+fn not_ignored_children(dir: &Path) -> HashSet<PathBuf> {
+    ignore::WalkBuilder::new(dir)
+        .max_depth(Some(1))
+        .hidden(false)
+        .ignore(false)
+        .build()
+        .flatten()
+        .filter(|e| e.depth() == 1)
+        .map(|e| e.into_path())
+        .collect()
 }
 
 fn sanitize_label(s: &str) -> String {
@@ -854,22 +1261,24 @@ fn sanitize_label(s: &str) -> String {
     out
 }
 
-fn format_bytes_short(n: u64) -> String {
-    const KB: f64 = 1024.0;
-    const MB: f64 = KB * 1024.0;
-    const GB: f64 = MB * 1024.0;
-    const TB: f64 = GB * 1024.0;
-
-    let n_f = n as f64;
-    if n_f < KB {
-        format!("{}B", n)
-    } else if n_f < MB {
-        format!("{:.1}K", n_f / KB)
-    } else if n_f < GB {
-        format!("{:.1}M", n_f / MB)
-    } else if n_f < TB {
-        format!("{:.1}G", n_f / GB)
-    } else {
-        format!("{:.1}T", n_f / TB)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_message_tells_empty_dir_from_no_matches() {
+        let dir = std::env::temp_dir().join(format!("swarmfs-picker-empty-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut p = FilePicker::new(dir.clone());
+        p.open(dir.clone());
+        assert_eq!(p.empty_message().as_deref(), Some("empty directory"));
+
+        std::fs::write(dir.join("notes.txt"), "x").unwrap();
+        p.open(dir.clone());
+        assert_eq!(p.empty_message(), None);
+        p.query.set("zzz".to_string());
+        p.recompute_visible();
+        assert_eq!(p.empty_message().as_deref(), Some("No matches for 'zzz'"));
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -0,0 +1,221 @@
+//! fzf-style fuzzy matching shared by the file picker, the command palette and the tab filters.
+
+use crate::theme::theme;
+use crate::widgets::{middle_cut, truncate_middle};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use std::collections::BTreeSet;
+
+/// Base score per matched char.
+const SCORE_MATCH: i64 = 16;
+/// Match right after `/`, `_`, `-`, `.`, a space, or at the start of the label.
+const BONUS_BOUNDARY: i64 = 10;
+/// Uppercase match after a lowercase char (`fooBar`).
+const BONUS_CAMEL: i64 = 8;
+/// Match continuing the previous one.
+const BONUS_CONSECUTIVE: i64 = 12;
+/// Per matched char in the last path component.
+const BONUS_BASENAME: i64 = 4;
+/// Per unmatched char between two matches, or before the first one.
+const PENALTY_GAP: i64 = 1;
+
+/// Fuzzy-matches `query` as a subsequence of `label`, fzf-style. Smartcase: a query with an
+/// uppercase letter matches case-sensitively, otherwise case is ignored.
+///
+/// Returns `None` when it is not a subsequence; otherwise the best-scoring alignment and its
+/// char indices (for highlighting). Matches at word boundaries and camelCase humps, runs of
+/// consecutive matches and matches in the basename score higher; gaps cost a little.
+pub fn score(label: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+    let label_chars: Vec<char> = label.chars().collect();
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let fold = |c: char| if case_sensitive { c } else { c.to_ascii_lowercase() };
+    let q_chars: Vec<char> = query.chars().map(fold).collect();
+
+    if q_chars.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let (n, m) = (label_chars.len(), q_chars.len());
+    if m > n {
+        return None;
+    }
+
+    let basename_start = label_chars.iter().rposition(|c| *c == '/').map_or(0, |i| i + 1);
+    let bonus = |j: usize| -> i64 {
+        let c = label_chars[j];
+        let mut b = if j >= basename_start { BONUS_BASENAME } else { 0 };
+        match j.checked_sub(1).map(|p| label_chars[p]) {
+            None | Some('/' | '_' | '-' | '.' | ' ') => b += BONUS_BOUNDARY,
+            Some(prev) if prev.is_lowercase() && c.is_uppercase() => b += BONUS_CAMEL,
+            _ => {}
+        }
+        b
+    };
+
+    // best[i][j]: best score with query char i matched at label index j; `from` backtracks.
+    const NONE: i64 = i64::MIN / 2;
+    let mut best = vec![vec![NONE; n]; m];
+    let mut from = vec![vec![usize::MAX; n]; m];
+    for i in 0..m {
+        // Max over k < j - 1 of best[i-1][k] + k * PENALTY_GAP, for gapped transitions.
+        let mut run: (i64, usize) = (NONE, usize::MAX);
+        for j in i..n {
+            if i > 0 && j >= 2 && best[i - 1][j - 2] > NONE {
+                let cand = best[i - 1][j - 2] + (j - 2) as i64 * PENALTY_GAP;
+                if cand > run.0 {
+                    run = (cand, j - 2);
+                }
+            }
+            if fold(label_chars[j]) != q_chars[i] {
+                continue;
+            }
+            let here = SCORE_MATCH + bonus(j);
+            if i == 0 {
+                best[0][j] = here - j as i64 * PENALTY_GAP;
+                continue;
+            }
+            let consecutive = (j >= 1 && best[i - 1][j - 1] > NONE)
+                .then(|| best[i - 1][j - 1] + BONUS_CONSECUTIVE);
+            // Skipped chars between k and j are j - k - 1.
+            let gapped = (run.0 > NONE).then(|| run.0 - (j as i64 - 1) * PENALTY_GAP);
+            match (consecutive, gapped) {
+                (Some(c), g) if g.is_none_or(|g| c >= g) => {
+                    best[i][j] = here + c;
+                    from[i][j] = j - 1;
+                }
+                (_, Some(g)) => {
+                    best[i][j] = here + g;
+                    from[i][j] = run.1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let (mut j, score) = (0..n)
+        .filter(|&j| best[m - 1][j] > NONE)
+        .map(|j| (j, best[m - 1][j]))
+        .max_by_key(|&(j, s)| (s, std::cmp::Reverse(j)))?;
+    let mut match_indices = vec![0; m];
+    for i in (0..m).rev() {
+        match_indices[i] = j;
+        j = from[i][j];
+    }
+    Some((score, match_indices))
+}
+
+/// Renders `label` with the chars at `match_indices` emphasized and the rest muted, fzf-style.
+pub fn highlight(label: &str, match_indices: &[usize]) -> Line<'static> {
+    if match_indices.is_empty() {
+        return Line::from(Span::styled(label.to_string(), theme().muted()));
+    }
+
+    let matches: BTreeSet<usize> = match_indices.iter().copied().collect();
+    let hit = Style::default().fg(theme().text).add_modifier(Modifier::BOLD);
+    let spans: Vec<Span> = label
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| {
+            let style = if matches.contains(&i) { hit } else { theme().muted() };
+            Span::styled(ch.to_string(), style)
+        })
+        .collect();
+    Line::from(spans)
+}
+
+/// Middle-truncates `label` to `max` chars, moving fuzzy-match positions along with the kept
+/// text; matches inside the elided part are dropped.
+pub fn truncate(label: &str, match_indices: &[usize], max: usize) -> (String, Vec<usize>) {
+    let Some((head, tail)) = middle_cut(label, max) else {
+        return (label.to_string(), match_indices.to_vec());
+    };
+    let tail_start = label.chars().count() - tail;
+    let indices = match_indices
+        .iter()
+        .filter_map(|&i| match i {
+            i if i < head => Some(i),
+            i if i >= tail_start => Some(i - tail_start + head + 1),
+            _ => None,
+        })
+        .collect();
+    (truncate_middle(label, max), indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rank<'a>(query: &str, labels: &[&'a str]) -> Vec<&'a str> {
+        let mut scored: Vec<(i64, &str)> =
+            labels.iter().filter_map(|l| score(l, query).map(|(s, _)| (s, *l))).collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        scored.into_iter().map(|(_, l)| l).collect()
+    }
+
+    #[test]
+    fn prefers_word_boundaries_and_basename() {
+        assert_eq!(rank("main", &["src/amain.rs", "src/main.rs"]), ["src/main.rs", "src/amain.rs"]);
+        // Basename beats a directory match of the same quality.
+        assert_eq!(rank("util", &["util/lib.rs", "src/util.rs"]), ["src/util.rs", "util/lib.rs"]);
+        // camelCase humps count as boundaries.
+        assert_eq!(rank("fb", &["src/fab.rs", "src/fooBar.rs"]), ["src/fooBar.rs", "src/fab.rs"]);
+        // Consecutive runs beat scattered matches.
+        assert_eq!(rank("ver", &["src/v_e_r.rs", "src/verify.rs"]), ["src/verify.rs", "src/v_e_r.rs"]);
+    }
+
+    #[test]
+    fn rejects_non_subsequences() {
+        assert_eq!(score("abc", "abd"), None);
+        assert_eq!(score("abc", "cba"), None);
+        assert_eq!(score("ab", "abc"), None);
+        assert_eq!(score("", "a"), None);
+    }
+
+    #[test]
+    fn picks_best_alignment_for_highlighting() {
+        // Greedy matching would take the first 'm'; the boundary match scores higher.
+        let (_, idx) = score("mx/main.rs", "main").unwrap();
+        assert_eq!(idx, vec![3, 4, 5, 6]);
+        let (_, idx) = score("FooBar", "fb").unwrap();
+        assert_eq!(idx, vec![0, 3]);
+        assert_eq!(score("anything", ""), Some((0, Vec::new())));
+    }
+
+    #[test]
+    fn indices_count_chars_not_bytes() {
+        let (_, idx) = score("ünï/cödé.rs", "cd").unwrap();
+        assert_eq!(idx, vec![4, 6]);
+        let line = highlight("ünï/cödé.rs", &idx);
+        assert_eq!(line.spans[4].content, "c");
+        assert_eq!(line.spans[6].content, "d");
+    }
+
+    #[test]
+    fn is_smartcase() {
+        assert!(score("README.md", "readme").is_some());
+        assert!(score("readme.md", "readme").is_some());
+        assert!(score("README.md", "README").is_some());
+        assert!(score("readme.md", "README").is_none());
+        assert!(score("Readme.md", "README").is_none());
+    }
+
+    #[test]
+    fn highlight_emphasizes_only_matched_chars() {
+        let line = highlight("main.rs", &[0, 1]);
+        assert_eq!(line.spans.len(), 7);
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert!(!line.spans[2].style.add_modifier.contains(Modifier::BOLD));
+        // No matches renders the whole label as one muted span.
+        assert_eq!(highlight("main.rs", &[]).spans.len(), 1);
+    }
+
+    #[test]
+    fn truncate_keeps_match_positions_on_the_kept_text() {
+        let (label, idx) = truncate("src/very/long/dir/file.rs", &[0, 6, 18, 19], 14);
+        assert_eq!(label, "src/v…/file.rs");
+        assert_eq!(idx, vec![0, 7, 8]);
+        let (label, idx) = truncate("short.rs", &[1], 14);
+        assert_eq!((label.as_str(), idx), ("short.rs", vec![1]));
+    }
+}
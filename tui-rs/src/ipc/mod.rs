@@ -1,12 +1,17 @@
-use crate::app::LogEntry;
+use crate::app::{LogEntry, LogLevel};
+use crate::tabs::common::now_ms;
 use anyhow::{Context, Result};
 use interprocess::local_socket::{GenericFilePath, ToFsName};
 use interprocess::local_socket::prelude::LocalSocketStream;
 use interprocess::local_socket::traits::Stream;
+use interprocess::local_socket::SendHalf;
 use serde_json::Value;
 use std::io::{BufRead, BufReader, Write};
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 pub mod types;
 
@@ -15,57 +20,286 @@ pub use types::{DaemonEvent, NetworkEvent};
 pub struct IpcClient {
     rpc: RpcClient,
     endpoint: String,
+    // Shared with the event thread; false while either side is reconnecting.
+    connected: Arc<AtomicBool>,
+    // Set on drop so an event thread for a replaced client stops reconnecting.
+    events_cancel: Arc<AtomicBool>,
+    timeout: Duration,
+}
+
+/// Default per-request timeout for `IpcClient::rpc`.
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(5);
+/// Timeout for requests that do real work daemon-side (hashing, joining, browsing peers).
+pub const LONG_RPC_TIMEOUT: Duration = Duration::from_secs(600);
+
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(100);
+const EVENT_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(5);
+/// Interrupted writes tolerated per request before giving up.
+const WRITE_RETRIES: u32 = 3;
+const WRITE_RETRY_DELAY: Duration = Duration::from_millis(10);
+/// Methods that never change daemon state: allowed in read-only mode, and safe to send again after
+/// the connection dropped mid-request.
+const READ_METHODS: &[&str] = &[
+    "daemon.ping",
+    "node.status",
+    "network.stats",
+    "logs.tail",
+    "network.overview",
+    "files.list",
+    "files.info",
+    "downloads.list",
+    "topic.list",
+    "topic.peers",
+    "browse.topic",
+];
+
+pub fn is_read_method(method: &str) -> bool {
+    READ_METHODS.contains(&method)
+}
+
+fn connect_stream(endpoint: &str) -> Result<LocalSocketStream> {
+    let name = endpoint
+        .to_fs_name::<GenericFilePath>()
+        .with_context(|| format!("invalid IPC endpoint name: {}", endpoint))?;
+
+    LocalSocketStream::connect(name).with_context(|| format!("connect IPC {}", endpoint))
 }
 
 impl IpcClient {
     pub fn connect(endpoint: String) -> Result<Self> {
-        let name = endpoint
-            .as_str()
-            .to_fs_name::<GenericFilePath>()
-            .with_context(|| format!("invalid IPC endpoint name: {}", endpoint))?;
-
-        let stream = LocalSocketStream::connect(name)
-            .with_context(|| format!("connect IPC {}", endpoint))?;
+        let stream = connect_stream(&endpoint)?;
 
         Ok(Self {
             rpc: RpcClient::new(stream),
             endpoint,
+            connected: Arc::new(AtomicBool::new(true)),
+            events_cancel: Arc::new(AtomicBool::new(false)),
+            timeout: DEFAULT_RPC_TIMEOUT,
         })
     }
 
+    /// Sets the default timeout applied to each `rpc` call.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
     pub fn rpc(&mut self, method: &str, params: Value) -> Result<Value> {
-        self.rpc.rpc(method, params)
+        self.rpc_with_timeout(method, params, self.timeout)
+    }
+
+    /// Like `rpc`, but with an explicit timeout for this call only.
+    pub fn rpc_with_timeout(&mut self, method: &str, params: Value, timeout: Duration) -> Result<Value> {
+        match self.rpc.rpc(method, params.clone(), timeout) {
+            Ok(v) => Ok(v),
+            Err(RpcError::Remote(e)) => Err(e),
+            Err(RpcError::Timeout) => {
+                anyhow::bail!("{} timed out after {}s", method, timeout.as_secs_f32())
+            }
+            Err(RpcError::Transport(e)) => {
+                // A failed write means the daemon never got the whole request, so sending it
+                // again is safe; otherwise it may already have run.
+                let resend = self.rpc.broken || is_read_method(method);
+                self.connected.store(false, Ordering::Relaxed);
+                if self.reconnect().is_err() || !resend {
+                    return Err(e);
+                }
+                self.rpc
+                    .rpc(method, params, timeout)
+                    .map_err(|e| e.into_error(method, timeout))
+            }
+        }
+    }
+
+    /// Re-opens the RPC stream with a single attempt so a dead daemon never stalls the UI
+    /// thread; the event thread keeps retrying with backoff and sets `connected` once it's back.
+    fn reconnect(&mut self) -> Result<()> {
+        let stream = connect_stream(&self.endpoint)?;
+        self.rpc = RpcClient::new(stream);
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
     }
 
     pub fn subscribe_events(&self, channels: Vec<&str>, tx: Sender<DaemonEvent>) -> Result<()> {
         let endpoint = self.endpoint.clone();
         let channels: Vec<String> = channels.into_iter().map(|s| s.to_string()).collect();
+        let connected = self.connected.clone();
+        let cancel = self.events_cancel.clone();
 
-        thread::spawn(move || {
-            if let Err(e) = event_thread(endpoint, channels, tx) {
-                // Best-effort: we can’t report this cleanly yet without a second channel.
-                let _ = e;
-            }
-        });
+        thread::spawn(move || event_thread(endpoint, channels, tx, connected, cancel));
 
         Ok(())
     }
 }
 
+impl Drop for IpcClient {
+    fn drop(&mut self) {
+        self.events_cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// An error the daemon answered with. `code` is its machine-readable `error.code`
+/// (e.g. `not_found`); match on that rather than on the message.
+#[derive(Debug)]
+pub struct RemoteError {
+    pub code: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+/// The daemon's error code when `e` is an error the daemon answered with.
+pub fn remote_error_code(e: &anyhow::Error) -> Option<&str> {
+    e.downcast_ref::<RemoteError>().map(|r| r.code.as_str())
+}
+
+enum RpcError {
+    /// The stream failed; the request may be retried on a new connection.
+    Transport(anyhow::Error),
+    /// The daemon answered with an error.
+    Remote(anyhow::Error),
+    /// No response within the timeout; the request id is abandoned.
+    Timeout,
+}
+
+impl RpcError {
+    fn into_error(self, method: &str, timeout: Duration) -> anyhow::Error {
+        match self {
+            RpcError::Transport(e) | RpcError::Remote(e) => e,
+            RpcError::Timeout => {
+                anyhow::anyhow!("{} timed out after {}s", method, timeout.as_secs_f32())
+            }
+        }
+    }
+}
+
+impl<E: Into<anyhow::Error>> From<E> for RpcError {
+    fn from(e: E) -> Self {
+        RpcError::Transport(e.into())
+    }
+}
+
+/// Writes all of `buf` and flushes, retrying interrupted writes up to `WRITE_RETRIES` times
+/// in total and resuming after partial writes. A send timeout surfaces as `WouldBlock` or
+/// `TimedOut` and is returned as-is, so the caller treats it as a transport failure.
+fn write_all_retrying<W: Write>(w: &mut W, buf: &[u8]) -> std::io::Result<()> {
+    let retryable = |e: &std::io::Error| e.kind() == std::io::ErrorKind::Interrupted;
+    let mut retries = 0;
+    let mut written = 0;
+    while written < buf.len() {
+        match w.write(&buf[written..]) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(n) => written += n,
+            Err(e) if retryable(&e) && retries < WRITE_RETRIES => {
+                retries += 1;
+                thread::sleep(WRITE_RETRY_DELAY);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    loop {
+        match w.flush() {
+            Ok(()) => return Ok(()),
+            Err(e) if retryable(&e) && retries < WRITE_RETRIES => {
+                retries += 1;
+                thread::sleep(WRITE_RETRY_DELAY);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// How an `RpcClient` bounds reads by the request deadline.
+enum Transport {
+    /// The socket's own send/recv timeouts bound each call.
+    Timed(BufReader<LocalSocketStream>),
+    /// Named pipes reject I/O timeouts, so a reader thread feeds complete lines through a
+    /// channel and `recv_timeout` enforces the deadline instead.
+    Threaded { send: SendHalf, lines: Receiver<std::io::Result<String>> },
+}
+
 struct RpcClient {
-    reader: BufReader<LocalSocketStream>,
+    transport: Transport,
     next_id: u64,
+    // Partial line left over from a timed-out read; completed by the next read.
+    buf: String,
+    // Set when a write failed part-way; the daemon may hold half a request, so the stream must
+    // be replaced rather than reused.
+    broken: bool,
 }
 
 impl RpcClient {
     fn new(stream: LocalSocketStream) -> Self {
+        let transport = match stream.set_recv_timeout(None) {
+            Err(e) if e.kind() == std::io::ErrorKind::Unsupported => {
+                let (recv, send) = stream.split();
+                let (tx, lines) = mpsc::channel();
+                thread::spawn(move || read_lines(BufReader::new(recv), tx));
+                Transport::Threaded { send, lines }
+            }
+            _ => Transport::Timed(BufReader::new(stream)),
+        };
         Self {
-            reader: BufReader::new(stream),
+            transport,
             next_id: 1,
+            buf: String::new(),
+            broken: false,
+        }
+    }
+
+    fn send(&mut self, line: &str, timeout: Duration) -> std::io::Result<()> {
+        match &mut self.transport {
+            Transport::Timed(reader) => {
+                reader.get_ref().set_send_timeout(Some(timeout))?;
+                write_all_retrying(reader.get_mut(), line.as_bytes())
+            }
+            Transport::Threaded { send, .. } => write_all_retrying(send, line.as_bytes()),
+        }
+    }
+
+    /// Waits up to `timeout` for the next complete line; `Ok(None)` when none arrived in time.
+    fn recv(&mut self, timeout: Duration) -> Result<Option<String>, RpcError> {
+        let disconnected = || RpcError::Transport(anyhow::anyhow!("daemon disconnected"));
+        match &mut self.transport {
+            Transport::Timed(reader) => {
+                reader.get_ref().set_recv_timeout(Some(timeout))?;
+                match reader.read_line(&mut self.buf) {
+                    Ok(0) => Err(disconnected()),
+                    Ok(_) if self.buf.ends_with('\n') => Ok(Some(std::mem::take(&mut self.buf))),
+                    Ok(_) => Ok(None),
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                        ) =>
+                    {
+                        Ok(None)
+                    }
+                    Err(e) => Err(e.into()),
+                }
+            }
+            Transport::Threaded { lines, .. } => match lines.recv_timeout(timeout) {
+                Ok(Ok(line)) if line.is_empty() => Err(disconnected()),
+                Ok(Ok(line)) => Ok(Some(line)),
+                Ok(Err(e)) => Err(e.into()),
+                Err(RecvTimeoutError::Timeout) => Ok(None),
+                Err(RecvTimeoutError::Disconnected) => Err(disconnected()),
+            },
         }
     }
 
-    fn rpc(&mut self, method: &str, params: Value) -> Result<Value> {
+    fn rpc(&mut self, method: &str, params: Value, timeout: Duration) -> Result<Value, RpcError> {
+        let deadline = Instant::now() + timeout;
         let id = self.next_id;
         self.next_id += 1;
 
@@ -76,19 +310,26 @@ impl RpcClient {
             "params": params
         });
 
+        if self.broken {
+            return Err(RpcError::Transport(anyhow::anyhow!("IPC connection needs to reconnect")));
+        }
         let line = serde_json::to_string(&req)? + "\n";
-        self.reader.get_mut().write_all(line.as_bytes())?;
-        self.reader.get_mut().flush()?;
+        if let Err(e) = self.send(&line, timeout) {
+            self.broken = true;
+            return Err(RpcError::Transport(anyhow::Error::new(e).context(format!("send {}", method))));
+        }
 
-        let mut buf = String::new();
         loop {
-            buf.clear();
-            let n = self.reader.read_line(&mut buf)?;
-            if n == 0 {
-                anyhow::bail!("daemon disconnected")
+            // Responses to abandoned (timed-out) ids are skipped below by the id check.
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(RpcError::Timeout);
             }
+            let Some(line) = self.recv(remaining)? else {
+                continue;
+            };
 
-            let msg: Value = serde_json::from_str(buf.trim())?;
+            let msg: Value = serde_json::from_str(line.trim())?;
             if msg.get("type").and_then(|v| v.as_str()) != Some("res") {
                 continue;
             }
@@ -100,25 +341,68 @@ impl RpcClient {
                 return Ok(msg.get("result").cloned().unwrap_or(Value::Null));
             }
 
-            let emsg = msg
-                .get("error")
-                .and_then(|e| e.get("message"))
-                .and_then(|m| m.as_str())
-                .unwrap_or("RPC error")
-                .to_string();
-            anyhow::bail!(emsg)
+            let error = msg.get("error");
+            let field = |k: &str| error.and_then(|e| e.get(k)).and_then(|m| m.as_str());
+            return Err(RpcError::Remote(anyhow::Error::new(RemoteError {
+                code: field("code").unwrap_or("error").to_string(),
+                message: field("message").unwrap_or("RPC error").to_string(),
+            })));
         }
     }
 }
 
-fn event_thread(endpoint: String, channels: Vec<String>, tx: Sender<DaemonEvent>) -> Result<()> {
-    let name = endpoint
-        .as_str()
-        .to_fs_name::<GenericFilePath>()
-        .with_context(|| format!("invalid IPC endpoint name: {}", endpoint))?;
+/// Reader thread for `Transport::Threaded`: forwards each complete line, then an empty line at
+/// EOF or the read error, and exits once the client is gone.
+fn read_lines<R: BufRead>(mut reader: R, tx: Sender<std::io::Result<String>>) {
+    loop {
+        let mut line = String::new();
+        let res = reader.read_line(&mut line).map(|_| line);
+        let done = !matches!(&res, Ok(l) if !l.is_empty());
+        if tx.send(res).is_err() || done {
+            return;
+        }
+    }
+}
 
-    let mut stream = LocalSocketStream::connect(name)
-        .with_context(|| format!("connect IPC {}", endpoint))?;
+/// Keeps an event subscription alive, reconnecting with backoff whenever the daemon goes away.
+/// Exits once the receiving side of `tx` is dropped or `cancel` is set.
+fn event_thread(
+    endpoint: String,
+    channels: Vec<String>,
+    tx: Sender<DaemonEvent>,
+    connected: Arc<AtomicBool>,
+    cancel: Arc<AtomicBool>,
+) {
+    let mut delay = RECONNECT_BASE_DELAY;
+    while !cancel.load(Ordering::Relaxed) {
+        match event_session(&endpoint, &channels, &tx, &connected, &cancel) {
+            Ok(SessionEnd::Stopped) => return,
+            Ok(SessionEnd::Disconnected) => delay = RECONNECT_BASE_DELAY,
+            Err(_) => {}
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        connected.store(false, Ordering::Relaxed);
+        thread::sleep(delay);
+        delay = (delay * 2).min(EVENT_RECONNECT_MAX_DELAY);
+    }
+}
+
+enum SessionEnd {
+    Disconnected,
+    /// The receiver was dropped or the client cancelled the subscription.
+    Stopped,
+}
+
+fn event_session(
+    endpoint: &str,
+    channels: &[String],
+    tx: &Sender<DaemonEvent>,
+    connected: &AtomicBool,
+    cancel: &AtomicBool,
+) -> Result<SessionEnd> {
+    let mut stream = connect_stream(endpoint)?;
 
     // Subscribe
     let req = serde_json::json!({
@@ -130,6 +414,7 @@ fn event_thread(endpoint: String, channels: Vec<String>, tx: Sender<DaemonEvent>
 
     stream.write_all((serde_json::to_string(&req)? + "\n").as_bytes())?;
     stream.flush()?;
+    connected.store(true, Ordering::Relaxed);
 
     let mut reader = BufReader::new(stream);
     let mut buf = String::new();
@@ -138,7 +423,10 @@ fn event_thread(endpoint: String, channels: Vec<String>, tx: Sender<DaemonEvent>
         buf.clear();
         let n = reader.read_line(&mut buf)?;
         if n == 0 {
-            break;
+            return Ok(SessionEnd::Disconnected);
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(SessionEnd::Stopped);
         }
 
         let v: Value = match serde_json::from_str(buf.trim()) {
@@ -148,16 +436,23 @@ fn event_thread(endpoint: String, channels: Vec<String>, tx: Sender<DaemonEvent>
 
         let typ = v.get("type").and_then(|x| x.as_str());
         if typ == Some("evt") {
-            if let Some(evt) = DaemonEvent::try_from(v).ok() {
-                let _ = tx.send(evt);
+            let evt = match DaemonEvent::try_from(v) {
+                Ok(evt) => evt,
+                // Surface protocol problems in the log instead of dropping them silently.
+                Err(e) => DaemonEvent::Log(LogEntry {
+                    ts: now_ms() as i64,
+                    level: LogLevel::Warn,
+                    message: format!("dropped daemon event: {}", e),
+                }),
+            };
+            if tx.send(evt).is_err() {
+                return Ok(SessionEnd::Stopped);
             }
             continue;
         }
 
         // Ignore responses (subscribe ack, etc.)
     }
-
-    Ok(())
 }
 
 fn parse_log_entry(v: &Value) -> Option<LogEntry> {
@@ -171,3 +466,67 @@ fn _parse_log_event(v: &Value) -> Option<DaemonEvent> {
     let entry = parse_log_entry(data)?;
     Some(DaemonEvent::Log(entry))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, ErrorKind};
+
+    /// Fails with the queued errors first, then accepts at most `chunk` bytes per write.
+    struct FlakyWriter {
+        errors: Vec<ErrorKind>,
+        chunk: usize,
+        out: Vec<u8>,
+    }
+
+    impl Write for FlakyWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if let Some(kind) = self.errors.pop() {
+                return Err(Error::from(kind));
+            }
+            let n = buf.len().min(self.chunk);
+            self.out.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_retries_interrupts_and_partial_writes() {
+        let mut w = FlakyWriter { errors: vec![ErrorKind::Interrupted, ErrorKind::Interrupted], chunk: 3, out: vec![] };
+        write_all_retrying(&mut w, b"hello\n").unwrap();
+        assert_eq!(w.out, b"hello\n");
+
+        // A send timeout is not retried.
+        let mut w = FlakyWriter { errors: vec![ErrorKind::WouldBlock], chunk: 8, out: vec![] };
+        assert_eq!(write_all_retrying(&mut w, b"x").unwrap_err().kind(), ErrorKind::WouldBlock);
+        assert!(w.out.is_empty());
+
+        let errors = vec![ErrorKind::Interrupted; WRITE_RETRIES as usize + 1];
+        let mut w = FlakyWriter { errors, chunk: 8, out: vec![] };
+        assert_eq!(write_all_retrying(&mut w, b"x").unwrap_err().kind(), ErrorKind::Interrupted);
+
+        let mut w = FlakyWriter { errors: vec![ErrorKind::BrokenPipe], chunk: 8, out: vec![] };
+        assert_eq!(write_all_retrying(&mut w, b"x").unwrap_err().kind(), ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn remote_errors_keep_the_daemon_code() {
+        let e = anyhow::Error::new(RemoteError { code: "not_found".into(), message: "download 3 not found".into() });
+        assert_eq!(remote_error_code(&e), Some("not_found"));
+        assert_eq!(e.to_string(), "download 3 not found");
+        // Matching on the message is not enough.
+        assert_eq!(remote_error_code(&anyhow::anyhow!("not found")), None);
+    }
+
+    #[test]
+    fn reader_thread_forwards_lines_then_eof() {
+        let (tx, rx) = mpsc::channel();
+        read_lines(&b"{\"a\":1}\n{\"b\":2}\n"[..], tx);
+        let lines: Vec<String> = rx.iter().map(|l| l.unwrap()).collect();
+        assert_eq!(lines, vec!["{\"a\":1}\n", "{\"b\":2}\n", ""]);
+    }
+}
@@ -17,19 +17,19 @@ impl TryFrom<Value> for DaemonEvent {
         let event = v.get("event").and_then(|x| x.as_str()).unwrap_or("");
         match event {
             "log" => {
-                let data = v.get("data").cloned().unwrap_or(Value::Null);
+                let data = required_data(&v, event)?;
                 Ok(DaemonEvent::Log(LogEntry::try_from(data)?))
             }
             _ if event.starts_with("network.") => {
-                let data = v.get("data").cloned().unwrap_or(Value::Null);
+                let data = required_data(&v, event)?;
                 Ok(DaemonEvent::Network(NetworkEvent::from_event_name(event, data)))
             }
             _ if event.starts_with("state.") => {
-                let data = v.get("data").cloned().unwrap_or(Value::Null);
+                let data = required_data(&v, event)?;
                 Ok(DaemonEvent::State(StateEvent::from_event_name(event, data)))
             }
             _ if event.starts_with("downloads.") => {
-                let data = v.get("data").cloned().unwrap_or(Value::Null);
+                let data = required_data(&v, event)?;
                 Ok(DaemonEvent::Downloads(DownloadsEvent::from_event_name(event, data)))
             }
             _ => anyhow::bail!("unknown event: {}", event),
@@ -37,6 +37,14 @@ impl TryFrom<Value> for DaemonEvent {
     }
 }
 
+/// Every event the daemon emits carries a payload; a missing or null `data` is a protocol bug.
+fn required_data(v: &Value, event: &str) -> Result<Value> {
+    match v.get("data") {
+        None | Some(Value::Null) => anyhow::bail!("malformed event {}: missing data", event),
+        Some(d) => Ok(d.clone()),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DownloadsEvent {
     Progress(Value),
@@ -106,3 +114,48 @@ impl NetworkEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assert_rejected(event: &str) {
+        for v in [json!({"event": event}), json!({"event": event, "data": null})] {
+            let err = DaemonEvent::try_from(v).unwrap_err();
+            assert!(err.to_string().contains("missing data"), "{}: {}", event, err);
+        }
+    }
+
+    #[test]
+    fn log_event_without_data_is_rejected() {
+        assert_rejected("log");
+    }
+
+    #[test]
+    fn network_event_without_data_is_rejected() {
+        assert_rejected("network.stats");
+        assert_rejected("network.peer");
+    }
+
+    #[test]
+    fn state_event_without_data_is_rejected() {
+        assert_rejected("state.files");
+        assert_rejected("state.topics");
+    }
+
+    #[test]
+    fn downloads_event_without_data_is_rejected() {
+        assert_rejected("downloads.progress");
+        assert_rejected("downloads.complete");
+    }
+
+    #[test]
+    fn event_with_data_is_accepted() {
+        let v = json!({"event": "log", "data": {"ts": 1, "level": "warn", "message": "hi"}});
+        match DaemonEvent::try_from(v).unwrap() {
+            DaemonEvent::Log(e) => assert_eq!(e.message, "hi"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+}
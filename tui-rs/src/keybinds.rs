@@ -0,0 +1,324 @@
+use crate::tabs::{top_row_char_to_number, TabId};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::Value;
+use std::fmt;
+
+/// Actions that apply regardless of the active tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalAction {
+    Quit,
+    Refresh,
+    RefreshAll,
+    SwitchTab(TabId),
+    Attention,
+    Help,
+    Palette,
+    RepoSwitch,
+    Status,
+    DismissBanner,
+    CycleAutoRefresh,
+}
+
+/// A single key chord such as `q`, `ctrl+r` or `f5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn plain(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Parses `"ctrl+alt+x"`-style chords. Names are case-insensitive except for single chars.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = s.split('+').collect();
+        // A trailing "+" means the plus key itself ("ctrl++").
+        let key = if s.ends_with("++") || s == "+" {
+            parts.retain(|p| !p.is_empty());
+            "+"
+        } else {
+            parts.pop()?
+        };
+        for m in parts.iter().filter(|p| !p.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            // Terminals report Ctrl/Alt chords with the unshifted char ("ctrl+R" arrives as Ctrl+r).
+            (Some(c), None)
+                if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                    && !modifiers.contains(KeyModifiers::SHIFT) =>
+            {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                f if f.starts_with('f') => KeyCode::F(f[1..].parse().ok().filter(|n| (1..=24).contains(n))?),
+                _ => return None,
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // Terminals report SHIFT inconsistently for characters; the char itself already encodes it.
+        let ignore = if matches!(key.code, KeyCode::Char(_)) {
+            KeyModifiers::SHIFT
+        } else {
+            KeyModifiers::NONE
+        };
+        self.code == key.code && (self.modifiers - ignore) == (key.modifiers - ignore)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (m, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(m) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Joins bindings for display, e.g. `"q / Ctrl+c"`.
+pub fn describe(list: &[KeyBinding]) -> String {
+    list.iter().map(|b| b.to_string()).collect::<Vec<_>>().join(" / ")
+}
+
+/// Global key bindings, overridable from the `tui.keybinds` object in `swarmfs.config.json`:
+///
+/// ```json
+/// { "tui": { "keybinds": { "quit": "ctrl+q", "refresh": ["r", "f5"], "refreshAll": "f6", "tab4": "F" } } }
+/// ```
+///
+/// Also `attention`, `help`, `palette`, `repos`, `status`, `dismissBanner` and `autoRefresh`.
+/// Tabs without an override keep the number-row keys.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    pub quit: Vec<KeyBinding>,
+    pub refresh: Vec<KeyBinding>,
+    /// Refreshes every tab at once. Defaults to Ctrl+r because `R` already means something in
+    /// the Files and Downloads tabs.
+    pub refresh_all: Vec<KeyBinding>,
+    /// Warnings & errors overlay.
+    pub attention: Vec<KeyBinding>,
+    pub help: Vec<KeyBinding>,
+    pub palette: Vec<KeyBinding>,
+    pub repos: Vec<KeyBinding>,
+    /// Node status overlay.
+    pub status: Vec<KeyBinding>,
+    /// Hides the daemon version warning banner.
+    pub dismiss_banner: Vec<KeyBinding>,
+    pub auto_refresh: Vec<KeyBinding>,
+    /// Per-tab overrides, indexed like `TabId::ALL`.
+    pub tabs: [Vec<KeyBinding>; 5],
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: vec![KeyBinding::plain('q')],
+            refresh: vec![KeyBinding::plain('r')],
+            refresh_all: vec![KeyBinding {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+            }],
+            attention: vec![KeyBinding::plain('w')],
+            help: vec![KeyBinding::plain('?')],
+            palette: vec![KeyBinding::plain(':')],
+            repos: vec![KeyBinding::plain('o')],
+            status: vec![KeyBinding::plain('I')],
+            dismiss_banner: vec![KeyBinding::plain('X')],
+            auto_refresh: vec![KeyBinding::plain('t')],
+            tabs: Default::default(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Reads overrides from a `keybinds` object. Unparseable entries keep the default.
+    pub fn from_config(v: Option<&Value>) -> Self {
+        let mut kb = Self::default();
+        let Some(v) = v else {
+            return kb;
+        };
+        let read = |name: &str| -> Option<Vec<KeyBinding>> {
+            let list: Vec<KeyBinding> = match v.get(name)? {
+                Value::String(s) => KeyBinding::parse(s).into_iter().collect(),
+                Value::Array(a) => a.iter().filter_map(|x| KeyBinding::parse(x.as_str()?)).collect(),
+                _ => Vec::new(),
+            };
+            (!list.is_empty()).then_some(list)
+        };
+        for (name, list) in [
+            ("quit", &mut kb.quit),
+            ("refresh", &mut kb.refresh),
+            ("refreshAll", &mut kb.refresh_all),
+            ("attention", &mut kb.attention),
+            ("help", &mut kb.help),
+            ("palette", &mut kb.palette),
+            ("repos", &mut kb.repos),
+            ("status", &mut kb.status),
+            ("dismissBanner", &mut kb.dismiss_banner),
+            ("autoRefresh", &mut kb.auto_refresh),
+        ] {
+            if let Some(k) = read(name) {
+                *list = k;
+            }
+        }
+        for (i, tab) in TabId::ALL.iter().enumerate() {
+            if let Some(k) = read(&format!("tab{}", tab.number())) {
+                kb.tabs[i] = k;
+            }
+        }
+        kb
+    }
+
+    pub fn lookup(&self, key: &KeyEvent) -> Option<GlobalAction> {
+        let hit = |list: &[KeyBinding]| list.iter().any(|b| b.matches(key));
+        let actions = [
+            (&self.quit, GlobalAction::Quit),
+            (&self.refresh, GlobalAction::Refresh),
+            (&self.refresh_all, GlobalAction::RefreshAll),
+            (&self.attention, GlobalAction::Attention),
+            (&self.help, GlobalAction::Help),
+            (&self.palette, GlobalAction::Palette),
+            (&self.repos, GlobalAction::RepoSwitch),
+            (&self.status, GlobalAction::Status),
+            (&self.dismiss_banner, GlobalAction::DismissBanner),
+            (&self.auto_refresh, GlobalAction::CycleAutoRefresh),
+        ];
+        if let Some((_, action)) = actions.iter().find(|(list, _)| hit(list)) {
+            return Some(*action);
+        }
+        if let Some(i) = self.tabs.iter().position(|list| hit(list)) {
+            return Some(GlobalAction::SwitchTab(TabId::ALL[i]));
+        }
+        // Number-row fallback for tabs that were not remapped.
+        if let KeyCode::Char(c) = key.code {
+            let tab = top_row_char_to_number(c).and_then(TabId::from_number)?;
+            let i = TabId::ALL.iter().position(|t| *t == tab)?;
+            if self.tabs[i].is_empty() {
+                return Some(GlobalAction::SwitchTab(tab));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(KeyBinding::parse("q"), Some(KeyBinding::plain('q')));
+        let b = KeyBinding::parse("Ctrl+R").unwrap();
+        assert_eq!((b.code, b.modifiers), (KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert!(b.matches(&key(KeyCode::Char('r'), KeyModifiers::CONTROL)));
+        assert_eq!(KeyBinding::parse("alt+X").map(|b| b.code), Some(KeyCode::Char('x')));
+        // Plain chars keep their case: `R` and `r` are different keys.
+        assert_eq!(KeyBinding::parse("R"), Some(KeyBinding::plain('R')));
+        assert_eq!(KeyBinding::parse("f5").map(|b| b.code), Some(KeyCode::F(5)));
+        assert_eq!(KeyBinding::parse("hyper+x"), None);
+        assert_eq!(KeyBinding::parse("f99"), None);
+    }
+
+    #[test]
+    fn defaults_match_hardcoded_keys() {
+        let kb = KeyBindings::default();
+        let none = KeyModifiers::NONE;
+        assert_eq!(kb.lookup(&key(KeyCode::Char('q'), none)), Some(GlobalAction::Quit));
+        assert_eq!(kb.lookup(&key(KeyCode::Char('r'), none)), Some(GlobalAction::Refresh));
+        assert_eq!(
+            kb.lookup(&key(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+            Some(GlobalAction::RefreshAll)
+        );
+        assert_eq!(
+            kb.lookup(&key(KeyCode::Char('4'), none)),
+            Some(GlobalAction::SwitchTab(TabId::Files))
+        );
+        assert_eq!(kb.lookup(&key(KeyCode::Char('x'), none)), None);
+        assert_eq!(kb.lookup(&key(KeyCode::Char('w'), none)), Some(GlobalAction::Attention));
+        assert_eq!(kb.lookup(&key(KeyCode::Char('I'), KeyModifiers::SHIFT)), Some(GlobalAction::Status));
+        // Modified chords are left to the tabs.
+        assert_eq!(kb.lookup(&key(KeyCode::Char('w'), KeyModifiers::CONTROL)), None);
+        assert_eq!(kb.lookup(&key(KeyCode::Char('t'), KeyModifiers::ALT)), None);
+    }
+
+    #[test]
+    fn displays_chords() {
+        assert_eq!(KeyBinding::parse("ctrl+q").unwrap().to_string(), "Ctrl+q");
+        assert_eq!(describe(&[KeyBinding::plain('r'), KeyBinding::parse("f5").unwrap()]), "r / F5");
+    }
+
+    #[test]
+    fn config_overrides_replace_defaults() {
+        let kb = KeyBindings::from_config(Some(&json!({
+            "quit": "ctrl+q",
+            "refresh": ["f5", "bogus+key"],
+            "tab4": "F"
+        })));
+        let none = KeyModifiers::NONE;
+        assert_eq!(kb.lookup(&key(KeyCode::Char('q'), none)), None);
+        assert_eq!(
+            kb.lookup(&key(KeyCode::Char('q'), KeyModifiers::CONTROL)),
+            Some(GlobalAction::Quit)
+        );
+        assert_eq!(kb.lookup(&key(KeyCode::F(5), none)), Some(GlobalAction::Refresh));
+        assert_eq!(
+            kb.lookup(&key(KeyCode::Char('F'), KeyModifiers::SHIFT)),
+            Some(GlobalAction::SwitchTab(TabId::Files))
+        );
+        // The remapped tab no longer answers to its number key; the others still do.
+        assert_eq!(kb.lookup(&key(KeyCode::Char('4'), none)), None);
+        assert_eq!(
+            kb.lookup(&key(KeyCode::Char('1'), none)),
+            Some(GlobalAction::SwitchTab(TabId::Network))
+        );
+    }
+}
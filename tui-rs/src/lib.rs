@@ -1,7 +1,12 @@
 pub mod app;
+pub mod cli;
 pub mod config;
 pub mod file_picker;
+pub mod fuzzy;
 pub mod ipc;
+pub mod keybinds;
+pub mod palette;
 pub mod tabs;
+pub mod theme;
 pub mod ui;
 pub mod widgets;
@@ -1,46 +1,99 @@
 use anyhow::{Context, Result};
 use crossterm::{
+    cursor,
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind, MouseButton,
-        MouseEventKind, KeyCode,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+        KeyEventKind, KeyModifiers, MouseButton, MouseEventKind, KeyCode,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
-use std::{sync::mpsc, time::Duration};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::Rect,
+    Terminal,
+};
+use std::{
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    time::{Duration, Instant},
+};
 
 use swarmfs_tui::{
-    app::App,
-    config::{get_ipc_endpoint, get_repo_root},
+    app::{App, LogEntry, LogLevel},
+    cli,
+    config::{
+        get_ipc_endpoint, get_repo_root, load_tui_settings, load_ui_state, repo_endpoint, save_ui_state,
+        SavedUiState, UI_STATE_VERSION,
+    },
     ipc::{DaemonEvent, IpcClient},
-    tabs::{global_keybind, Tab, TabId, UiCommand},
+    keybinds::GlobalAction,
+    tabs::{common::{now_ms, ActivityLog}, global_keybind, row_jump_owns_keys, Tab, TabId, UiCommand},
     tabs::{BrowseTab, DownloadsTab, FilesTab, LogsTab, NetworkTab},
-    ui::{draw_footer, draw_tab_bar, layout},
+    theme,
+    ui::{
+        activity_rows, attention_on_key, draw_activity, draw_attention_overlay, draw_banner, draw_footer,
+        draw_help_overlay, draw_repos_overlay, draw_status_overlay, draw_tab_bar, draw_waiting_screen, help_on_key,
+        layout, repos_on_key, status_on_key,
+    },
 };
 
+/// How often `wait_for_daemon` retries the connection.
+const DAEMON_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 fn main() -> Result<()> {
     let cwd = std::env::current_dir().context("current_dir")?;
     let repo_root = get_repo_root(&cwd)?;
-    let (repo_root, _data_dir, endpoint) = get_ipc_endpoint(&repo_root)?;
-
-    if !cfg!(windows) {
-        let sock_path = std::path::PathBuf::from(&endpoint);
-        if !sock_path.exists() {
-            anyhow::bail!(
-                "IPC socket not found at {} (repo_root={}). Set SWARMFS_IPC_ENDPOINT to override.",
-                sock_path.display(),
-                repo_root.display(),
-            )
+    let (mut repo_root, _data_dir, endpoint) = match get_ipc_endpoint(&repo_root) {
+        Ok(v) => v,
+        Err(e) => {
+            // `{:#}` prints the context chain on one line, e.g. "invalid <path>: dataDir must be a string".
+            eprintln!("swarmfs-tui: {:#}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let read_only_flag = args.iter().any(|a| a == "--read-only");
+    let json_call = match cli::parse_json_args(&args) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("swarmfs-tui: {:#}", e);
+            std::process::exit(2);
         }
+    };
+    if let Some((method, params)) = json_call {
+        let read_only = read_only_flag || load_tui_settings(&repo_root).read_only;
+        if let Err(e) = cli::run_json(&endpoint, &method, params, read_only) {
+            eprintln!("swarmfs-tui: {:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.iter().any(|a| a == "--status") {
+        if let Err(e) = cli::run_status(&endpoint) {
+            eprintln!("swarmfs-tui: {:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
     }
 
-    let mut ipc = IpcClient::connect(endpoint.clone())?;
-    let (evt_tx, evt_rx) = mpsc::channel::<DaemonEvent>();
-    ipc.subscribe_events(vec!["log", "network", "state", "downloads"], evt_tx)?;
+    install_panic_hook();
+    enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // The daemon may be started after the TUI; wait for its socket instead of exiting.
+    let Some((mut ipc, mut evt_rx)) = wait_for_daemon(&mut terminal, &endpoint, &repo_root)? else {
+        return restore_terminal(&mut terminal);
+    };
 
     let mut app = App::new();
-    let _ = app.refresh_basics(&mut ipc);
+    app.repo_root = repo_root.clone();
+    app.endpoint = endpoint.clone();
 
     let mut network_tab = NetworkTab::new(endpoint.clone());
     let mut browse_tab = BrowseTab::new(endpoint.clone());
@@ -48,23 +101,22 @@ fn main() -> Result<()> {
     let mut files_tab = FilesTab::new(endpoint.clone());
     let mut logs_tab = LogsTab::new();
 
-    network_tab.refresh(&mut ipc);
-    downloads_tab.refresh(&mut ipc);
-    browse_tab.refresh(&mut ipc);
-    browse_tab.browse_prefetch();
-    files_tab.refresh(&mut ipc);
+    configure_for_repo(&repo_root, read_only_flag, &mut app, &mut files_tab);
+    theme::install(app.settings.theme.clone());
+    initial_load(&mut ipc, &mut app, &mut network_tab, &mut browse_tab, &mut downloads_tab, &mut files_tab);
 
-    enable_raw_mode()?;
-    let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    restore_ui_state(&repo_root, &mut app, &mut network_tab, &mut files_tab);
+    if app.active_tab == TabId::Logs {
+        logs_tab.on_activated();
+    }
 
     let tick_rate = Duration::from_millis(50);
+    let mut prev_tab = app.active_tab;
+    let mut saved_ui_state = ui_state_snapshot(&app, &network_tab, &files_tab);
+    let mut dirty = DirtyTabs::default();
 
     loop {
-        files_tab.poll_async();
+        dirty.files |= files_tab.poll_async();
         network_tab.poll_async();
         browse_tab.poll_async();
         while let Ok(evt) = evt_rx.try_recv() {
@@ -73,18 +125,19 @@ fn main() -> Result<()> {
                     network_tab.on_network_event(net_evt);
                 }
                 DaemonEvent::State(state_evt) => {
+                    // Apply payloads we understand in place; anything else falls back to a
+                    // refresh so the UI stays correct even if the payload format changes.
                     match state_evt {
-                        swarmfs_tui::ipc::types::StateEvent::Files(_)
-                        | swarmfs_tui::ipc::types::StateEvent::Topics(_)
-                        | swarmfs_tui::ipc::types::StateEvent::Downloads(_)
-                        | swarmfs_tui::ipc::types::StateEvent::Other { .. } => {
-                            // Refresh tab state on any state event.
-                            // This keeps the UI reactive even if the event payload format changes.
-                            network_tab.refresh(&mut ipc);
-                            browse_tab.refresh(&mut ipc);
-                            downloads_tab.refresh(&mut ipc);
-                            files_tab.refresh(&mut ipc);
+                        swarmfs_tui::ipc::types::StateEvent::Files(data) => {
+                            dirty.files |= !files_tab.apply_state(&data);
                         }
+                        swarmfs_tui::ipc::types::StateEvent::Topics(data) => {
+                            dirty.network |= !network_tab.apply_state(&data);
+                            // Browse lists joined topics.
+                            dirty.browse = true;
+                        }
+                        swarmfs_tui::ipc::types::StateEvent::Downloads(_) => dirty.downloads = true,
+                        swarmfs_tui::ipc::types::StateEvent::Other { .. } => dirty.mark_all(),
                     }
                 }
                 DaemonEvent::Downloads(dl_evt) => {
@@ -94,11 +147,11 @@ fn main() -> Result<()> {
                         }
                         swarmfs_tui::ipc::types::DownloadsEvent::Complete(v) => {
                             downloads_tab.on_downloads_complete(v);
-                            downloads_tab.refresh(&mut ipc);
+                            dirty.downloads = true;
                         }
                         swarmfs_tui::ipc::types::DownloadsEvent::Error(v) => {
                             downloads_tab.on_downloads_error(v);
-                            downloads_tab.refresh(&mut ipc);
+                            dirty.downloads = true;
                         }
                         swarmfs_tui::ipc::types::DownloadsEvent::Other { .. } => {}
                     }
@@ -107,9 +160,45 @@ fn main() -> Result<()> {
             }
             app.on_daemon_event(evt);
         }
+        // A burst of state events costs at most one refresh per tab per tick.
+        dirty.refresh(&mut ipc, &mut network_tab, &mut browse_tab, &mut downloads_tab, &mut files_tab);
+
+        app.set_ipc_connected(ipc.is_connected());
+        app.refresh_status_if_due();
+
+        // Never refresh under an open modal or text input; the refresh would clobber what's typed.
+        let input_captured = app.confirm.is_open()
+            || app.ui.attention_open
+            || app.ui.status_open
+            || app.ui.help_open
+            || app.ui.repos_open
+            || app.palette.is_open()
+            || network_tab.is_modal_open()
+            || network_tab.is_text_input_active()
+            || downloads_tab.is_modal_open()
+            || files_tab.is_modal_open()
+            || files_tab.is_text_input_active()
+            || browse_tab.is_text_input_active();
+        if !input_captured && app.take_auto_refresh_due() {
+            match app.active_tab {
+                TabId::Network => network_tab.refresh(&mut ipc),
+                TabId::Browse => browse_tab.refresh(&mut ipc),
+                TabId::Downloads => downloads_tab.refresh(&mut ipc),
+                TabId::Files => files_tab.refresh(&mut ipc),
+                TabId::Logs => {}
+            }
+        }
 
+        let strip_rows = activity_rows(active_activity(
+            app.active_tab,
+            &network_tab,
+            &browse_tab,
+            &downloads_tab,
+            &files_tab,
+            &logs_tab,
+        ));
         terminal.draw(|f| {
-            let areas = layout(f.area());
+            let areas = layout(f.area(), app.version_banner.is_some(), strip_rows);
             draw_tab_bar(f, areas.tab_bar, &mut app);
 
             match app.active_tab {
@@ -120,12 +209,156 @@ fn main() -> Result<()> {
                 TabId::Logs => logs_tab.draw(f, areas.content, &mut app),
             }
 
-            draw_footer(f, areas.footer, &mut app);
+            draw_banner(f, areas.banner, &app);
+            let tab_hint = match app.active_tab {
+                TabId::Network => network_tab.footer_hint(),
+                TabId::Browse => browse_tab.footer_hint(),
+                TabId::Downloads => downloads_tab.footer_hint(),
+                TabId::Files => files_tab.footer_hint(),
+                TabId::Logs => logs_tab.footer_hint(),
+            };
+            draw_activity(
+                f,
+                areas.activity,
+                active_activity(app.active_tab, &network_tab, &browse_tab, &downloads_tab, &files_tab, &logs_tab),
+            );
+            draw_footer(f, areas.footer, &mut app, &tab_hint);
+
+            if app.ui.attention_open {
+                draw_attention_overlay(f, areas.content, &mut app);
+            }
+
+            if app.ui.status_open {
+                draw_status_overlay(f, areas.content, &mut app);
+            }
+
+            if app.ui.repos_open {
+                draw_repos_overlay(f, areas.content, &mut app);
+            }
+
+            if app.ui.help_open {
+                let tab_keys = match app.active_tab {
+                    TabId::Network => network_tab.key_help(),
+                    TabId::Browse => browse_tab.key_help(),
+                    TabId::Downloads => downloads_tab.key_help(),
+                    TabId::Files => files_tab.key_help(),
+                    TabId::Logs => logs_tab.key_help(),
+                };
+                draw_help_overlay(f, areas.content, &mut app, tab_keys);
+            }
+
+            app.palette.draw(f, areas.content);
+            app.confirm.draw(f, areas.content);
         })?;
 
         if event::poll(tick_rate)? {
             match event::read()? {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if app.confirm.is_open() {
+                        if let Some(cmd) = app.confirm.on_key(key) {
+                            apply_command(
+                                cmd,
+                                &mut app,
+                                &mut ipc,
+                                &mut network_tab,
+                                &mut browse_tab,
+                                &mut downloads_tab,
+                                &mut files_tab,
+                            );
+                        }
+                        continue;
+                    }
+
+                    if app.ui.attention_open {
+                        attention_on_key(key, &mut app);
+                        continue;
+                    }
+
+                    if app.ui.status_open {
+                        status_on_key(key, &mut app);
+                        continue;
+                    }
+
+                    if app.ui.help_open {
+                        help_on_key(key, &mut app);
+                        continue;
+                    }
+
+                    if app.ui.repos_open {
+                        let Some(target) = repos_on_key(key, &mut app) else {
+                            continue;
+                        };
+                        let switched = repo_endpoint(&target)
+                            .and_then(|(_, endpoint)| Ok((connect_daemon(&endpoint)?, endpoint)));
+                        match switched {
+                            Ok(((new_ipc, new_rx), endpoint)) => {
+                                persist_ui_state(&app, &network_tab, &files_tab);
+                                // Dropping the old client and receiver stops the old event thread.
+                                ipc = new_ipc;
+                                evt_rx = new_rx;
+                                repo_root = target;
+                                app.reset_for_repo(repo_root.clone(), endpoint.clone());
+                                network_tab.set_endpoint(endpoint.clone());
+                                browse_tab.set_endpoint(endpoint.clone());
+                                files_tab.set_endpoint(endpoint);
+                                downloads_tab = DownloadsTab::new();
+                                configure_for_repo(&repo_root, read_only_flag, &mut app, &mut files_tab);
+                                initial_load(
+                                    &mut ipc,
+                                    &mut app,
+                                    &mut network_tab,
+                                    &mut browse_tab,
+                                    &mut downloads_tab,
+                                    &mut files_tab,
+                                );
+                                restore_ui_state(&repo_root, &mut app, &mut network_tab, &mut files_tab);
+                                saved_ui_state = ui_state_snapshot(&app, &network_tab, &files_tab);
+                                if app.active_tab == TabId::Logs {
+                                    logs_tab.on_activated();
+                                }
+                                app.push_log(LogEntry {
+                                    ts: now_ms() as i64,
+                                    level: LogLevel::Info,
+                                    message: format!("switched to repo {}", repo_root.display()),
+                                });
+                            }
+                            Err(e) => app.push_log(LogEntry {
+                                ts: now_ms() as i64,
+                                level: LogLevel::Error,
+                                message: format!("switch to {} failed: {:#}", target.display(), e),
+                            }),
+                        }
+                        continue;
+                    }
+
+                    if app.palette.is_open() {
+                        if let Some(action) = app.palette.on_key(key) {
+                            if let Some(t) = action.tab {
+                                app.set_active_tab(t);
+                            }
+                            let cmd = (action.command)();
+                            if matches!(cmd, UiCommand::Quit) {
+                                let tabs: [&dyn Tab; 5] =
+                                    [&network_tab, &browse_tab, &downloads_tab, &files_tab, &logs_tab];
+                                request_quit(&mut app, &tabs);
+                                continue;
+                            }
+                            apply_command(
+                                cmd,
+                                &mut app,
+                                &mut ipc,
+                                &mut network_tab,
+                                &mut browse_tab,
+                                &mut downloads_tab,
+                                &mut files_tab,
+                            );
+                            if app.active_tab == TabId::Logs && prev_tab != TabId::Logs {
+                                logs_tab.on_activated();
+                            }
+                        }
+                        continue;
+                    }
+
                     if app.active_tab == TabId::Network && network_tab.is_modal_open() {
                         let cmd = network_tab.on_key(key, &mut app);
                         apply_command(
@@ -168,6 +401,34 @@ fn main() -> Result<()> {
                         continue;
                     }
 
+                    if app.active_tab == TabId::Network && network_tab.is_text_input_active() {
+                        let cmd = network_tab.on_key(key, &mut app);
+                        apply_command(
+                            cmd,
+                            &mut app,
+                            &mut ipc,
+                            &mut network_tab,
+                            &mut browse_tab,
+                            &mut downloads_tab,
+                            &mut files_tab,
+                        );
+                        continue;
+                    }
+
+                    if app.active_tab == TabId::Files && files_tab.is_text_input_active() {
+                        let cmd = files_tab.on_key(key, &mut app);
+                        apply_command(
+                            cmd,
+                            &mut app,
+                            &mut ipc,
+                            &mut network_tab,
+                            &mut browse_tab,
+                            &mut downloads_tab,
+                            &mut files_tab,
+                        );
+                        continue;
+                    }
+
                     if app.active_tab == TabId::Browse && browse_tab.is_text_input_active() {
                         let cmd = browse_tab.on_key(key, &mut app);
                         apply_command(
@@ -182,16 +443,79 @@ fn main() -> Result<()> {
                         continue;
                     }
 
+                    if row_jump_owns_keys(&app) {
+                        let cmd = match app.active_tab {
+                            TabId::Files => files_tab.on_key(key, &mut app),
+                            _ => network_tab.on_key(key, &mut app),
+                        };
+                        apply_command(
+                            cmd,
+                            &mut app,
+                            &mut ipc,
+                            &mut network_tab,
+                            &mut browse_tab,
+                            &mut downloads_tab,
+                            &mut files_tab,
+                        );
+                        continue;
+                    }
+
                     // If a modal is open, it must capture all key input so typing works.
-                    // 'q' should remain a global quit shortcut ONLY when no modal is open.
-                    if matches!(key.code, KeyCode::Char('q')) {
-                        app.should_quit = true;
+                    // Quit stays a global shortcut ONLY when no modal is open.
+                    let global = global_keybind(key, &app.settings.keybinds);
+                    if matches!(global, UiCommand::Quit) {
+                        let tabs: [&dyn Tab; 5] = [&network_tab, &browse_tab, &downloads_tab, &files_tab, &logs_tab];
+                        request_quit(&mut app, &tabs);
                         continue;
                     }
 
-                    // Global keybinds (quit + tab switching)
-                    match global_keybind(key) {
+                    match app.settings.keybinds.lookup(&key) {
+                        Some(GlobalAction::Attention) => {
+                            app.ui.attention_open = true;
+                            app.ui.attention_selected = 0;
+                            continue;
+                        }
+                        Some(GlobalAction::Help) => {
+                            app.ui.help_open = true;
+                            app.ui.help_scroll = 0;
+                            continue;
+                        }
+                        Some(GlobalAction::Palette) => {
+                            app.palette.open();
+                            continue;
+                        }
+                        Some(GlobalAction::RepoSwitch) => {
+                            app.open_repo_switcher();
+                            continue;
+                        }
+                        Some(GlobalAction::Status) => {
+                            app.ui.status_open = true;
+                            continue;
+                        }
+                        Some(GlobalAction::DismissBanner) if app.version_banner.is_some() => {
+                            app.version_banner = None;
+                            app.version_banner_dismissed = true;
+                            continue;
+                        }
+                        Some(GlobalAction::CycleAutoRefresh) => {
+                            app.cycle_auto_refresh();
+                            continue;
+                        }
+                        _ => {}
+                    }
+
+                    // Global keybinds (quit, refresh, tab switching)
+                    match global {
                         UiCommand::Quit => app.should_quit = true,
+                        cmd @ (UiCommand::Refresh | UiCommand::RefreshAll) => apply_command(
+                            cmd,
+                            &mut app,
+                            &mut ipc,
+                            &mut network_tab,
+                            &mut browse_tab,
+                            &mut downloads_tab,
+                            &mut files_tab,
+                        ),
                         UiCommand::SwitchTab(t) => {
                             app.set_active_tab(t);
                             if t == TabId::Logs {
@@ -199,16 +523,21 @@ fn main() -> Result<()> {
                             }
                         }
                         UiCommand::None
-                        | UiCommand::Refresh
                         | UiCommand::JoinSelected
                         | UiCommand::LeaveSelected
+                        | UiCommand::AutoJoinAll
                         | UiCommand::TopicNewOpen
+                        | UiCommand::TopicEditOpen
                         | UiCommand::TopicNewSave
                         | UiCommand::TopicNewCancel
                         | UiCommand::TopicRemoveSelected
                         | UiCommand::FilesVerifySelected
-                        | UiCommand::FilesRemoveSelected
+                        | UiCommand::FilesRemoveSelected { .. }
                         | UiCommand::FilesAddOpen
+                        | UiCommand::FilesAddPathsOpen
+                        | UiCommand::FilesAddPathsConfirm
+                        | UiCommand::FilesRecentOpen
+                        | UiCommand::FilesRecentConfirm
                         | UiCommand::FilesAddConfirm
                         | UiCommand::FilesAddCancel
                         | UiCommand::BrowseRefresh
@@ -216,9 +545,14 @@ fn main() -> Result<()> {
                         | UiCommand::DownloadsAddOpenPrefill { .. }
                         | UiCommand::DownloadsRefresh
                         | UiCommand::DownloadsResume
+                        | UiCommand::DownloadsCancelSelected
+                        | UiCommand::DownloadsTogglePauseSelected
                         | UiCommand::DownloadsAddOpen
                         | UiCommand::DownloadsAddConfirm
-                        | UiCommand::DownloadsAddCancel => {
+                        | UiCommand::DownloadsAddCancel
+                        | UiCommand::RepoSwitchOpen
+                        | UiCommand::Confirm { .. }
+                        | UiCommand::ConfirmModes { .. } => {
                             // Fallthrough to tab handlers.
                             let cmd = match app.active_tab {
                                 TabId::Network => network_tab.on_key(key, &mut app),
@@ -228,18 +562,6 @@ fn main() -> Result<()> {
                                 TabId::Logs => logs_tab.on_key(key, &mut app),
                             };
 
-                            if matches!(cmd, UiCommand::Refresh) && app.active_tab == TabId::Network {
-                                network_tab.refresh(&mut ipc);
-                            }
-
-                            if matches!(cmd, UiCommand::Refresh) && app.active_tab == TabId::Files {
-                                files_tab.refresh(&mut ipc);
-                            }
-
-                            if matches!(cmd, UiCommand::Refresh) && app.active_tab == TabId::Downloads {
-                                downloads_tab.refresh(&mut ipc);
-                            }
-
                             apply_command(
                                 cmd,
                                 &mut app,
@@ -249,16 +571,69 @@ fn main() -> Result<()> {
                                 &mut downloads_tab,
                                 &mut files_tab,
                             );
-
-                            // Files commands are dispatched via UiCommand.
                         }
                     }
                 }
 
+                Event::Paste(text) => {
+                    // Overlays without a text field swallow the paste.
+                    if app.confirm.is_open()
+                        || app.ui.attention_open
+                        || app.ui.status_open
+                        || app.ui.help_open
+                        || app.ui.repos_open
+                    {
+                        continue;
+                    }
+                    if app.palette.is_open() {
+                        app.palette.on_paste(&text);
+                        continue;
+                    }
+                    match app.active_tab {
+                        TabId::Network => network_tab.on_paste(&text, &mut app),
+                        TabId::Browse => browse_tab.on_paste(&text, &mut app),
+                        TabId::Downloads => downloads_tab.on_paste(&text, &mut app),
+                        TabId::Files => files_tab.on_paste(&text, &mut app),
+                        TabId::Logs => logs_tab.on_paste(&text, &mut app),
+                    }
+                }
+
                 Event::Mouse(m) => {
+                    // The confirm popup, palette and repo/status/attention overlays are
+                    // keyboard-only; ignore clicks behind them.
+                    if app.confirm.is_open()
+                        || app.palette.is_open()
+                        || app.ui.repos_open
+                        || app.ui.status_open
+                        || app.ui.attention_open
+                    {
+                        continue;
+                    }
+
+                    if app.ui.help_open {
+                        match m.kind {
+                            MouseEventKind::ScrollDown => app.ui.help_scroll = app.ui.help_scroll.saturating_add(3),
+                            MouseEventKind::ScrollUp => app.ui.help_scroll = app.ui.help_scroll.saturating_sub(3),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
                     // Compute current layout for routing.
                     let size = terminal.size()?;
-                    let areas = layout(Rect::new(0, 0, size.width, size.height));
+                    let strip_rows = activity_rows(active_activity(
+                        app.active_tab,
+                        &network_tab,
+                        &browse_tab,
+                        &downloads_tab,
+                        &files_tab,
+                        &logs_tab,
+                    ));
+                    let areas = layout(
+                        Rect::new(0, 0, size.width, size.height),
+                        app.version_banner.is_some(),
+                        strip_rows,
+                    );
 
                     // Tab-bar mouse click
                     if let MouseEventKind::Down(MouseButton::Left) = m.kind {
@@ -305,18 +680,243 @@ fn main() -> Result<()> {
             }
         }
 
+        if app.active_tab != prev_tab {
+            if app.settings.cancel_on_tab_switch {
+                match prev_tab {
+                    TabId::Network => network_tab.cancel_background(),
+                    TabId::Browse => browse_tab.cancel_background(),
+                    TabId::Downloads => downloads_tab.cancel_background(),
+                    TabId::Files => files_tab.cancel_background(),
+                    TabId::Logs => logs_tab.cancel_background(),
+                }
+            }
+            prev_tab = app.active_tab;
+        }
+
+        // Saved as soon as pins, recent lists or verify history change, so a crash or kill
+        // loses nothing.
+        let snapshot = ui_state_snapshot(&app, &network_tab, &files_tab);
+        if snapshot != saved_ui_state {
+            save_ui_state(&app.repo_root, &snapshot);
+            saved_ui_state = snapshot;
+        }
+
         if app.should_quit {
             break;
         }
     }
 
+    persist_ui_state(&app, &network_tab, &files_tab);
+
+    restore_terminal(&mut terminal)
+}
+
+/// Saves the window state of the current repo for the next run.
+fn persist_ui_state(app: &App, network_tab: &NetworkTab, files_tab: &FilesTab) {
+    save_ui_state(&app.repo_root, &ui_state_snapshot(app, network_tab, files_tab));
+}
+
+fn ui_state_snapshot(app: &App, network_tab: &NetworkTab, files_tab: &FilesTab) -> SavedUiState {
+    let (files_sort, files_sort_desc) = files_tab.sort_state();
+    SavedUiState {
+        version: UI_STATE_VERSION,
+        active_tab: Some(app.active_tab.title().to_string()),
+        files_sort: Some(files_sort.to_string()),
+        files_sort_desc,
+        files_split: Some(files_tab.split_percent()),
+        network_split: Some(network_tab.split_percent()),
+        recent_adds: files_tab.recent_adds(),
+        recent_dirs: files_tab.picker_recent_dirs(),
+        pinned_files: files_tab.pins(),
+        pinned_topics: network_tab.pins(),
+        verify_history: files_tab.verify_history(),
+    }
+}
+
+/// Applies the window state saved by the last run in this repo.
+fn restore_ui_state(repo_root: &Path, app: &mut App, network_tab: &mut NetworkTab, files_tab: &mut FilesTab) {
+    let state = load_ui_state(repo_root);
+    if let Some(tab) = state.active_tab.as_deref().and_then(TabId::from_title) {
+        app.set_active_tab(tab);
+    }
+    if let Some(sort) = &state.files_sort {
+        files_tab.set_sort_state(sort, state.files_sort_desc);
+    }
+    if let Some(p) = state.files_split {
+        files_tab.set_split_percent(p);
+    }
+    if let Some(p) = state.network_split {
+        network_tab.set_split_percent(p);
+    }
+    files_tab.set_recent_adds(state.recent_adds);
+    files_tab.set_picker_recent_dirs(state.recent_dirs);
+    files_tab.set_pins(state.pinned_files);
+    files_tab.set_verify_history(state.verify_history);
+    network_tab.set_pins(state.pinned_topics);
+}
+
+/// Tabs whose daemon state changed since the last tick.
+#[derive(Default)]
+struct DirtyTabs {
+    network: bool,
+    browse: bool,
+    downloads: bool,
+    files: bool,
+}
+
+impl DirtyTabs {
+    fn mark_all(&mut self) {
+        *self = Self {
+            network: true,
+            browse: true,
+            downloads: true,
+            files: true,
+        };
+    }
+
+    /// Refreshes the dirty tabs and clears the flags.
+    fn refresh(
+        &mut self,
+        ipc: &mut IpcClient,
+        network_tab: &mut NetworkTab,
+        browse_tab: &mut BrowseTab,
+        downloads_tab: &mut DownloadsTab,
+        files_tab: &mut FilesTab,
+    ) {
+        let dirty = std::mem::take(self);
+        if dirty.network {
+            network_tab.refresh(ipc);
+        }
+        if dirty.browse {
+            browse_tab.refresh(ipc);
+        }
+        if dirty.downloads {
+            downloads_tab.refresh(ipc);
+        }
+        if dirty.files {
+            files_tab.refresh(ipc);
+        }
+    }
+}
+
+/// Restores the terminal before the default hook prints the panic, so the message and backtrace
+/// land in a usable shell instead of the alternate screen.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let mut out = std::io::stdout();
+        let _ = execute!(out, LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste, cursor::Show);
+        default_hook(info);
+    }));
+}
+
+fn restore_terminal<B: Backend + std::io::Write>(terminal: &mut Terminal<B>) -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
     terminal.show_cursor()?;
-
     Ok(())
 }
 
+/// Polls until the daemon accepts a connection. Returns `None` if the user aborts with q/Esc/Ctrl+C.
+fn wait_for_daemon<B: Backend>(
+    terminal: &mut Terminal<B>,
+    endpoint: &str,
+    repo_root: &Path,
+) -> Result<Option<(IpcClient, Receiver<DaemonEvent>)>> {
+    let started = Instant::now();
+    let mut next_try = started;
+    let mut last_error: Option<String> = None;
+    loop {
+        if Instant::now() >= next_try {
+            next_try = Instant::now() + DAEMON_POLL_INTERVAL;
+            // Named pipes have no file to watch; on unix, skip connecting until the socket exists.
+            if cfg!(windows) || Path::new(endpoint).exists() {
+                match connect_daemon(endpoint) {
+                    Ok(c) => return Ok(Some(c)),
+                    Err(e) => last_error = Some(format!("{:#}", e)),
+                }
+            }
+        }
+
+        terminal.draw(|f| {
+            draw_waiting_screen(f, endpoint, repo_root, started.elapsed(), last_error.as_deref())
+        })?;
+
+        if event::poll(Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.kind == KeyEventKind::Press
+                    && (ctrl_c || matches!(key.code, KeyCode::Esc | KeyCode::Char('q')))
+                {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// Quits, or asks first while a tab still has work in flight that quitting would abort.
+fn request_quit(app: &mut App, tabs: &[&dyn Tab]) {
+    if tabs.iter().any(|t| t.is_busy()) {
+        app.confirm.open("Operation in progress, quit anyway?".to_string(), UiCommand::Quit);
+    } else {
+        app.should_quit = true;
+    }
+}
+
+fn connect_daemon(endpoint: &str) -> Result<(IpcClient, Receiver<DaemonEvent>)> {
+    let ipc = IpcClient::connect(endpoint.to_string())?;
+    let (evt_tx, evt_rx) = mpsc::channel::<DaemonEvent>();
+    ipc.subscribe_events(vec!["log", "network", "state", "downloads"], evt_tx)?;
+    Ok((ipc, evt_rx))
+}
+
+/// Applies the repo's TUI settings.
+fn configure_for_repo(repo_root: &Path, read_only_flag: bool, app: &mut App, files_tab: &mut FilesTab) {
+    app.settings = load_tui_settings(repo_root);
+    app.read_only = read_only_flag || app.settings.read_only;
+    app.auto_refresh = (app.settings.auto_refresh_secs > 0)
+        .then(|| Duration::from_secs(app.settings.auto_refresh_secs));
+    files_tab.set_picker_size_percent(app.settings.picker_size_percent);
+    files_tab.set_picker_double_click_ms(app.settings.double_click_ms);
+    files_tab.set_picker_scroll_step(app.settings.scroll_step);
+}
+
+fn initial_load(
+    ipc: &mut IpcClient,
+    app: &mut App,
+    network_tab: &mut NetworkTab,
+    browse_tab: &mut BrowseTab,
+    downloads_tab: &mut DownloadsTab,
+    files_tab: &mut FilesTab,
+) {
+    let _ = app.refresh_basics(ipc);
+    network_tab.refresh(ipc);
+    downloads_tab.refresh(ipc);
+    browse_tab.refresh(ipc);
+    browse_tab.browse_prefetch();
+    files_tab.refresh(ipc);
+}
+
+/// The activity feed of whichever tab is showing.
+fn active_activity<'a>(
+    active: TabId,
+    network_tab: &'a NetworkTab,
+    browse_tab: &'a BrowseTab,
+    downloads_tab: &'a DownloadsTab,
+    files_tab: &'a FilesTab,
+    logs_tab: &'a LogsTab,
+) -> Option<&'a ActivityLog> {
+    match active {
+        TabId::Network => network_tab.activity(),
+        TabId::Browse => browse_tab.activity(),
+        TabId::Downloads => downloads_tab.activity(),
+        TabId::Files => files_tab.activity(),
+        TabId::Logs => logs_tab.activity(),
+    }
+}
+
 fn apply_command(
     cmd: UiCommand,
     app: &mut App,
@@ -326,26 +926,52 @@ fn apply_command(
     downloads_tab: &mut DownloadsTab,
     files_tab: &mut FilesTab,
 ) {
+    if app.read_only && cmd.is_mutating() {
+        app.toast(LogLevel::Warn, "read-only mode");
+        return;
+    }
+
     match cmd {
         UiCommand::None => {}
         UiCommand::Quit => app.should_quit = true,
         UiCommand::SwitchTab(t) => app.set_active_tab(t),
-        UiCommand::Refresh => {
-            let _ = ipc;
+        UiCommand::Refresh => match app.active_tab {
+            // Each tab has its own notion of "refresh".
+            TabId::Network => network_tab.refresh(ipc),
+            TabId::Files => files_tab.reload(ipc),
+            TabId::Browse => browse_tab.browse_refresh(ipc, app),
+            TabId::Downloads => downloads_tab.refresh(ipc),
+            TabId::Logs => {}
+        },
+        UiCommand::RefreshAll => {
+            let _ = app.refresh_basics(ipc);
+            network_tab.refresh(ipc);
+            browse_tab.refresh(ipc);
+            downloads_tab.refresh(ipc);
+            files_tab.reload(ipc);
+            app.toast(LogLevel::Info, "refreshed all tabs");
         }
         UiCommand::JoinSelected => network_tab.join_selected(ipc),
         UiCommand::LeaveSelected => network_tab.leave_selected(ipc),
+        UiCommand::AutoJoinAll => network_tab.auto_join_all(),
         UiCommand::TopicNewOpen => network_tab.topic_new_open(),
+        UiCommand::TopicEditOpen => network_tab.topic_edit_open(),
         UiCommand::TopicNewCancel => network_tab.topic_new_cancel(),
-        UiCommand::TopicNewSave => network_tab.topic_new_save(ipc),
+        UiCommand::TopicNewSave => network_tab.topic_new_save(ipc, app),
+        UiCommand::Confirm { prompt, action } => app.confirm.open(prompt, *action),
+        UiCommand::ConfirmModes { prompt, modes } => app.confirm.open_modes(prompt, modes),
         UiCommand::TopicRemoveSelected => network_tab.remove_selected(ipc),
         UiCommand::FilesVerifySelected => files_tab.verify_selected(ipc),
-        UiCommand::FilesRemoveSelected => files_tab.remove_selected(ipc),
+        UiCommand::FilesRemoveSelected { delete } => files_tab.remove_selected(ipc, delete),
         UiCommand::FilesAddOpen => files_tab.add_open(),
-        UiCommand::FilesAddConfirm => files_tab.add_confirm(ipc),
+        UiCommand::FilesAddPathsOpen => files_tab.add_paths_open(),
+        UiCommand::FilesAddPathsConfirm => files_tab.add_paths_confirm(),
+        UiCommand::FilesRecentOpen => files_tab.recent_open(),
+        UiCommand::FilesRecentConfirm => files_tab.recent_confirm(),
+        UiCommand::FilesAddConfirm => files_tab.add_confirm(),
         UiCommand::FilesAddCancel => files_tab.add_cancel(),
-        UiCommand::BrowseRefresh => browse_tab.browse_refresh(ipc),
-        UiCommand::BrowseDownloadSelected => browse_tab.download_selected(ipc),
+        UiCommand::BrowseRefresh => browse_tab.browse_refresh(ipc, app),
+        UiCommand::BrowseDownloadSelected => browse_tab.download_selected(ipc, app),
         UiCommand::DownloadsAddOpenPrefill { topic, merkle_root } => {
             app.set_active_tab(TabId::Downloads);
             downloads_tab.add_open_prefill(ipc, topic, merkle_root);
@@ -355,8 +981,11 @@ fn apply_command(
             let _ = ipc.rpc("downloads.resume", serde_json::json!({}));
             downloads_tab.refresh(ipc);
         }
+        UiCommand::DownloadsCancelSelected => downloads_tab.cancel_selected(ipc),
+        UiCommand::DownloadsTogglePauseSelected => downloads_tab.toggle_pause_selected(ipc),
         UiCommand::DownloadsAddOpen => downloads_tab.add_open(ipc),
-        UiCommand::DownloadsAddConfirm => downloads_tab.add_confirm(ipc),
+        UiCommand::DownloadsAddConfirm => downloads_tab.add_confirm(ipc, app),
         UiCommand::DownloadsAddCancel => downloads_tab.add_cancel(),
+        UiCommand::RepoSwitchOpen => app.open_repo_switcher(),
     }
 }
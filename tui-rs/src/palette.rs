@@ -0,0 +1,186 @@
+use crate::fuzzy;
+use crate::tabs::{TabId, UiCommand};
+use crate::theme::theme;
+use crate::widgets::{draw_modal_shell, TextInput, TextInputAction};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+/// An entry in the command palette.
+pub struct PaletteAction {
+    pub label: &'static str,
+    /// Tab to switch to before dispatching, so any modal the command opens is visible.
+    pub tab: Option<TabId>,
+    pub command: fn() -> UiCommand,
+}
+
+const fn action(label: &'static str, tab: Option<TabId>, command: fn() -> UiCommand) -> PaletteAction {
+    PaletteAction { label, tab, command }
+}
+
+/// Every `UiCommand`-producing action, regardless of the active tab.
+pub const ACTIONS: &[PaletteAction] = &[
+    action("Switch to Network", None, || UiCommand::SwitchTab(TabId::Network)),
+    action("Switch to Browse", None, || UiCommand::SwitchTab(TabId::Browse)),
+    action("Switch to Downloads", None, || UiCommand::SwitchTab(TabId::Downloads)),
+    action("Switch to Files", None, || UiCommand::SwitchTab(TabId::Files)),
+    action("Switch to Logs", None, || UiCommand::SwitchTab(TabId::Logs)),
+    action("Refresh active tab", None, || UiCommand::Refresh),
+    action("Refresh all tabs", None, || UiCommand::RefreshAll),
+    action("Join selected topics", Some(TabId::Network), || UiCommand::JoinSelected),
+    action("Leave selected topics", Some(TabId::Network), || UiCommand::LeaveSelected),
+    action("Join all auto-join topics", Some(TabId::Network), || UiCommand::AutoJoinAll),
+    action("New topic", Some(TabId::Network), || UiCommand::TopicNewOpen),
+    action("Edit topic", Some(TabId::Network), || UiCommand::TopicEditOpen),
+    action("Remove selected topics", Some(TabId::Network), || UiCommand::Confirm {
+        prompt: "Remove selected topic(s)?".to_string(),
+        action: Box::new(UiCommand::TopicRemoveSelected),
+    }),
+    action("Refresh browse results", Some(TabId::Browse), || UiCommand::BrowseRefresh),
+    action("Download selected browse results", Some(TabId::Browse), || UiCommand::BrowseDownloadSelected),
+    action("New download", Some(TabId::Downloads), || UiCommand::DownloadsAddOpen),
+    action("Resume interrupted downloads", Some(TabId::Downloads), || UiCommand::DownloadsResume),
+    action("Pause/resume selected downloads", Some(TabId::Downloads), || {
+        UiCommand::DownloadsTogglePauseSelected
+    }),
+    action("Cancel selected downloads", Some(TabId::Downloads), || UiCommand::Confirm {
+        prompt: "Cancel selected download(s)?".to_string(),
+        action: Box::new(UiCommand::DownloadsCancelSelected),
+    }),
+    action("Refresh downloads", Some(TabId::Downloads), || UiCommand::DownloadsRefresh),
+    action("Add files", Some(TabId::Files), || UiCommand::FilesAddOpen),
+    action("Add files by path", Some(TabId::Files), || UiCommand::FilesAddPathsOpen),
+    action("Re-add a recent path", Some(TabId::Files), || UiCommand::FilesRecentOpen),
+    action("Verify selected files", Some(TabId::Files), || UiCommand::FilesVerifySelected),
+    action("Remove selected files", Some(TabId::Files), || {
+        UiCommand::confirm_files_remove("Remove selected file(s)?".to_string())
+    }),
+    action("Switch repo", None, || UiCommand::RepoSwitchOpen),
+    action("Quit", None, || UiCommand::Quit),
+];
+
+/// `:`-triggered overlay that fuzzy-matches [`ACTIONS`] and returns the chosen one.
+#[derive(Default)]
+pub struct CommandPalette {
+    open: bool,
+    query: TextInput,
+    /// Indices into `ACTIONS` with their match positions, best match first.
+    filtered: Vec<(usize, Vec<usize>)>,
+    state: TableState,
+}
+
+impl CommandPalette {
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.refilter();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn refilter(&mut self) {
+        let q = self.query.value();
+        let mut hits: Vec<(i64, usize, Vec<usize>)> = ACTIONS
+            .iter()
+            .enumerate()
+            .filter_map(|(i, a)| fuzzy::score(a.label, q).map(|(score, idx)| (score, i, idx)))
+            .collect();
+        // Stable on ties so an empty query keeps the declared order.
+        hits.sort_by_key(|h| std::cmp::Reverse(h.0));
+        self.filtered = hits.into_iter().map(|(_, i, idx)| (i, idx)).collect();
+        self.state.select((!self.filtered.is_empty()).then_some(0));
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let cur = self.state.selected().unwrap_or(0) as isize;
+        let next = (cur + delta).clamp(0, self.filtered.len() as isize - 1);
+        self.state.select(Some(next as usize));
+    }
+
+    pub fn on_paste(&mut self, text: &str) {
+        if self.query.insert_str(text) {
+            self.refilter();
+        }
+    }
+
+    /// Returns the chosen action on Enter; Esc closes without one.
+    pub fn on_key(&mut self, key: KeyEvent) -> Option<&'static PaletteAction> {
+        match key.code {
+            KeyCode::Down | KeyCode::Tab => self.move_by(1),
+            KeyCode::Up | KeyCode::BackTab => self.move_by(-1),
+            _ => match self.query.handle_key(key) {
+                TextInputAction::Changed => self.refilter(),
+                TextInputAction::Cancel => self.open = false,
+                TextInputAction::Submit => {
+                    let (i, _) = self.filtered.get(self.state.selected()?)?;
+                    self.open = false;
+                    return Some(&ACTIONS[*i]);
+                }
+                TextInputAction::None => {}
+            },
+        }
+        None
+    }
+
+    pub fn draw(&mut self, f: &mut Frame, area: Rect) {
+        if !self.open {
+            return;
+        }
+        let inner = draw_modal_shell(f, 60, 60, area, "Command palette");
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        self.query.draw(f, chunks[0], "Command", true);
+
+        let rows: Vec<Row> = self
+            .filtered
+            .iter()
+            .map(|(i, idx)| Row::new(vec![fuzzy::highlight(ACTIONS[*i].label, idx)]))
+            .collect();
+        let table = Table::new(rows, [Constraint::Percentage(100)]).row_highlight_style(theme().highlight());
+        f.render_stateful_widget(table, chunks[1], &mut self.state);
+
+        let help = Paragraph::new("type to filter | ↑/↓ move | Enter run | Esc close").style(theme().muted());
+        f.render_widget(help, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn filters_and_picks_best_match() {
+        let mut p = CommandPalette::default();
+        p.open();
+        assert_eq!(p.filtered.len(), ACTIONS.len());
+        for c in "verif".chars() {
+            p.on_key(key(KeyCode::Char(c)));
+        }
+        let chosen = p.on_key(key(KeyCode::Enter)).expect("an action");
+        assert_eq!(chosen.label, "Verify selected files");
+        assert!(!p.is_open());
+
+        p.open();
+        for c in "zzz".chars() {
+            p.on_key(key(KeyCode::Char(c)));
+        }
+        assert!(p.on_key(key(KeyCode::Enter)).is_none());
+        assert!(p.is_open());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::app::App;
-use crate::ipc::IpcClient;
-use crate::tabs::{Tab, TabId, UiCommand};
+use crate::app::{App, LogLevel};
+use crate::ipc::{IpcClient, LONG_RPC_TIMEOUT};
+use crate::tabs::common::ActivityLog;
+use crate::tabs::{draw_empty_state, key_help, key_help_line, KeyHelp, Tab, TabId, UiCommand};
+use crate::theme::theme;
 use crate::widgets::{
     contains, handle_scrollbar_down, handle_scrollbar_drag, mouse_in, render_scrollbar, Button,
     MultiSelectState, MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec, TextInput,
@@ -9,17 +11,19 @@ use crate::widgets::{
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Text},
     widgets::{Block, Borders, Paragraph, Row, Table, TableState},
     Frame,
 };
 use serde_json::Value;
 use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub struct BrowseTab {
     endpoint: String,
@@ -43,9 +47,16 @@ pub struct BrowseTab {
     browse_rx: Receiver<(u64, Result<Value, String>)>,
     browse_req_id: u64,
     browse_busy: Option<(String, Instant)>,
+    browse_cancel: Option<Arc<AtomicBool>>,
+
+    download_rx: Receiver<(u64, Result<usize, String>)>,
+    download_req_id: u64,
 
     last_error: Option<String>,
+    // Short-lived confirmation shown in the footer (message, shown-at).
+    flash: Option<(String, Instant)>,
     hovered: BrowseHovered,
+    activity: ActivityLog,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -87,6 +98,7 @@ impl BrowseTab {
         results_state.select(Some(0));
 
         let (_tx, rx) = mpsc::channel::<(u64, Result<Value, String>)>();
+        let (_dtx, drx) = mpsc::channel::<(u64, Result<usize, String>)>();
         Self {
             endpoint,
             topics: Vec::new(),
@@ -109,12 +121,24 @@ impl BrowseTab {
             browse_rx: rx,
             browse_req_id: 0,
             browse_busy: None,
+            browse_cancel: None,
+
+            download_rx: drx,
+            download_req_id: 0,
 
             last_error: None,
+            flash: None,
             hovered: BrowseHovered::None,
+            activity: ActivityLog::default(),
         }
     }
 
+    /// Points the tab at another daemon, dropping everything cached from the old one.
+    pub fn set_endpoint(&mut self, endpoint: String) {
+        self.cancel_background();
+        *self = Self::new(endpoint);
+    }
+
     pub fn is_text_input_active(&self) -> bool {
         self.focus == BrowseFocus::Search
     }
@@ -126,6 +150,7 @@ impl BrowseTab {
             }
 
             self.browse_busy = None;
+            self.browse_cancel = None;
             match res {
                 Ok(v) => {
                     self.cache = parse_browse_cache(&v);
@@ -137,6 +162,30 @@ impl BrowseTab {
                 }
             }
         }
+
+        while let Ok((req_id, res)) = self.download_rx.try_recv() {
+            if req_id != self.download_req_id {
+                continue;
+            }
+
+            match res {
+                Ok(n) => {
+                    self.activity.push(format!("started {} download(s)", n));
+                    self.flash = Some((format!("started {} download(s)", n), Instant::now()));
+                    self.last_error = None;
+                }
+                Err(e) => {
+                    self.activity.push(format!("download failed: {}", e));
+                    self.last_error = Some(e);
+                }
+            }
+        }
+
+        if let Some((_, at)) = &self.flash {
+            if at.elapsed() > Duration::from_secs(3) {
+                self.flash = None;
+            }
+        }
     }
 
     pub fn refresh(&mut self, ipc: &mut IpcClient) {
@@ -193,16 +242,18 @@ impl BrowseTab {
         self.browse_reload_all_connected();
     }
 
-    pub fn browse_refresh(&mut self, _ipc: &mut IpcClient) {
-        self.browse_reload_all_connected();
+    pub fn browse_refresh(&mut self, _ipc: &mut IpcClient, app: &mut App) {
+        if !self.browse_reload_all_connected() {
+            app.toast(LogLevel::Warn, "no connected topics");
+        }
     }
 
-    fn browse_reload_all_connected(&mut self) {
+    /// Returns false when there is no connected topic to browse.
+    fn browse_reload_all_connected(&mut self) -> bool {
         // Async: browse all connected topics and cache results per topic.
         let topics: Vec<String> = self.topics.iter().map(|t| t.name.clone()).collect();
         if topics.is_empty() {
-            self.last_error = Some("no connected topics".to_string());
-            return;
+            return false;
         }
 
         let endpoint = self.endpoint.clone();
@@ -215,12 +266,23 @@ impl BrowseTab {
         self.browse_busy = Some((format!("browsing {} topic(s)", topics.len()), Instant::now()));
         self.last_error = None;
 
+        if let Some(prev) = self.browse_cancel.take() {
+            prev.store(true, Ordering::Relaxed);
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.browse_cancel = Some(cancel.clone());
+
         thread::spawn(move || {
             let res = (|| {
-                let mut c = crate::ipc::IpcClient::connect(endpoint).map_err(|e| e.to_string())?;
+                let mut c = crate::ipc::IpcClient::connect(endpoint)
+                    .map_err(|e| e.to_string())?
+                    .with_timeout(LONG_RPC_TIMEOUT);
 
                 let mut out: BTreeMap<String, Vec<Value>> = BTreeMap::new();
                 for name in topics {
+                    if cancel.load(Ordering::Relaxed) {
+                        return Err("browse cancelled".to_string());
+                    }
                     match c.rpc("browse.topic", serde_json::json!({"name": name, "timeout": 5000}))
                     {
                         Ok(v) => {
@@ -238,11 +300,64 @@ impl BrowseTab {
             })();
             let _ = tx.send((req_id, res));
         });
+        true
+    }
+
+    pub fn download_selected(&mut self, ipc: &mut IpcClient, app: &mut App) {
+        let _ = ipc;
+        let targets = self.selected_download_targets();
+        if targets.is_empty() {
+            app.toast(LogLevel::Warn, "no browse items selected");
+            return;
+        }
+
+        let endpoint = self.endpoint.clone();
+        let (tx, rx) = mpsc::channel::<(u64, Result<usize, String>)>();
+        self.download_rx = rx;
+
+        self.download_req_id = self.download_req_id.wrapping_add(1);
+        let req_id = self.download_req_id;
+        self.last_error = None;
+
+        thread::spawn(move || {
+            let res = (|| {
+                let mut c = crate::ipc::IpcClient::connect(endpoint)
+                    .map_err(|e| e.to_string())?
+                    .with_timeout(LONG_RPC_TIMEOUT);
+                let n = targets.len();
+                for r in targets {
+                    c.rpc(
+                        "downloads.start",
+                        serde_json::json!({
+                            "topic": r.topic,
+                            "merkleRoot": r.merkle_root,
+                            "outputPath": default_output_path(&r),
+                        }),
+                    )
+                    .map_err(|e| e.to_string())?;
+                }
+                Ok::<usize, String>(n)
+            })();
+            let _ = tx.send((req_id, res));
+        });
     }
 
-    pub fn download_selected(&mut self, _ipc: &mut IpcClient) {
-        // Action is routed via UiCommand from on_key/on_mouse.
-        let _ = _ipc;
+    fn selected_download_targets(&self) -> Vec<BrowseResultRow> {
+        let picked: Vec<BrowseResultRow> = self
+            .results
+            .iter()
+            .filter(|r| self.results_sel.is_selected(&r.merkle_root))
+            .cloned()
+            .collect();
+        if !picked.is_empty() {
+            return picked;
+        }
+        self.results_state
+            .selected()
+            .and_then(|i| self.results.get(i))
+            .cloned()
+            .into_iter()
+            .collect()
     }
 
     fn selected_download_target(&self) -> Option<(String, String)> {
@@ -422,12 +537,50 @@ impl BrowseTab {
     }
 }
 
+const KEY_HELP: &[KeyHelp] = &[
+    key_help("h/l ←/→", "focus topics/results"),
+    key_help("j/k ↑/↓", "move"),
+    key_help("J/K PgDn/PgUp", "page"),
+    key_help("Tab/Space", "toggle selection"),
+    key_help("Ctrl/Shift+click", "toggle / extend selection"),
+    key_help("Ctrl+a", "select all"),
+    key_help("c", "clear selection"),
+    key_help("/", "search"),
+    key_help("Esc", "clear search / leave search"),
+    key_help("Enter / d", "download selected"),
+    key_help("D", "download with options"),
+];
+
 impl Tab for BrowseTab {
     fn id(&self) -> TabId {
         TabId::Browse
     }
 
-    fn draw(&mut self, f: &mut Frame, area: Rect, _app: &mut App) {
+    fn footer_hint(&self) -> String {
+        if self.is_text_input_active() {
+            "type to search | Esc clear, then leave search".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    fn activity(&self) -> Option<&ActivityLog> {
+        Some(&self.activity)
+    }
+
+    fn key_help(&self) -> &'static [KeyHelp] {
+        KEY_HELP
+    }
+
+    fn cancel_background(&mut self) {
+        if let Some(cancel) = self.browse_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+            self.browse_req_id = self.browse_req_id.wrapping_add(1);
+            self.browse_busy = None;
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Rect, app: &mut App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(8), Constraint::Length(3)].as_ref())
@@ -441,7 +594,7 @@ impl Tab for BrowseTab {
         // Topics table
         self.topics_viewport_rows = main[0].height.saturating_sub(3).max(1) as usize;
         let topic_header = Row::new(vec!["Sel", "Topic", "Joined", "Peers"])
-            .style(Style::default().fg(Color::Yellow));
+            .style(theme().accent());
         let topic_rows = self.topics.iter().map(|t| {
             let mark = if self.topics_sel.is_selected(&t.name) { "[x]" } else { "[ ]" };
             let joined = if t.joined { "yes" } else { "no" };
@@ -453,7 +606,7 @@ impl Tab for BrowseTab {
             ])
         });
         let topic_table_style = if self.focus == BrowseFocus::Topics {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
@@ -473,12 +626,16 @@ impl Tab for BrowseTab {
                 .borders(Borders::ALL)
                 .border_style(topic_table_style),
         )
-        .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        .row_highlight_style(theme().highlight());
         f.render_stateful_widget(topics_table, main[0], &mut self.topics_state);
+        if self.topics.is_empty() {
+            let msg = "No joined topics — join one in the Network tab";
+            draw_empty_state(f, main[0].inner(Margin { vertical: 1, horizontal: 1 }), msg);
+        }
 
         // Public content area (search + results)
         let public_style = if self.focus == BrowseFocus::Search || self.focus == BrowseFocus::Results {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
@@ -498,7 +655,7 @@ impl Tab for BrowseTab {
         // Results table
         self.results_viewport_rows = public_chunks[1].height.saturating_sub(2).max(1) as usize;
         let results_header = Row::new(vec!["Sel", "Topic", "Name", "Size", "Chunks", "Root"])
-            .style(Style::default().fg(Color::Yellow));
+            .style(theme().accent());
         let result_rows = self.results.iter().map(|r| {
             let mark = if self.results_sel.is_selected(&r.merkle_root) {
                 "[x]"
@@ -534,7 +691,7 @@ impl Tab for BrowseTab {
         )
         .header(results_header)
         .block(Block::default().borders(Borders::NONE))
-        .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        .row_highlight_style(theme().highlight());
 
         let show_scrollbar = self.results.len() > self.results_viewport_rows;
         let mut results_area = public_chunks[1];
@@ -542,6 +699,17 @@ impl Tab for BrowseTab {
             results_area.width = results_area.width.saturating_sub(1);
         }
         f.render_stateful_widget(results_table, results_area, &mut self.results_state);
+        if self.results.is_empty() {
+            let q = self.query.value().trim();
+            let msg = if self.browse_busy.is_some() {
+                "Browsing…".to_string()
+            } else if !q.is_empty() {
+                format!("No results for '{}'", q)
+            } else {
+                "No public content yet — press r to browse joined topics".to_string()
+            };
+            draw_empty_state(f, results_area, &msg);
+        }
         let results_table_ctrl = MultiSelectTableController::new(TableHitTestSpec {
             header_rows: 1,
             inner_margin: Margin {
@@ -567,13 +735,14 @@ impl Tab for BrowseTab {
             .constraints([Constraint::Min(10), Constraint::Length(12), Constraint::Length(14)].as_ref())
             .split(chunks[1]);
 
-        let mut footer_lines: Vec<Line> = vec![Line::from(
-            "Keys: / focus search | tab/space toggle | Ctrl-click toggle | Shift-click range | drag-select resets | Ctrl+A all | c clear | r browse | Enter download | PgUp/PgDn",
-        )];
+        let mut footer_lines: Vec<Line> = vec![Line::from(key_help_line(KEY_HELP))];
         if let Some((msg, started)) = &self.browse_busy {
             let secs = started.elapsed().as_secs_f32();
             footer_lines.push(Line::from(format!("Busy: {} ({:.1}s)", msg, secs)));
         }
+        if let Some((msg, _)) = &self.flash {
+            footer_lines.push(Line::styled(msg.clone(), Style::default().fg(theme().ok)));
+        }
         if let Some(e) = &self.last_error {
             footer_lines.push(Line::from(format!("Error: {}", e)));
         }
@@ -589,12 +758,19 @@ impl Tab for BrowseTab {
 
         let download_btn = Button {
             label: "Download".to_string(),
-            enabled: !self.results_sel.selected().is_empty() || self.results_state.selected().is_some(),
+            enabled: !app.read_only
+                && (!self.results_sel.selected().is_empty() || self.results_state.selected().is_some()),
         };
         download_btn.draw(f, footer_chunks[2], self.hovered == BrowseHovered::Download);
     }
 
-    fn on_key(&mut self, key: KeyEvent, _app: &mut App) -> UiCommand {
+    fn on_paste(&mut self, text: &str, _app: &mut App) {
+        if self.focus == BrowseFocus::Search && self.query.insert_str(text) {
+            self.rebuild_results_from_cache();
+        }
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> UiCommand {
         match key.code {
             KeyCode::Char('/') => {
                 self.focus = BrowseFocus::Search;
@@ -670,12 +846,12 @@ impl Tab for BrowseTab {
                 }
                 BrowseFocus::Search => {}
             },
-            KeyCode::Char('r') => return UiCommand::BrowseRefresh,
-            KeyCode::Enter => {
+            KeyCode::Enter | KeyCode::Char('d') => return UiCommand::BrowseDownloadSelected,
+            KeyCode::Char('D') => {
                 if let Some((topic, merkle_root)) = self.selected_download_target() {
                     return UiCommand::DownloadsAddOpenPrefill { topic, merkle_root };
                 }
-                self.last_error = Some("no browse items selected".to_string());
+                app.toast(LogLevel::Warn, "no browse items selected");
                 return UiCommand::None;
             }
 
@@ -766,11 +942,7 @@ impl Tab for BrowseTab {
                     return UiCommand::BrowseRefresh;
                 }
                 if mouse_in(footer_chunks[2], &mouse) {
-                    if let Some((topic, merkle_root)) = self.selected_download_target() {
-                        return UiCommand::DownloadsAddOpenPrefill { topic, merkle_root };
-                    }
-                    self.last_error = Some("no browse items selected".to_string());
-                    return UiCommand::None;
+                    return UiCommand::BrowseDownloadSelected;
                 }
 
                 let topic_keys: Vec<String> = self.topics.iter().map(|t| t.name.clone()).collect();
@@ -914,6 +1086,17 @@ impl Tab for BrowseTab {
     }
 }
 
+/// Where a direct download from Browse lands: the entry's file name in the current directory.
+fn default_output_path(r: &BrowseResultRow) -> String {
+    let name = std::path::Path::new(&r.name)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| r.merkle_root.chars().take(16).collect());
+    let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    cwd.join(name).to_string_lossy().to_string()
+}
+
 fn parse_browse_cache(v: &Value) -> BTreeMap<String, Vec<BrowseResultRow>> {
     let mut out: BTreeMap<String, Vec<BrowseResultRow>> = BTreeMap::new();
 
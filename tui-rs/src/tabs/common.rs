@@ -1,7 +1,12 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
+    widgets::TableState,
 };
-use std::time::{SystemTime, UNIX_EPOCH};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub fn now_ms() -> u64 {
     SystemTime::now()
@@ -10,6 +15,28 @@ pub fn now_ms() -> u64 {
         .unwrap_or(0)
 }
 
+/// Formats a unix-ms timestamp relative to now ("just now", "5m ago", "3d ago").
+pub fn format_relative_time(ts_ms: i64) -> String {
+    let now = now_ms() as i64;
+    let secs = now.saturating_sub(ts_ms).max(0) / 1000;
+    if secs < 60 {
+        return "just now".to_string();
+    }
+    let mins = secs / 60;
+    if mins < 60 {
+        return format!("{}m ago", mins);
+    }
+    let hours = mins / 60;
+    if hours < 24 {
+        return format!("{}h ago", hours);
+    }
+    let days = hours / 24;
+    if days < 365 {
+        return format!("{}d ago", days);
+    }
+    format!("{}y ago", days / 365)
+}
+
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -38,6 +65,31 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     horizontal[1]
 }
 
+/// Default table share (percent) of the table/details split in the Files and Network tabs.
+pub const SPLIT_DEFAULT: u16 = 65;
+const SPLIT_MIN: u16 = 30;
+const SPLIT_MAX: u16 = 85;
+const SPLIT_STEP: u16 = 5;
+
+/// Clamps a table/details split so neither side disappears.
+pub fn clamp_split(percent: u16) -> u16 {
+    percent.clamp(SPLIT_MIN, SPLIT_MAX)
+}
+
+/// Moves the table/details split one step (`<`/`>`).
+pub fn adjust_split(percent: u16, grow_table: bool) -> u16 {
+    clamp_split(if grow_table {
+        percent.saturating_add(SPLIT_STEP)
+    } else {
+        percent.saturating_sub(SPLIT_STEP)
+    })
+}
+
+/// Horizontal constraints for the table/details split; `draw` and `on_mouse` must both use it.
+pub fn split_constraints(percent: u16) -> [Constraint; 2] {
+    [Constraint::Percentage(percent), Constraint::Percentage(100 - percent)]
+}
+
 pub fn progress_percent(verified: u64, total: u64) -> u64 {
     if total == 0 {
         return 0;
@@ -45,6 +97,16 @@ pub fn progress_percent(verified: u64, total: u64) -> u64 {
     ((verified.saturating_mul(100)) / total).min(100)
 }
 
+/// Elapsed time for busy indicators: `3.2s` under a minute, then `2m05s`.
+pub fn format_elapsed(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", d.as_secs_f64())
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
 pub fn format_bytes_per_sec(bps: u64) -> String {
     if bps == 0 {
         return "".to_string();
@@ -63,3 +125,284 @@ pub fn format_bytes_per_sec(bps: u64) -> String {
     let gb = mb / 1024.0;
     format!("{:.1} GiB/s", gb)
 }
+
+/// Copies `text` to the system clipboard. Needs the `clipboard` feature.
+#[cfg(feature = "clipboard")]
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut cb = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+    cb.set_text(text.to_string()).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn copy_to_clipboard(_text: &str) -> Result<(), String> {
+    Err("clipboard support not compiled in".to_string())
+}
+
+/// Opens the folder holding `path` (or `path` itself if it is a directory) in the OS file
+/// manager. Returns the folder that was opened.
+pub fn open_containing_folder(path: &Path) -> Result<PathBuf, String> {
+    let dir = if path.is_dir() {
+        path.to_path_buf()
+    } else {
+        path.parent()
+            .filter(|p| p.is_dir())
+            .ok_or_else(|| format!("{} does not exist", path.display()))?
+            .to_path_buf()
+    };
+    open_path(&dir)?;
+    Ok(dir)
+}
+
+/// Hands `path` to the platform opener (`xdg-open`, `open` or `explorer.exe`) without waiting
+/// for it. The path is passed as a single argument, never through a shell, so `&`, `%` and the
+/// like in file names are taken literally.
+pub fn open_path(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("open");
+    #[cfg(windows)]
+    let mut cmd = Command::new("explorer.exe");
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let mut cmd = Command::new("xdg-open");
+
+    let mut child = cmd
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("cannot run the system opener: {}", e))?;
+    // Reap it in the background so it doesn't linger as a zombie.
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Reads a non-negative count from JSON that may be an integer, a float (`3.0`) or a numeric
+/// string (`"3"`).
+pub fn value_as_u64(v: &Value) -> Option<u64> {
+    v.as_u64()
+        .or_else(|| v.as_f64().filter(|f| f.is_finite() && *f >= 0.0).map(|f| f as u64))
+        .or_else(|| {
+            let s = v.as_str()?.trim();
+            s.parse::<u64>()
+                .ok()
+                .or_else(|| s.parse::<f64>().ok().filter(|f| f.is_finite() && *f >= 0.0).map(|f| f as u64))
+        })
+}
+
+/// A table's focused row, captured before its rows are rebuilt so focus and scroll survive a
+/// refresh that reorders, adds or drops rows.
+#[derive(Debug, Clone)]
+pub struct FocusAnchor {
+    key: Option<String>,
+    index: usize,
+    /// Focused row's distance from the top of the viewport.
+    screen_row: usize,
+}
+
+impl FocusAnchor {
+    pub fn capture(state: &TableState, key: Option<String>) -> Option<Self> {
+        let index = state.selected()?;
+        Some(Self {
+            key,
+            index,
+            screen_row: index.saturating_sub(state.offset()),
+        })
+    }
+
+    /// Re-focuses the row with the captured key, or the nearest index if it vanished, keeping it
+    /// at the same screen row where possible. Returns the focused index.
+    pub fn restore<'a>(self, state: &mut TableState, keys: impl Iterator<Item = &'a str>) -> Option<usize> {
+        let mut len = 0;
+        let mut found = None;
+        for (i, k) in keys.enumerate() {
+            if found.is_none() && self.key.as_deref() == Some(k) {
+                found = Some(i);
+            }
+            len += 1;
+        }
+        if len == 0 {
+            state.select(None);
+            return None;
+        }
+        let idx = found.unwrap_or(self.index.min(len - 1));
+        state.select(Some(idx));
+        *state.offset_mut() = idx.saturating_sub(self.screen_row);
+        Some(idx)
+    }
+}
+
+/// Pinned row keys in their manual order; saved with the window state.
+#[derive(Debug, Default)]
+pub struct PinList {
+    keys: Vec<String>,
+}
+
+impl PinList {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self { keys }
+    }
+
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.keys.iter().any(|k| k == key)
+    }
+
+    /// Pins `key` at the end of the pinned group, or unpins it.
+    pub fn toggle(&mut self, key: &str) {
+        if self.is_pinned(key) {
+            self.keys.retain(|k| k != key);
+        } else {
+            self.keys.push(key.to_string());
+        }
+    }
+
+    /// Moves a pinned `key` by `delta` positions among the pinned keys that are in `visible`.
+    /// Keys that are pinned but currently absent (e.g. filtered out) keep their slots.
+    pub fn move_by(&mut self, key: &str, delta: isize, visible: &[&str]) -> bool {
+        let slots: Vec<usize> = self
+            .keys
+            .iter()
+            .enumerate()
+            .filter(|(_, k)| visible.contains(&k.as_str()))
+            .map(|(i, _)| i)
+            .collect();
+        let Some(pos) = slots.iter().position(|&i| self.keys[i] == key) else {
+            return false;
+        };
+        let target = pos as isize + delta;
+        if target < 0 || target as usize >= slots.len() {
+            return false;
+        }
+        self.keys.swap(slots[pos], slots[target as usize]);
+        true
+    }
+
+    /// Stable-partitions `items` so pinned ones come first, in pin order.
+    pub fn apply<T>(&self, items: &mut Vec<T>, key: impl Fn(&T) -> &str) {
+        let (mut pinned, rest): (Vec<T>, Vec<T>) =
+            items.drain(..).partition(|t| self.is_pinned(key(t)));
+        pinned.sort_by_key(|t| self.keys.iter().position(|k| k == key(t)));
+        items.extend(pinned);
+        items.extend(rest);
+    }
+}
+
+/// Entries kept per tab in the activity strip above the footer.
+pub const ACTIVITY_MAX: usize = 20;
+
+/// A tab's recent actions and their outcomes ("joined 2 topic(s)", "add failed: ..."), newest
+/// last. Unlike the daemon log this only records what the user did from this tab.
+#[derive(Debug, Default)]
+pub struct ActivityLog {
+    entries: VecDeque<(i64, String)>,
+}
+
+impl ActivityLog {
+    pub fn push(&mut self, text: impl Into<String>) {
+        if self.entries.len() >= ACTIVITY_MAX {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((now_ms() as i64, text.into()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Up to `n` most recent entries as `(unix ms, text)`, newest first.
+    pub fn recent(&self, n: usize) -> impl Iterator<Item = &(i64, String)> {
+        self.entries.iter().rev().take(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn value_as_u64_accepts_ints_floats_and_strings() {
+        assert_eq!(value_as_u64(&json!(3)), Some(3));
+        assert_eq!(value_as_u64(&json!(3.0)), Some(3));
+        assert_eq!(value_as_u64(&json!("3")), Some(3));
+        assert_eq!(value_as_u64(&json!(" 7.0 ")), Some(7));
+    }
+
+    #[test]
+    fn open_containing_folder_rejects_missing_paths() {
+        let err = open_containing_folder(Path::new("/no/such/dir/file.txt")).unwrap_err();
+        assert!(err.contains("does not exist"));
+    }
+
+    #[test]
+    fn adjust_split_is_clamped() {
+        assert_eq!(adjust_split(SPLIT_DEFAULT, true), 70);
+        assert_eq!(adjust_split(SPLIT_MAX, true), SPLIT_MAX);
+        assert_eq!(adjust_split(SPLIT_MIN, false), SPLIT_MIN);
+    }
+
+    #[test]
+    fn format_elapsed_switches_to_minutes() {
+        assert_eq!(format_elapsed(Duration::from_millis(3240)), "3.2s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m05s");
+    }
+
+    #[test]
+    fn value_as_u64_rejects_non_numeric() {
+        assert_eq!(value_as_u64(&json!(-1)), None);
+        assert_eq!(value_as_u64(&json!("abc")), None);
+        assert_eq!(value_as_u64(&json!(null)), None);
+    }
+
+    #[test]
+    fn focus_anchor_follows_key_and_falls_back_to_nearest_index() {
+        let mut state = TableState::default();
+        state.select(Some(5));
+        *state.offset_mut() = 3;
+
+        let rows = ["x", "a", "b", "c", "d", "e", "f", "g"];
+        let anchor = FocusAnchor::capture(&state, Some("c".to_string())).unwrap();
+        assert_eq!(anchor.restore(&mut state, rows.iter().copied()), Some(3));
+        assert_eq!((state.selected(), state.offset()), (Some(3), 1));
+
+        let anchor = FocusAnchor::capture(&state, Some("gone".to_string())).unwrap();
+        assert_eq!(anchor.restore(&mut state, ["a", "b"].iter().copied()), Some(1));
+
+        let anchor = FocusAnchor::capture(&state, None).unwrap();
+        assert_eq!(anchor.restore(&mut state, std::iter::empty()), None);
+        assert_eq!(state.selected(), None);
+    }
+
+    #[test]
+    fn pin_list_orders_pinned_first_and_moves_within_group() {
+        let mut pins = PinList::default();
+        pins.toggle("c");
+        pins.toggle("a");
+        let mut items = vec!["a", "b", "c", "d"];
+        pins.apply(&mut items, |s| s);
+        assert_eq!(items, ["c", "a", "b", "d"]);
+
+        assert!(pins.move_by("a", -1, &items));
+        pins.apply(&mut items, |s| s);
+        assert_eq!(items, ["a", "c", "b", "d"]);
+
+        // Unpinned rows and moves past the group edge are rejected.
+        assert!(!pins.move_by("b", -1, &items));
+        assert!(!pins.move_by("a", -1, &items));
+    }
+
+    #[test]
+    fn activity_log_is_bounded_and_newest_first() {
+        let mut log = ActivityLog::default();
+        assert!(log.is_empty());
+        for i in 0..ACTIVITY_MAX + 5 {
+            log.push(format!("action {}", i));
+        }
+        assert_eq!(log.recent(usize::MAX).count(), ACTIVITY_MAX);
+        let texts: Vec<&str> = log.recent(2).map(|(_, t)| t.as_str()).collect();
+        assert_eq!(texts, vec!["action 24", "action 23"]);
+    }
+}
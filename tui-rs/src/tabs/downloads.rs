@@ -1,16 +1,17 @@
-use crate::app::App;
-use crate::ipc::IpcClient;
-use crate::tabs::{Tab, TabId, UiCommand};
-use crate::tabs::common::{format_bytes_per_sec, now_ms, progress_percent};
+use crate::app::{App, LogLevel};
+use crate::ipc::{remote_error_code, IpcClient};
+use crate::tabs::{draw_empty_state, key_help, key_help_line, KeyHelp, Tab, TabId, UiCommand};
+use crate::tabs::common::{format_bytes_per_sec, now_ms, open_containing_folder, progress_percent, ActivityLog};
+use crate::theme::theme;
 use crate::widgets::{
     compute_scrollbar_metrics, contains, handle_scrollbar_down, handle_scrollbar_drag, mouse_in,
     cycle_focus_next, cycle_focus_prev, modal_geometry, draw_modal_shell, render_scrollbar, Button,
-    MultiSelectState, MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec,
+    sanitize_paste, MultiSelectState, MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span, Text},
     widgets::{Block, Borders, Gauge, Paragraph, Row, Table, TableState},
     Frame,
@@ -26,9 +27,13 @@ pub struct DownloadsTab {
     hovered: DownloadsHovered,
     last_error: Option<String>,
     live: BTreeMap<DownloadKey, LiveDownload>,
+    // Downloads paused from this UI (or reported paused by the daemon).
+    paused: BTreeSet<i64>,
     add: DownloadsAddState,
 
     drag_select_start: Option<usize>,
+
+    activity: ActivityLog,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -79,6 +84,7 @@ struct DownloadRow {
     merkle_root: String,
     output_path: String,
     completed_at: Option<i64>,
+    paused: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -116,6 +122,7 @@ impl DownloadsTab {
             hovered: DownloadsHovered::None,
             last_error: None,
             live: BTreeMap::new(),
+            paused: BTreeSet::new(),
             add: DownloadsAddState {
                 open: false,
                 focus: DownloadsAddFocus::Topic,
@@ -128,6 +135,7 @@ impl DownloadsTab {
             },
 
             drag_select_start: None,
+            activity: ActivityLog::default(),
         }
     }
 
@@ -168,7 +176,7 @@ impl DownloadsTab {
         self.add.open = false;
     }
 
-    pub fn add_confirm(&mut self, ipc: &mut IpcClient) {
+    pub fn add_confirm(&mut self, ipc: &mut IpcClient, app: &mut App) {
         if !self.add.open {
             return;
         }
@@ -179,18 +187,18 @@ impl DownloadsTab {
             .and_then(|i| self.add.topics.get(i))
             .map(|t| t.name.clone())
         else {
-            self.last_error = Some("topic required".to_string());
+            app.toast(LogLevel::Warn, "topic required");
             return;
         };
 
         let root = self.add.merkle_root.trim().to_string();
         if root.is_empty() {
-            self.last_error = Some("merkle root required".to_string());
+            app.toast(LogLevel::Warn, "merkle root required");
             return;
         }
         let dest = self.add.destination.trim().to_string();
         if dest.is_empty() {
-            self.last_error = Some("destination required".to_string());
+            app.toast(LogLevel::Warn, "destination required");
             return;
         }
 
@@ -201,11 +209,13 @@ impl DownloadsTab {
         });
         match ipc.rpc("downloads.start", params) {
             Ok(_) => {
+                self.activity.push(format!("started download from {} to {}", topic, dest));
                 self.add.open = false;
                 self.last_error = None;
                 self.refresh(ipc);
             }
             Err(e) => {
+                self.activity.push(format!("download failed: {}", e));
                 self.last_error = Some(e.to_string());
             }
         }
@@ -335,6 +345,7 @@ impl DownloadsTab {
                                 .unwrap_or("")
                                 .to_string(),
                             completed_at: it.get("completed_at").and_then(|x| x.as_i64()),
+                            paused: it.get("paused").and_then(|x| x.as_bool()).unwrap_or(false),
                         })
                     })
                     .collect();
@@ -347,6 +358,12 @@ impl DownloadsTab {
 
                 let existing: BTreeSet<i64> = self.entries.iter().map(|e| e.id).collect();
                 self.selection.retain_existing(&existing);
+                self.paused.retain(|id| existing.contains(id));
+                for e in &self.entries {
+                    if e.paused {
+                        self.paused.insert(e.id);
+                    }
+                }
 
                 let existing_live: BTreeSet<DownloadKey> = self
                     .entries
@@ -366,6 +383,113 @@ impl DownloadsTab {
         }
     }
 
+    /// Shows the focused download's folder in the OS file manager. Relative output paths are
+    /// taken from the repo root, where the daemon runs.
+    fn open_focused_folder(&self, app: &mut App) {
+        let Some(row) = self.table_state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        match open_containing_folder(&app.repo_root.join(&row.output_path)) {
+            Ok(dir) => app.toast(LogLevel::Info, format!("opened {}", dir.display())),
+            Err(e) => app.toast(LogLevel::Error, e),
+        }
+    }
+
+    fn selected_rows(&self) -> Vec<DownloadRow> {
+        let picked: Vec<DownloadRow> = self
+            .entries
+            .iter()
+            .filter(|e| self.selection.is_selected(&e.id))
+            .cloned()
+            .collect();
+        if !picked.is_empty() {
+            return picked;
+        }
+        self.table_state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+            .cloned()
+            .into_iter()
+            .collect()
+    }
+
+    fn confirm_cancel(&self) -> UiCommand {
+        let rows = self.selected_rows();
+        if rows.is_empty() {
+            return UiCommand::None;
+        }
+        let unfinished = rows.iter().filter(|r| r.completed_at.is_none()).count();
+        let prompt = if unfinished > 0 {
+            format!("Cancel {} download(s)? Partial files of {} unfinished are deleted.", rows.len(), unfinished)
+        } else {
+            format!("Remove {} finished download(s) from the list?", rows.len())
+        };
+        UiCommand::Confirm { prompt, action: Box::new(UiCommand::DownloadsCancelSelected) }
+    }
+
+    pub fn cancel_selected(&mut self, ipc: &mut IpcClient) {
+        let rows = self.selected_rows();
+        if rows.is_empty() {
+            return;
+        }
+
+        for row in &rows {
+            if let Err(e) = ipc.rpc("downloads.cancel", download_params(row)) {
+                if !is_gone(&e) {
+                    self.activity.push(format!("cancel failed: {}", e));
+                    self.last_error = Some(e.to_string());
+                    return;
+                }
+            }
+            self.paused.remove(&row.id);
+        }
+
+        self.activity.push(format!("cancelled {} download(s)", rows.len()));
+        self.last_error = None;
+        self.refresh(ipc);
+    }
+
+    pub fn toggle_pause_selected(&mut self, ipc: &mut IpcClient) {
+        let rows = self.selected_rows();
+        if rows.is_empty() {
+            return;
+        }
+
+        let (mut paused, mut resumed) = (0, 0);
+        for row in &rows {
+            if row.completed_at.is_some() {
+                continue;
+            }
+            let resume = self.paused.contains(&row.id);
+            let method = if resume { "downloads.resume" } else { "downloads.pause" };
+            match ipc.rpc(method, download_params(row)) {
+                Ok(_) => {
+                    if resume {
+                        self.paused.remove(&row.id);
+                        resumed += 1;
+                    } else {
+                        self.paused.insert(row.id);
+                        paused += 1;
+                    }
+                }
+                Err(e) if is_gone(&e) => {
+                    self.paused.remove(&row.id);
+                }
+                Err(e) => {
+                    self.activity.push(format!("pause/resume failed: {}", e));
+                    self.last_error = Some(e.to_string());
+                    return;
+                }
+            }
+        }
+
+        if paused + resumed > 0 {
+            self.activity.push(format!("paused {}, resumed {} download(s)", paused, resumed));
+        }
+        self.last_error = None;
+        self.refresh(ipc);
+    }
+
     fn draw_add_modal(&mut self, f: &mut Frame, area: Rect) {
         let inner = draw_modal_shell(f, 80, 80, area, "Add download");
         let chunks = Layout::default()
@@ -382,11 +506,11 @@ impl DownloadsTab {
             .split(inner);
 
         let topic_border = if self.add.focus == DownloadsAddFocus::Topic {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
-        let header = Row::new(vec!["Topic", "Peers"]).style(Style::default().fg(Color::Yellow));
+        let header = Row::new(vec!["Topic", "Peers"]).style(theme().accent());
         let rows = self
             .add
             .topics
@@ -407,7 +531,7 @@ impl DownloadsTab {
                     .borders(Borders::ALL)
                     .border_style(topic_border),
             )
-            .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow))
+            .row_highlight_style(theme().highlight())
             .style(Style::default());
         f.render_stateful_widget(table, topics_area, &mut self.add.topics_state);
 
@@ -418,7 +542,7 @@ impl DownloadsTab {
         }
 
         let mr_border = if self.add.focus == DownloadsAddFocus::MerkleRoot {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
@@ -431,7 +555,7 @@ impl DownloadsTab {
         f.render_widget(mr, chunks[1]);
 
         let dst_border = if self.add.focus == DownloadsAddFocus::Destination {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
@@ -476,12 +600,42 @@ impl DownloadsTab {
     }
 }
 
+const KEY_HELP: &[KeyHelp] = &[
+    key_help("j/k ↑/↓", "move"),
+    key_help("J/K PgDn/PgUp", "page"),
+    key_help("Tab", "toggle selection"),
+    key_help("Ctrl/Shift+click", "toggle / extend selection"),
+    key_help("A / Ctrl+a", "select all"),
+    key_help("c", "clear selection"),
+    key_help("Space", "pause/resume selected"),
+    key_help("x / Del", "cancel selected"),
+    key_help("n", "new download"),
+    key_help("R", "resume interrupted downloads"),
+    key_help("O", "open the download's folder"),
+];
+
 impl Tab for DownloadsTab {
     fn id(&self) -> TabId {
         TabId::Downloads
     }
 
-    fn draw(&mut self, f: &mut Frame, area: Rect, _app: &mut App) {
+    fn footer_hint(&self) -> String {
+        if self.add.open {
+            "new download: Tab/Shift+Tab switch fields | Enter confirm | Esc abort".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    fn activity(&self) -> Option<&ActivityLog> {
+        Some(&self.activity)
+    }
+
+    fn key_help(&self) -> &'static [KeyHelp] {
+        KEY_HELP
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Rect, app: &mut App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(8), Constraint::Length(3)].as_ref())
@@ -501,7 +655,7 @@ impl Tab for DownloadsTab {
             "Status",
             "Output",
         ])
-        .style(Style::default().fg(Color::Yellow));
+        .style(theme().accent());
 
         let now = now_ms();
         let rows = self.entries.iter().map(|e| {
@@ -520,6 +674,8 @@ impl Tab for DownloadsTab {
 
             let (speed, status, row_style) = if e.completed_at.is_some() {
                 ("".to_string(), "done".to_string(), Style::default())
+            } else if self.paused.contains(&e.id) {
+                ("".to_string(), "[PAUSED]".to_string(), Style::default().fg(theme().dim))
             } else if let Some(l) = self.live.get(&lk) {
                 if l.error.is_some() {
                     ("".to_string(), "error".to_string(), Style::default())
@@ -563,9 +719,13 @@ impl Tab for DownloadsTab {
             .header(header)
             .block(Block::default().title("Downloads").borders(Borders::ALL))
             .column_spacing(0)
-            .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+            .row_highlight_style(theme().highlight());
 
         f.render_stateful_widget(table, list_area, &mut self.table_state);
+        if self.entries.is_empty() {
+            let msg = "No downloads yet — press n to start one";
+            draw_empty_state(f, list_area.inner(Margin { vertical: 1, horizontal: 1 }), msg);
+        }
 
         // Gauge overlay: Ratatui tables are text-only, so we render gauges on top of the
         // Progress column after rendering the table.
@@ -589,34 +749,41 @@ impl Tab for DownloadsTab {
                 };
 
                 let (pct, label, color) = if e.completed_at.is_some() {
-                    (100, "Complete".to_string(), Color::Green)
+                    (100, "Complete".to_string(), theme().ok)
+                } else if self.paused.contains(&e.id) {
+                    let pct = self
+                        .live
+                        .get(&lk)
+                        .map(|l| progress_percent(l.verified, l.total))
+                        .unwrap_or(0);
+                    (pct, "Paused".to_string(), theme().dim)
                 } else if let Some(l) = self.live.get(&lk) {
                     if l.error.is_some() {
                         (
                             progress_percent(l.verified, l.total),
                             "Error".to_string(),
-                            Color::Red,
+                            theme().error,
                         )
                     } else if l.completed {
-                        (100, "Complete".to_string(), Color::Green)
+                        (100, "Complete".to_string(), theme().ok)
                     } else {
                         let stalled = now.saturating_sub(l.last_ts) > 3000;
                         if stalled {
                             (
                                 progress_percent(l.verified, l.total),
                                 "Verifying".to_string(),
-                                Color::Yellow,
+                                theme().accent,
                             )
                         } else {
                             (
                                 progress_percent(l.verified, l.total),
                                 "Downloading".to_string(),
-                                Color::White,
+                                theme().text,
                             )
                         }
                     }
                 } else {
-                    (0, "Pending".to_string(), Color::Gray)
+                    (0, "Pending".to_string(), theme().muted)
                 };
 
                 let y = progress_col.y.saturating_add(1).saturating_add(rel as u16);
@@ -634,7 +801,7 @@ impl Tab for DownloadsTab {
                 if gauge_area.width >= 3 {
                     let ratio = (pct.min(100) as f64) / 100.0;
                     let g = Gauge::default()
-                        .gauge_style(Style::default().fg(color).bg(Color::Black))
+                        .gauge_style(Style::default().fg(color).bg(theme().surface))
                         .ratio(ratio)
                         .label(Span::raw(label));
                     f.render_widget(g, gauge_area);
@@ -647,9 +814,7 @@ impl Tab for DownloadsTab {
             .constraints([Constraint::Min(10), Constraint::Length(12), Constraint::Length(12)].as_ref())
             .split(footer_area);
 
-        let mut footer_lines: Vec<Line> = vec![Line::from(
-            "Keys: n new | r refresh | R resume | tab/space toggle | Ctrl-click toggle | Shift-click range | drag-select resets | Ctrl+A all | c clear | j/k move | PgUp/PgDn",
-        )];
+        let mut footer_lines: Vec<Line> = vec![Line::from(key_help_line(KEY_HELP))];
         if let Some(e) = &self.last_error {
             footer_lines.push(Line::from(format!("Error: {}", e)));
         }
@@ -666,7 +831,7 @@ impl Tab for DownloadsTab {
 
         let resume_btn = Button {
             label: "Resume".to_string(),
-            enabled: true,
+            enabled: !app.read_only,
         };
         resume_btn.draw(f, footer_chunks[2], self.hovered == DownloadsHovered::Resume);
 
@@ -675,7 +840,18 @@ impl Tab for DownloadsTab {
         }
     }
 
-    fn on_key(&mut self, key: KeyEvent, _app: &mut App) -> UiCommand {
+    fn on_paste(&mut self, text: &str, _app: &mut App) {
+        if !self.add.open {
+            return;
+        }
+        match self.add.focus {
+            DownloadsAddFocus::MerkleRoot => self.add.merkle_root.push_str(&sanitize_paste(text)),
+            DownloadsAddFocus::Destination => self.add.destination.push_str(&sanitize_paste(text)),
+            _ => {}
+        }
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> UiCommand {
         if self.add.open {
             const ORDER: [DownloadsAddFocus; 5] = [
                 DownloadsAddFocus::Topic,
@@ -740,7 +916,6 @@ impl Tab for DownloadsTab {
 
         match key.code {
             KeyCode::Char('n') => return UiCommand::DownloadsAddOpen,
-            KeyCode::Char('r') => return UiCommand::DownloadsRefresh,
             KeyCode::Char('R') => return UiCommand::DownloadsResume,
             KeyCode::Char('j') | KeyCode::Down => {
                 let next = match self.table_state.selected() {
@@ -780,7 +955,10 @@ impl Tab for DownloadsTab {
                     self.selection.set_anchor(Some(next));
                 }
             }
-            KeyCode::Tab | KeyCode::Char(' ') => {
+            KeyCode::Char(' ') => return UiCommand::DownloadsTogglePauseSelected,
+            KeyCode::Char('O') => self.open_focused_folder(app),
+            KeyCode::Char('x') | KeyCode::Delete => return self.confirm_cancel(),
+            KeyCode::Tab => {
                 if let Some(i) = self.table_state.selected() {
                     if let Some(e) = self.entries.get(i) {
                         self.selection.toggle(e.id, i);
@@ -1059,6 +1237,20 @@ fn fetch_topics(ipc: &mut IpcClient) -> Vec<DownloadsAddTopicRow> {
     out
 }
 
+fn download_params(row: &DownloadRow) -> Value {
+    serde_json::json!({
+        "id": row.id,
+        "topic": row.topic,
+        "merkleRoot": row.merkle_root,
+        "outputPath": row.output_path,
+    })
+}
+
+/// The download finished or was removed between selection and the RPC.
+fn is_gone(e: &anyhow::Error) -> bool {
+    matches!(remote_error_code(e), Some("not_found" | "completed"))
+}
+
 fn parse_download_key(v: &Value) -> Option<DownloadKey> {
     let topic = v.get("topic")?.as_str()?.to_string();
     let merkle_root = v.get("merkleRoot")?.as_str()?.to_string();
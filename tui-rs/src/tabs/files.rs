@@ -1,54 +1,128 @@
-use crate::app::App;
+use crate::app::{App, LogLevel};
+use crate::config::VerifyRecord;
 use crate::file_picker::{FilePicker, PickerAction};
-use crate::ipc::IpcClient;
-use crate::tabs::{Tab, TabId, UiCommand};
+use crate::fuzzy;
+use crate::ipc::{IpcClient, LONG_RPC_TIMEOUT};
+use crate::tabs::common::{
+    adjust_split, clamp_split, format_elapsed, format_relative_time, now_ms, open_containing_folder, progress_percent,
+    split_constraints, value_as_u64, ActivityLog, FocusAnchor, PinList, SPLIT_DEFAULT,
+};
+use crate::tabs::{draw_empty_state, key_help, key_help_line, KeyHelp, Tab, TabId, UiCommand};
+use crate::theme::theme;
 use crate::widgets::{
-    handle_scrollbar_down, handle_scrollbar_drag, mouse_in, render_scrollbar, Button,
-    MultiSelectState, MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec,
+    compute_scrollbar_metrics_with_margin, draw_modal_shell, ensure_visible, format_bytes_short, handle_scrollbar_down,
+    handle_scrollbar_drag, mouse_in, render_scrollbar, render_table_viewport, scroll_viewport, truncate_middle, Button,
+    JsonTreeState, MultiSelectState, MultiSelectTableController, RowJumpAction, ScrollbarDownResult, TableHitTestSpec,
+    TextInput, TextInputAction,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::{Line, Text},
-    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 use serde_json::Value;
-use std::collections::BTreeSet;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 
 pub struct FilesTab {
+    // Visible rows (tree-flattened when `tree_view` is on); `model` holds the sorted flat list.
     entries: Vec<FileEntryRow>,
+    model: FilesModel,
+    tree_view: bool,
+    // Dirs whose children are shown; dirs start collapsed so large trees stay manageable.
+    expanded: BTreeSet<String>,
+    // Fuzzy path filter (`/`); while set, `entries` is a flat list of matches.
+    filter: TextInput,
+    filter_editing: bool,
+    // Matched char positions in each visible row's path, parallel to `entries`.
+    filter_matches: Vec<Vec<usize>>,
     table_state: TableState,
     selection: MultiSelectState<String>,
     scrollbar_drag: Option<usize>,
     drag_select_start: Option<usize>,
     last_viewport_rows: usize,
+    // The wheel scrolled the table away from the focused row; cleared by keys and clicks.
+    viewport_detached: bool,
+    // Table share of the table/details split; `<`/`>` adjust it.
+    split_percent: u16,
     endpoint: String,
     info_rx: Receiver<(u64, String, Result<Value, String>)>,
     info_req_id: u64,
     verify_rx: Receiver<(u64, VerifyMsg)>,
     verify_req_id: u64,
+    // `files.add` workers report here; several adds may be in flight at once.
+    add_tx: Sender<AddDone>,
+    add_rx: Receiver<AddDone>,
+    adds_running: usize,
     verify_progress: Option<(usize, usize)>,
+    /// When the running verify started; only meaningful while `verify_progress` is set.
+    verify_started: Instant,
+    // Cancellation flag shared with the in-flight verify worker.
+    verify_cancel: Option<Arc<AtomicBool>>,
+    // Set by `C` until the worker reports its partial results.
+    verify_cancelling: bool,
+    // Verify requests made while one is running; each entry is one batch of paths.
+    verify_queue: VecDeque<Vec<String>>,
+    row_ops: HashMap<String, RowOpState>,
     focused_path: Option<String>,
     last_error: Option<String>,
-    last_info: Option<Value>,
-    last_verify: Option<Value>,
+    // Full-screen `files.info` viewer: scroll offset while open, and its last viewport height.
+    info_viewer: Option<usize>,
+    info_viewer_rows: usize,
+    info_tree: JsonTreeState,
+    wrap_details: bool,
+    last_add: Option<AddSummary>,
+    /// "Add by path" modal, when open.
+    add_paths: Option<AddPathsState>,
+    /// Paths successfully added, newest first; persisted with the window state.
+    recent: VecDeque<String>,
+    /// "Re-add recent" menu, when open.
+    recent_menu: Option<TableState>,
+    activity: ActivityLog,
+    verify_history: VerifyHistory,
     hovered: FilesHovered,
     picker: FilePicker,
+    // Directory the picker was last closed in; reopened there while it still exists.
+    last_dir: Option<PathBuf>,
+    sort: FilesSort,
+    sort_desc: bool,
+    // Manually ordered pinned paths, shown above everything else.
+    pins: PinList,
+}
+
+/// Outcome of one `files.add` worker; `missing` paths were never sent.
+struct AddDone {
+    roots: Vec<String>,
+    missing: Vec<(String, String)>,
+    res: Result<Value, String>,
 }
 
 #[derive(Debug, Clone)]
 enum VerifyMsg {
-    Progress { done: usize, total: usize },
+    /// `current` is the path about to be verified (`None` once all are done).
+    Progress { done: usize, total: usize, current: Option<String> },
+    Item { path: String, ok: bool },
     Done { value: Value },
     Error { message: String },
 }
 
+/// Per-row state within the current verify batch; finished rows show their result in the
+/// OK column instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowOpState {
+    Pending,
+    Running,
+}
+
 #[derive(Debug, Clone)]
 struct FileEntryRow {
     typ: String,
@@ -56,6 +130,237 @@ struct FileEntryRow {
     size: Option<u64>,
     chunks: Option<u64>,
     merkle_root: Option<String>,
+    added_at: Option<i64>,
+    tree: TreePos,
+}
+
+impl FileEntryRow {
+    /// A tracked file the daemon has not hashed yet; drawn dimmed.
+    fn is_unhashed(&self) -> bool {
+        self.typ == "f" && self.merkle_root.is_none()
+    }
+}
+
+/// Tracked entries from `files.list` plus the per-path results fetched for them, so a refresh
+/// and the draw code agree on one source of truth.
+#[derive(Debug, Default)]
+struct FilesModel {
+    /// Flat list in display order (sorted and pinned by the tab).
+    entries: Vec<FileEntryRow>,
+    /// `files.info` results by path.
+    info: HashMap<String, Value>,
+    /// Outcome of the last verify of each path in this session (`true` = passed).
+    verify: HashMap<String, bool>,
+    /// The last finished `files.verify` batch, shown in the details pane.
+    verify_report: Option<Value>,
+}
+
+impl FilesModel {
+    fn entries(&self) -> &[FileEntryRow] {
+        &self.entries
+    }
+
+    fn entry(&self, path: &str) -> Option<&FileEntryRow> {
+        self.entries.iter().find(|e| e.path == path)
+    }
+
+    /// Replaces the entries with a fresh snapshot. Results are dropped for paths that are gone,
+    /// and verify marks also for rows whose content changed (re-added or a new merkle root).
+    fn set_entries(&mut self, entries: Vec<FileEntryRow>) {
+        let old: HashMap<&str, &FileEntryRow> = self.entries.iter().map(|e| (e.path.as_str(), e)).collect();
+        let fresh: HashMap<&str, &FileEntryRow> = entries.iter().map(|e| (e.path.as_str(), e)).collect();
+        self.info.retain(|p, _| fresh.contains_key(p.as_str()));
+        self.verify.retain(|p, _| match (old.get(p.as_str()), fresh.get(p.as_str())) {
+            (Some(o), Some(n)) => o.added_at == n.added_at && o.merkle_root == n.merkle_root,
+            _ => false,
+        });
+        self.entries = entries;
+    }
+
+    fn info(&self, path: &str) -> Option<&Value> {
+        self.info.get(path)
+    }
+
+    fn set_info(&mut self, path: String, info: Value) {
+        self.info.insert(path, info);
+    }
+
+    fn verify(&self, path: &str) -> Option<bool> {
+        self.verify.get(path).copied()
+    }
+
+    fn set_verify(&mut self, path: &str, ok: bool) {
+        self.verify.insert(path.to_string(), ok);
+    }
+
+    fn clear_verify(&mut self) {
+        self.verify.clear();
+    }
+
+    /// One-line summary of `verify_report`.
+    fn verify_summary(&self) -> Option<String> {
+        let v = self.verify_report.as_ref()?;
+        let count = |k: &str| v.get("summary").and_then(|s| s.get(k)).and_then(|x| x.as_u64());
+        let (ok, failed) = (count("ok").unwrap_or(0), count("failed").unwrap_or(0));
+        let total = count("total").unwrap_or(ok + failed);
+        let mut summary = format!("verify: {} ok, {} failed ({} total)", ok, failed, total);
+        if v.get("cancelled").and_then(|x| x.as_bool()) == Some(true) {
+            let requested = v.get("requested").and_then(|x| x.as_u64()).unwrap_or(total);
+            summary.push_str(&format!(", cancelled after {} of {}", total, requested));
+        }
+        Some(summary)
+    }
+
+    /// Forgets verify results for `roots` and anything tracked beneath them.
+    fn clear_verify_under(&mut self, roots: &[String]) {
+        let under = |p: &str| {
+            roots.iter().any(|r| {
+                let r = r.trim_end_matches('/');
+                p == r || (p.starts_with(r) && p.as_bytes().get(r.len()) == Some(&b'/'))
+            })
+        };
+        self.verify.retain(|p, _| !under(p));
+    }
+}
+
+/// Where a row sits in the directory tree; all zero in the flat view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TreePos {
+    depth: usize,
+    has_children: bool,
+    /// Byte offset into `path` where the name relative to the parent dir starts.
+    name_start: usize,
+}
+
+const VERIFY_HISTORY_MAX: usize = 50;
+/// Entries kept in the Files tab's re-add menu.
+const RECENT_ADDS_MAX: usize = 20;
+
+/// Widths of the Files table columns before Path: Sel, Type, OK, Size, Chunks, Root, Added.
+const FILES_FIXED_COLUMNS: [u16; 7] = [4, 5, 3, 12, 8, 14, 9];
+
+/// Bounded log of completed verify runs, saved with the window state so trends survive restarts.
+#[derive(Debug, Default)]
+struct VerifyHistory {
+    records: VecDeque<VerifyRecord>,
+}
+
+impl VerifyHistory {
+    fn new(records: Vec<VerifyRecord>) -> Self {
+        let mut h = Self { records: records.into() };
+        h.truncate();
+        h
+    }
+
+    fn push(&mut self, record: VerifyRecord) {
+        self.records.push_back(record);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.records.len() > VERIFY_HISTORY_MAX {
+            self.records.pop_front();
+        }
+    }
+
+    /// "last N runs: K with failures" plus one bar per run, scaled by its failure ratio.
+    fn trend_line(&self, last: usize) -> Option<Line<'static>> {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        if self.records.is_empty() {
+            return None;
+        }
+        let recent: Vec<&VerifyRecord> = self.records.iter().rev().take(last).rev().collect();
+        let with_failures = recent.iter().filter(|r| r.failed > 0).count();
+        let mut spans = vec![Span::raw(format!(
+            "last {} runs: {} with failures ",
+            recent.len(),
+            with_failures
+        ))];
+        for r in recent {
+            let total = (r.ok + r.failed).max(1);
+            let idx = ((r.failed * (BARS.len() as u64 - 1)).div_ceil(total)) as usize;
+            let color = if r.failed > 0 { theme().error } else { theme().ok };
+            spans.push(Span::styled(BARS[idx].to_string(), Style::default().fg(color)));
+        }
+        Some(Line::from(spans))
+    }
+}
+
+/// Per-path outcome of a `files.add` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AddSummary {
+    added: usize,
+    total: usize,
+    /// (path, reason) for every path the daemon rejected.
+    failed: Vec<(String, String)>,
+}
+
+impl AddSummary {
+    fn status_line(&self) -> Option<String> {
+        if self.failed.is_empty() {
+            return None;
+        }
+        if self.added == 0 {
+            return Some(format!("add failed for all {} path(s) (see details)", self.total));
+        }
+        Some(format!(
+            "added {}/{}, {} failed (see details)",
+            self.added,
+            self.total,
+            self.failed.len()
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilesSort {
+    /// Daemon order (files first, then dirs).
+    None,
+    Added,
+    Path,
+    Size,
+    Chunks,
+}
+
+impl FilesSort {
+    fn name(self) -> &'static str {
+        match self {
+            FilesSort::None => "none",
+            FilesSort::Added => "added",
+            FilesSort::Path => "path",
+            FilesSort::Size => "size",
+            FilesSort::Chunks => "chunks",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [FilesSort::None, FilesSort::Added, FilesSort::Path, FilesSort::Size, FilesSort::Chunks]
+            .into_iter()
+            .find(|s| s.name() == name)
+    }
+
+    /// Next column for the `s` key, with the direction it starts in (true = descending).
+    fn next(self) -> (Self, bool) {
+        match self {
+            FilesSort::None => (FilesSort::Added, true),
+            FilesSort::Added => (FilesSort::Path, false),
+            FilesSort::Path => (FilesSort::Size, true),
+            FilesSort::Size => (FilesSort::Chunks, true),
+            FilesSort::Chunks => (FilesSort::None, false),
+        }
+    }
+}
+
+/// Orders present values by `desc`, keeping rows without a value at the end either way.
+fn cmp_present_first<T: Ord>(a: &Option<T>, b: &Option<T>, desc: bool) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a, b) {
+        (Some(x), Some(y)) if desc => y.cmp(x),
+        (Some(x), Some(y)) => x.cmp(y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -74,33 +379,114 @@ impl FilesTab {
 
         let (_tx, rx) = mpsc::channel::<(u64, String, Result<Value, String>)>();
         let (_vtx, vrx) = mpsc::channel::<(u64, VerifyMsg)>();
+        let (add_tx, add_rx) = mpsc::channel::<AddDone>();
         Self {
             entries: Vec::new(),
+            model: FilesModel::default(),
+            tree_view: true,
+            expanded: BTreeSet::new(),
+            filter: TextInput::new(),
+            filter_editing: false,
+            filter_matches: Vec::new(),
             table_state,
             selection: MultiSelectState::default(),
             scrollbar_drag: None,
             drag_select_start: None,
             last_viewport_rows: 10,
+            viewport_detached: false,
+            split_percent: SPLIT_DEFAULT,
             endpoint,
             info_rx: rx,
             info_req_id: 0,
             verify_rx: vrx,
             verify_req_id: 0,
+            add_tx,
+            add_rx,
+            adds_running: 0,
             verify_progress: None,
+            verify_started: Instant::now(),
+            verify_cancel: None,
+            verify_cancelling: false,
+            verify_queue: VecDeque::new(),
+            row_ops: HashMap::new(),
             focused_path: None,
             last_error: None,
-            last_info: None,
-            last_verify: None,
+            info_viewer: None,
+            info_viewer_rows: 1,
+            info_tree: JsonTreeState::default(),
+            wrap_details: false,
+            last_add: None,
+            add_paths: None,
+            recent: VecDeque::new(),
+            recent_menu: None,
+            activity: ActivityLog::default(),
+            verify_history: VerifyHistory::default(),
             hovered: FilesHovered::None,
             picker: FilePicker::new(PathBuf::from(".")),
+            last_dir: None,
+            sort: FilesSort::None,
+            sort_desc: false,
+            pins: PinList::default(),
         }
     }
 
+    /// Points the tab at another daemon, dropping everything cached from the old one.
+    pub fn set_endpoint(&mut self, endpoint: String) {
+        self.cancel_background();
+        let split_percent = self.split_percent;
+        *self = Self::new(endpoint);
+        self.split_percent = split_percent;
+    }
+
+    pub fn verify_history(&self) -> Vec<VerifyRecord> {
+        self.verify_history.records.iter().copied().collect()
+    }
+
+    pub fn set_verify_history(&mut self, records: Vec<VerifyRecord>) {
+        self.verify_history = VerifyHistory::new(records);
+    }
+
+    pub fn pins(&self) -> Vec<String> {
+        self.pins.keys().to_vec()
+    }
+
+    pub fn set_pins(&mut self, keys: Vec<String>) {
+        self.pins = PinList::new(keys);
+        self.reorder_keeping_focus();
+    }
+
+    pub fn set_picker_size_percent(&mut self, percent: u16) {
+        self.picker.set_size_percent(percent);
+    }
+
+    pub fn set_picker_double_click_ms(&mut self, ms: u64) {
+        self.picker.set_double_click_ms(ms);
+    }
+
+    pub fn set_picker_scroll_step(&mut self, rows: usize) {
+        self.picker.set_scroll_step(rows);
+    }
+
+    pub fn picker_recent_dirs(&self) -> Vec<String> {
+        self.picker.recent_dirs()
+    }
+
+    pub fn set_picker_recent_dirs(&mut self, dirs: Vec<String>) {
+        self.picker.set_recent_dirs(dirs);
+    }
+
     pub fn is_modal_open(&self) -> bool {
-        self.picker.is_open()
+        self.picker.is_open() || self.info_viewer.is_some() || self.add_paths.is_some() || self.recent_menu.is_some()
     }
 
-    pub fn poll_async(&mut self) {
+    /// Applies finished background work; returns true when a completed add left the list stale.
+    pub fn poll_async(&mut self) -> bool {
+        let mut stale = false;
+        while let Ok(done) = self.add_rx.try_recv() {
+            self.adds_running = self.adds_running.saturating_sub(1);
+            stale |= self.finish_add(done);
+        }
+
         while let Ok((req_id, path, res)) = self.info_rx.try_recv() {
             if req_id != self.info_req_id {
                 continue;
@@ -111,7 +497,7 @@ impl FilesTab {
 
             match res {
                 Ok(v) => {
-                    self.last_info = Some(v);
+                    self.model.set_info(path, v);
                     self.last_error = None;
                 }
                 Err(e) => {
@@ -126,20 +512,47 @@ impl FilesTab {
             }
 
             match msg {
-                VerifyMsg::Progress { done, total } => {
+                VerifyMsg::Progress { done, total, current } => {
                     self.verify_progress = Some((done, total));
+                    if let Some(p) = current {
+                        self.row_ops.insert(p, RowOpState::Running);
+                    }
+                }
+                VerifyMsg::Item { path, ok } => {
+                    self.model.set_verify(&path, ok);
+                    self.row_ops.remove(&path);
                 }
                 VerifyMsg::Done { value } => {
                     self.verify_progress = None;
-                    self.last_verify = Some(value);
+                    self.verify_cancel = None;
+                    self.verify_cancelling = false;
+                    // Rows a cancelled worker never reached lose their pending mark.
+                    self.row_ops.clear();
+                    let summary = value.get("summary");
+                    let count = |k: &str| summary.and_then(|s| s.get(k)).and_then(value_as_u64).unwrap_or(0);
+                    self.verify_history.push(VerifyRecord {
+                        ts: now_ms() as i64,
+                        ok: count("ok"),
+                        failed: count("failed"),
+                    });
+                    self.model.verify_report = Some(value);
+                    if let Some(summary) = self.model.verify_summary() {
+                        self.activity.push(summary);
+                    }
                     self.last_error = None;
+                    self.start_next_verify();
                 }
                 VerifyMsg::Error { message } => {
                     self.verify_progress = None;
+                    self.verify_cancel = None;
+                    self.verify_cancelling = false;
+                    self.activity.push(format!("verify failed: {}", message));
                     self.last_error = Some(message);
+                    self.start_next_verify();
                 }
             }
         }
+        stale
     }
 
     fn selected_path(&self) -> Option<String> {
@@ -150,19 +563,8 @@ impl FilesTab {
     pub fn refresh(&mut self, ipc: &mut IpcClient) {
         match ipc.rpc("files.list", serde_json::json!({})) {
             Ok(v) => {
-                self.entries = parse_files_list(&v);
-
-                let existing: BTreeSet<String> = self.entries.iter().map(|e| e.path.clone()).collect();
-                self.selection.retain_existing(&existing);
-
-                if self.entries.is_empty() {
-                    self.table_state.select(None);
-                } else if self.table_state.selected().is_none() {
-                    self.table_state.select(Some(0));
-                }
+                self.set_entries(parse_files_list(&v));
                 self.last_error = None;
-
-                self.request_focused_info_if_needed();
             }
             Err(e) => {
                 self.last_error = Some(e.to_string());
@@ -170,8 +572,64 @@ impl FilesTab {
         }
     }
 
+    /// A full refresh asked for by the user: forgets this session's verify marks, then re-lists.
+    pub fn reload(&mut self, ipc: &mut IpcClient) {
+        self.model.clear_verify();
+        self.refresh(ipc);
+    }
+
+    fn set_entries(&mut self, entries: Vec<FileEntryRow>) {
+        let anchor = FocusAnchor::capture(&self.table_state, self.selected_path());
+        let detached_offset = self.viewport_detached.then(|| self.table_state.offset());
+        self.model.set_entries(entries);
+        let known: BTreeSet<String> = self.model.entries().iter().map(|e| e.path.clone()).collect();
+        self.expanded.retain(|p| known.contains(p));
+        self.apply_sort();
+
+        // Selections hidden by the filter survive a refresh; collapsed ones do not.
+        let visible = if self.filter_active() { self.model.entries() } else { &self.entries };
+        let existing: BTreeSet<String> = visible.iter().map(|e| e.path.clone()).collect();
+        self.selection.retain_existing(&existing);
+
+        match anchor {
+            Some(anchor) => {
+                let paths = self.entries.iter().map(|e| e.path.as_str());
+                let idx = anchor.restore(&mut self.table_state, paths);
+                self.selection.set_anchor(idx);
+            }
+            None if !self.entries.is_empty() => self.table_state.select(Some(0)),
+            None => {}
+        }
+        // A refresh must not yank a wheel-scrolled view back to the focused row.
+        if let Some(offset) = detached_offset {
+            *self.table_state.offset_mut() = offset;
+            scroll_viewport(&mut self.table_state, 0, self.last_viewport_rows, self.entries.len());
+        }
+
+        self.request_focused_info_if_needed();
+    }
+
+    /// Applies a `state.files` payload without a `files.list` round-trip.
+    ///
+    /// Only removals of a listed path can be applied locally; returns false when the caller should
+    /// fall back to [`FilesTab::refresh`] (adds need sizes and roots the event does not carry).
+    pub fn apply_state(&mut self, data: &Value) -> bool {
+        let op = data.get("op").and_then(|x| x.as_str());
+        let path = data.get("path").and_then(|x| x.as_str());
+        match (op, path) {
+            (Some("remove"), Some(path)) if self.model.entry(path).is_some() => {
+                let mut entries = self.model.entries().to_vec();
+                entries.retain(|e| e.path != path);
+                self.row_ops.remove(path);
+                self.set_entries(entries);
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn verify_selected(&mut self, _ipc: &mut IpcClient) {
-        let mut paths: Vec<String> = self.selection.selected().iter().cloned().collect();
+        let mut paths = self.visible_selection();
         if paths.is_empty() {
             if let Some(p) = self.selected_path() {
                 paths.push(p);
@@ -182,6 +640,31 @@ impl FilesTab {
             return;
         }
 
+        // Don't clobber a running verify; run this one after it.
+        if self.verify_cancel.is_some() {
+            self.verify_queue.push_back(paths);
+            return;
+        }
+        self.start_verify(paths);
+    }
+
+    /// Stops the running verify after the file in progress and drops queued batches; the
+    /// worker still reports what it verified so far.
+    fn cancel_verify(&mut self) {
+        if let Some(cancel) = &self.verify_cancel {
+            cancel.store(true, Ordering::Relaxed);
+            self.verify_cancelling = true;
+            self.verify_queue.clear();
+        }
+    }
+
+    fn start_next_verify(&mut self) {
+        if let Some(paths) = self.verify_queue.pop_front() {
+            self.start_verify(paths);
+        }
+    }
+
+    fn start_verify(&mut self, paths: Vec<String>) {
         let endpoint = self.endpoint.clone();
         let (tx, rx): (Sender<(u64, VerifyMsg)>, Receiver<(u64, VerifyMsg)>) = mpsc::channel();
         self.verify_rx = rx;
@@ -189,23 +672,43 @@ impl FilesTab {
         self.verify_req_id = self.verify_req_id.wrapping_add(1);
         let req_id = self.verify_req_id;
 
+        if let Some(prev) = self.verify_cancel.take() {
+            prev.store(true, Ordering::Relaxed);
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.verify_cancel = Some(cancel.clone());
+        self.verify_cancelling = false;
+
         self.verify_progress = Some((0, paths.len()));
+        self.verify_started = Instant::now();
         self.last_error = None;
+        self.row_ops = paths
+            .iter()
+            .map(|p| (p.clone(), RowOpState::Pending))
+            .collect();
 
         thread::spawn(move || {
             let res = (|| {
-                let mut c = crate::ipc::IpcClient::connect(endpoint).map_err(|e| e.to_string())?;
+                let mut c = crate::ipc::IpcClient::connect(endpoint)
+                    .map_err(|e| e.to_string())?
+                    .with_timeout(LONG_RPC_TIMEOUT);
                 let total = paths.len();
                 let mut ok_count: u64 = 0;
                 let mut fail_count: u64 = 0;
                 let mut results: Vec<Value> = Vec::new();
+                let mut cancelled = false;
 
                 for (i, path) in paths.into_iter().enumerate() {
+                    if cancel.load(Ordering::Relaxed) {
+                        cancelled = true;
+                        break;
+                    }
                     let _ = tx.send((
                         req_id,
                         VerifyMsg::Progress {
                             done: i,
                             total,
+                            current: Some(path.clone()),
                         },
                     ));
 
@@ -217,10 +720,24 @@ impl FilesTab {
                                 Some(false) => fail_count += 1,
                                 None => {}
                             }
+                            let _ = tx.send((
+                                req_id,
+                                VerifyMsg::Item {
+                                    path: path.clone(),
+                                    ok: valid != Some(false),
+                                },
+                            ));
                             results.push(serde_json::json!({"path": path, "result": v}));
                         }
                         Err(e) => {
                             fail_count += 1;
+                            let _ = tx.send((
+                                req_id,
+                                VerifyMsg::Item {
+                                    path: path.clone(),
+                                    ok: false,
+                                },
+                            ));
                             results.push(serde_json::json!({
                                 "path": path,
                                 "error": e.to_string()
@@ -232,8 +749,9 @@ impl FilesTab {
                 let _ = tx.send((
                     req_id,
                     VerifyMsg::Progress {
-                        done: total,
+                        done: results.len(),
                         total,
+                        current: None,
                     },
                 ));
 
@@ -243,6 +761,8 @@ impl FilesTab {
                         "failed": fail_count,
                         "total": ok_count + fail_count
                     },
+                    "cancelled": cancelled,
+                    "requested": total,
                     "results": results
                 }))
             })();
@@ -258,32 +778,333 @@ impl FilesTab {
         });
     }
 
-    pub fn remove_selected(&mut self, ipc: &mut IpcClient) {
+    /// Selected paths among the visible rows; rows hidden by the filter stay selected but are
+    /// not acted on.
+    fn visible_selection(&self) -> Vec<String> {
         let mut paths: Vec<String> = self.selection.selected().iter().cloned().collect();
+        if self.filter_active() {
+            paths.retain(|p| self.entries.iter().any(|e| e.path == *p));
+        }
+        paths
+    }
+
+    fn remove_targets(&self) -> Vec<String> {
+        let mut paths = self.visible_selection();
         if paths.is_empty() {
             if let Some(p) = self.selected_path() {
                 paths.push(p);
             }
         }
+        paths
+    }
+
+    fn confirm_remove(&self) -> UiCommand {
+        let n = self.remove_targets().len();
+        if n == 0 {
+            return UiCommand::None;
+        }
+        UiCommand::confirm_files_remove(format!("Remove {} file(s)?", n))
+    }
 
+    /// Untracks the remove targets; with `delete` the daemon also deletes them from disk.
+    pub fn remove_selected(&mut self, ipc: &mut IpcClient, delete: bool) {
+        let paths = self.remove_targets();
         if paths.is_empty() {
             return;
         }
 
+        let verb = if delete { "deleted" } else { "removed" };
+        let total = paths.len();
         for path in paths {
-            match ipc.rpc("files.remove", serde_json::json!({"path": path})) {
+            match ipc.rpc("files.remove", serde_json::json!({"path": path, "delete": delete})) {
                 Ok(_v) => {}
                 Err(e) => {
+                    self.activity.push(format!("remove {} failed: {}", path, e));
                     self.last_error = Some(e.to_string());
                     return;
                 }
             }
         }
 
+        self.activity.push(format!("{} {} file(s)", verb, total));
         self.last_error = None;
         self.refresh(ipc);
     }
 
+    /// Sort column name and direction, for saving between runs.
+    pub fn sort_state(&self) -> (&'static str, bool) {
+        (self.sort.name(), self.sort_desc)
+    }
+
+    /// Restores a saved sort; unknown column names are ignored.
+    pub fn set_sort_state(&mut self, name: &str, desc: bool) {
+        if let Some(sort) = FilesSort::from_name(name) {
+            self.sort = sort;
+            self.sort_desc = desc && sort != FilesSort::None;
+            self.reorder_keeping_focus();
+        }
+    }
+
+    pub fn split_percent(&self) -> u16 {
+        self.split_percent
+    }
+
+    pub fn set_split_percent(&mut self, percent: u16) {
+        self.split_percent = clamp_split(percent);
+    }
+
+    fn cycle_sort(&mut self) {
+        (self.sort, self.sort_desc) = self.sort.next();
+        self.reorder_keeping_focus();
+    }
+
+    fn reverse_sort(&mut self) {
+        if self.sort != FilesSort::None {
+            self.sort_desc = !self.sort_desc;
+            self.reorder_keeping_focus();
+        }
+    }
+
+    /// Header label with an arrow on the active sort column.
+    fn sort_label(&self, col: FilesSort, label: &str) -> String {
+        if self.sort != col {
+            return label.to_string();
+        }
+        format!("{}{}", label, if self.sort_desc { "▼" } else { "▲" })
+    }
+
+    fn reorder_keeping_focus(&mut self) {
+        let focused = self.selected_path();
+        self.apply_sort();
+        if let Some(p) = focused {
+            if let Some(idx) = self.entries.iter().position(|e| e.path == p) {
+                self.table_state.select(Some(idx));
+                self.selection.set_anchor(Some(idx));
+                ensure_visible(&mut self.table_state, self.last_viewport_rows, self.entries.len());
+            }
+        }
+    }
+
+    fn toggle_pin_focused(&mut self) {
+        if let Some(p) = self.selected_path() {
+            self.pins.toggle(&p);
+            self.reorder_keeping_focus();
+        }
+    }
+
+    fn move_pin_focused(&mut self, delta: isize) {
+        let Some(p) = self.selected_path() else {
+            return;
+        };
+        let visible: Vec<&str> = self.entries.iter().map(|e| e.path.as_str()).collect();
+        if self.pins.move_by(&p, delta, &visible) {
+            self.reorder_keeping_focus();
+        }
+    }
+
+    fn apply_sort(&mut self) {
+        let desc = self.sort_desc;
+        let all = &mut self.model.entries;
+        match self.sort {
+            // Entries come straight from `files.list`; nothing to do.
+            FilesSort::None => {}
+            // Stable sorts: rows without the value keep their relative order at the end.
+            FilesSort::Added => all.sort_by(|a, b| cmp_present_first(&a.added_at, &b.added_at, desc)),
+            FilesSort::Size => all.sort_by(|a, b| cmp_present_first(&a.size, &b.size, desc)),
+            FilesSort::Chunks => all.sort_by(|a, b| cmp_present_first(&a.chunks, &b.chunks, desc)),
+            FilesSort::Path if desc => all.sort_by(|a, b| b.path.cmp(&a.path)),
+            FilesSort::Path => all.sort_by(|a, b| a.path.cmp(&b.path)),
+        }
+        // Pinned rows always sit on top in their manual order.
+        self.pins.apply(all, |e| &e.path);
+        self.rebuild_visible();
+    }
+
+    /// Rebuilds the visible `entries` from the model, honoring tree mode and collapsed dirs.
+    /// A filter searches every tracked path, collapsed or not, and lists the matches flat.
+    fn rebuild_visible(&mut self) {
+        let q = self.filter.value().trim();
+        if !q.is_empty() {
+            (self.entries, self.filter_matches) = self
+                .model
+                .entries()
+                .iter()
+                .filter_map(|e| fuzzy::score(&e.path, q).map(|(_, idx)| (e.clone(), idx)))
+                .unzip();
+            return;
+        }
+        self.entries = if self.tree_view {
+            flatten_tree(self.model.entries(), &self.expanded)
+        } else {
+            self.model.entries().to_vec()
+        };
+        self.filter_matches.clear();
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        self.filter_editing
+    }
+
+    fn filter_active(&self) -> bool {
+        !self.filter.value().trim().is_empty()
+    }
+
+    fn apply_filter(&mut self) {
+        self.reorder_keeping_focus();
+        if self.table_state.selected().is_none_or(|i| i >= self.entries.len()) {
+            self.set_focus((!self.entries.is_empty()).then_some(0));
+        } else {
+            self.request_focused_info_if_needed();
+        }
+    }
+
+    fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.filter_editing = false;
+        self.apply_filter();
+    }
+
+    fn toggle_tree_view(&mut self) {
+        self.tree_view = !self.tree_view;
+        self.reorder_keeping_focus();
+    }
+
+    /// Expands or collapses the focused directory. Selections inside a collapsed dir are dropped
+    /// so bulk actions only ever apply to visible rows.
+    fn toggle_expand_focused(&mut self) {
+        let Some(e) = self.table_state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        if !self.tree_view || !e.tree.has_children {
+            return;
+        }
+        let path = e.path.clone();
+        if !self.expanded.remove(&path) {
+            self.expanded.insert(path);
+        }
+        self.reorder_keeping_focus();
+        let visible: BTreeSet<String> = self.entries.iter().map(|e| e.path.clone()).collect();
+        self.selection.retain_existing(&visible);
+    }
+
+    /// Enter: expands/collapses a dir in tree view, otherwise opens the full `files.info` viewer.
+    fn enter_focused(&mut self) {
+        let is_tree_dir = self.tree_view
+            && self
+                .table_state
+                .selected()
+                .and_then(|i| self.entries.get(i))
+                .is_some_and(|e| e.tree.has_children);
+        if is_tree_dir {
+            self.toggle_expand_focused();
+        } else if self.selected_path().is_some() {
+            self.info_viewer = Some(0);
+        }
+    }
+
+    /// `files.info` for the focused row, once fetched.
+    fn focused_info(&self) -> Option<&Value> {
+        self.model.info(self.focused_path.as_deref()?)
+    }
+
+    fn info_viewer_lines(&self) -> Vec<Line<'static>> {
+        match self.focused_info() {
+            Some(v) => {
+                let s = serde_json::to_string_pretty(v).unwrap_or_else(|_| "{}".into());
+                s.lines().map(|l| Line::from(l.to_string())).collect()
+            }
+            None => vec![Line::from("loading…")],
+        }
+    }
+
+    fn info_viewer_on_key(&mut self, key: KeyEvent) {
+        let Some(off) = self.info_viewer else {
+            return;
+        };
+        let page = self.info_viewer_rows.max(1);
+        let max_off = self.info_viewer_lines().len().saturating_sub(page);
+        let next = match key.code {
+            KeyCode::Esc => {
+                self.info_viewer = None;
+                return;
+            }
+            KeyCode::Char('j') | KeyCode::Down => off + 1,
+            KeyCode::Char('k') | KeyCode::Up => off.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ') => off + page,
+            KeyCode::PageUp => off.saturating_sub(page),
+            KeyCode::Char('g') | KeyCode::Home => 0,
+            KeyCode::Char('G') | KeyCode::End => max_off,
+            _ => off,
+        };
+        self.info_viewer = Some(next.min(max_off));
+    }
+
+    fn draw_info_viewer(&mut self, f: &mut Frame, area: Rect) {
+        let Some(off) = self.info_viewer else {
+            return;
+        };
+        let title = format!("files.info: {}", self.focused_path.as_deref().unwrap_or("?"));
+        let inner = draw_modal_shell(f, 95, 95, area, &title);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let lines = self.info_viewer_lines();
+        let rows = chunks[0].height.max(1) as usize;
+        self.info_viewer_rows = rows;
+        let off = off.min(lines.len().saturating_sub(rows));
+        self.info_viewer = Some(off);
+
+        let metrics = compute_scrollbar_metrics_with_margin(
+            chunks[0],
+            0,
+            Margin {
+                vertical: 0,
+                horizontal: 0,
+            },
+            lines.len(),
+            off,
+        );
+        let mut text_area = chunks[0];
+        if metrics.is_some() {
+            text_area.width = text_area.width.saturating_sub(1);
+        }
+        let p = Paragraph::new(Text::from(lines)).scroll((off.min(u16::MAX as usize) as u16, 0));
+        f.render_widget(p, text_area);
+        if let Some(metrics) = metrics {
+            render_scrollbar(f, metrics);
+        }
+
+        let help = Paragraph::new("j/k scroll | PgUp/PgDn page | g/G top/bottom | Esc close")
+            .style(theme().muted());
+        f.render_widget(help, chunks[1]);
+    }
+
+    /// `h`: collapse the focused dir, or move focus to its parent.
+    fn collapse_or_parent(&mut self) {
+        let Some(i) = self.table_state.selected() else {
+            return;
+        };
+        let Some(e) = self.entries.get(i) else {
+            return;
+        };
+        if !self.tree_view {
+            return;
+        }
+        if e.tree.has_children && self.expanded.contains(&e.path) {
+            self.toggle_expand_focused();
+            return;
+        }
+        let depth = e.tree.depth;
+        if depth == 0 {
+            return;
+        }
+        if let Some(parent) = self.entries[..i].iter().rposition(|p| p.tree.depth < depth) {
+            self.set_focus(Some(parent));
+        }
+    }
+
     fn toggle_selected_current(&mut self) {
         let Some(p) = self.selected_path() else {
             return;
@@ -300,6 +1121,7 @@ impl FilesTab {
     fn set_focus(&mut self, idx: Option<usize>) {
         self.table_state.select(idx);
         self.selection.set_anchor(idx);
+        ensure_visible(&mut self.table_state, self.last_viewport_rows, self.entries.len());
         self.request_focused_info_if_needed();
     }
 
@@ -315,15 +1137,17 @@ impl FilesTab {
     fn request_focused_info_if_needed(&mut self) {
         let Some(p) = self.selected_path() else {
             self.focused_path = None;
-            self.last_info = None;
+            self.info_tree.focused = false;
             return;
         };
 
         if self.focused_path.as_deref() == Some(p.as_str()) {
             return;
         }
+        // A previously fetched result shows until the fresh one arrives.
         self.focused_path = Some(p.clone());
-        self.last_info = None;
+        self.info_tree.reset();
+        self.info_tree.focused = false;
 
         let endpoint = self.endpoint.clone();
         let (tx, rx): (
@@ -346,48 +1170,341 @@ impl FilesTab {
     }
 
     pub fn add_open(&mut self) {
-        let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        self.picker.open(cwd);
+        let dir = self
+            .last_dir
+            .clone()
+            .filter(|d| d.is_dir())
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+        self.picker.open(dir);
     }
 
-    pub fn add_cancel(&mut self) {
+    /// Shows the focused entry's folder in the OS file manager.
+    fn open_focused_folder(&self, app: &mut App) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        match open_containing_folder(Path::new(&path)) {
+            Ok(dir) => app.toast(LogLevel::Info, format!("opened {}", dir.display())),
+            Err(e) => app.toast(LogLevel::Error, e),
+        }
+    }
+
+    /// Opens the picker on the focused entry's parent directory with the entry highlighted.
+    fn reveal_focused(&mut self) {
+        let Some(e) = self.table_state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        let path = PathBuf::from(&e.path);
+        self.picker.reveal(&path);
+    }
+
+    /// Closes the picker, remembering where it was for the next `add_open`.
+    fn close_picker(&mut self) {
+        self.last_dir = Some(self.picker.cwd().to_path_buf());
         self.picker.close();
+    }
+
+    pub fn add_cancel(&mut self) {
+        self.close_picker();
         self.hovered = FilesHovered::None;
     }
 
-    pub fn add_confirm(&mut self, ipc: &mut IpcClient) {
+    pub fn add_confirm(&mut self) {
         let mut paths = self.picker.selected_paths();
         if paths.is_empty() {
             if let Some(p) = self.picker.current_path() {
                 paths.push(p);
             }
         }
+        self.close_picker();
+        if !paths.is_empty() {
+            self.send_add(paths, Vec::new());
+        }
+    }
+
+    pub fn add_paths_open(&mut self) {
+        self.add_paths = Some(AddPathsState::default());
+    }
+
+    /// Sends the typed paths that exist to `files.add`; the rest are reported as failures
+    /// alongside the daemon's own results.
+    pub fn add_paths_confirm(&mut self) {
+        let Some(state) = self.add_paths.take() else {
+            return;
+        };
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let (found, missing): (Vec<String>, Vec<String>) = state
+            .all_paths()
+            .into_iter()
+            .map(|p| resolve_typed_path(&p, &cwd).to_string_lossy().into_owned())
+            .partition(|p| Path::new(p).exists());
+        if found.is_empty() && missing.is_empty() {
+            return;
+        }
+        self.send_add(found, missing);
+    }
 
+    /// Runs `files.add` for `paths` on a worker thread; hashing large trees can take minutes.
+    /// `missing` paths were never sent and are reported with the daemon's results.
+    fn send_add(&mut self, paths: Vec<String>, missing: Vec<String>) {
+        let missing: Vec<(String, String)> =
+            missing.into_iter().map(|p| (p, "no such file or directory".to_string())).collect();
         if paths.is_empty() {
-            self.picker.close();
+            self.finish_add(AddDone { roots: Vec::new(), missing, res: Ok(Value::Null) });
             return;
         }
+        self.activity.push(format!("adding {} path(s)…", paths.len()));
+        self.adds_running += 1;
+        let endpoint = self.endpoint.clone();
+        let tx = self.add_tx.clone();
+        thread::spawn(move || {
+            let res = (|| {
+                let mut c = crate::ipc::IpcClient::connect(endpoint)
+                    .map_err(|e| e.to_string())?
+                    .with_timeout(LONG_RPC_TIMEOUT);
+                c.rpc("files.add", serde_json::json!({"paths": paths.clone()}))
+                    .map_err(|e| e.to_string())
+            })();
+            let _ = tx.send(AddDone { roots: paths, missing, res });
+        });
+    }
 
-        match ipc.rpc("files.add", serde_json::json!({"paths": paths})) {
-            Ok(_v) => {
-                self.last_error = None;
-                self.picker.close();
-                self.refresh(ipc);
+    /// Records a finished add; returns true when the daemon answered, so the list needs a refresh.
+    fn finish_add(&mut self, done: AddDone) -> bool {
+        let AddDone { roots, missing, res } = done;
+        let mut summary = if roots.is_empty() {
+            AddSummary { added: 0, total: 0, failed: Vec::new() }
+        } else {
+            match res {
+                Ok(v) => {
+                    // Re-added content may have changed; earlier verify marks no longer apply.
+                    self.model.clear_verify_under(&roots);
+                    let summary = parse_add_results(&v, roots.len());
+                    self.remember_recent(roots.into_iter().filter(|r| !summary.failed.iter().any(|(p, _)| p == r)));
+                    summary
+                }
+                Err(e) => {
+                    self.activity.push(format!("add failed: {}", e));
+                    self.last_error = Some(e);
+                    self.last_add = None;
+                    return false;
+                }
             }
-            Err(e) => {
-                self.last_error = Some(e.to_string());
-                self.picker.close();
+        };
+        let answered = summary.total > 0;
+        summary.total += missing.len();
+        summary.failed.extend(missing);
+        self.last_error = summary.status_line();
+        self.activity.push(match &self.last_error {
+            Some(status) => status.clone(),
+            None => format!("added {} path(s)", summary.added),
+        });
+        self.last_add = Some(summary);
+        answered
+    }
+
+    /// Moves `paths` to the front of the recent-adds list, dropping the oldest past the cap.
+    fn remember_recent(&mut self, paths: impl Iterator<Item = String>) {
+        for p in paths {
+            self.recent.retain(|r| *r != p);
+            self.recent.push_front(p);
+        }
+        self.recent.truncate(RECENT_ADDS_MAX);
+    }
+
+    pub fn recent_adds(&self) -> Vec<String> {
+        self.recent.iter().cloned().collect()
+    }
+
+    pub fn set_recent_adds(&mut self, paths: Vec<String>) {
+        self.recent = paths.into_iter().take(RECENT_ADDS_MAX).collect();
+    }
+
+    /// Opens the re-add menu over recent paths that still exist.
+    pub fn recent_open(&mut self) {
+        self.recent.retain(|p| Path::new(p).exists());
+        if self.recent.is_empty() {
+            self.last_error = Some("no recent adds".to_string());
+            return;
+        }
+        let mut state = TableState::default();
+        state.select(Some(0));
+        self.recent_menu = Some(state);
+    }
+
+    pub fn recent_confirm(&mut self) {
+        let Some(state) = self.recent_menu.take() else {
+            return;
+        };
+        if let Some(p) = state.selected().and_then(|i| self.recent.get(i)).cloned() {
+            self.send_add(vec![p], Vec::new());
+        }
+    }
+
+    fn recent_on_key(&mut self, key: KeyEvent) -> UiCommand {
+        let len = self.recent.len();
+        let Some(state) = self.recent_menu.as_mut() else {
+            return UiCommand::None;
+        };
+        let cur = state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.recent_menu = None,
+            KeyCode::Enter => return UiCommand::FilesRecentConfirm,
+            KeyCode::Down | KeyCode::Char('j') => state.select(Some((cur + 1).min(len.saturating_sub(1)))),
+            KeyCode::Up | KeyCode::Char('k') => state.select(Some(cur.saturating_sub(1))),
+            KeyCode::Home | KeyCode::Char('g') => state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => state.select(Some(len.saturating_sub(1))),
+            _ => {}
+        }
+        UiCommand::None
+    }
+
+    fn draw_recent(&mut self, f: &mut Frame, area: Rect) {
+        let Some(state) = self.recent_menu.as_mut() else {
+            return;
+        };
+        let inner = draw_modal_shell(f, 70, 50, area, "Re-add recent");
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+        let rows: Vec<Row> = self.recent.iter().map(|p| Row::new(vec![p.as_str()])).collect();
+        let table = Table::new(rows, [Constraint::Percentage(100)]).row_highlight_style(theme().highlight());
+        f.render_stateful_widget(table, chunks[0], state);
+        let help = Paragraph::new("j/k move | Enter add again | Esc close").style(theme().muted());
+        f.render_widget(help, chunks[1]);
+    }
+
+    fn add_paths_on_key(&mut self, key: KeyEvent) -> UiCommand {
+        let Some(state) = self.add_paths.as_mut() else {
+            return UiCommand::None;
+        };
+        if key.code == KeyCode::Backspace && state.input.value().is_empty() {
+            // Pull the previous line back for editing.
+            if let Some(last) = state.paths.pop() {
+                state.input.set(last);
+            }
+            return UiCommand::None;
+        }
+        match state.input.handle_key(key) {
+            TextInputAction::Cancel => self.add_paths = None,
+            TextInputAction::Submit if state.input.value().trim().is_empty() => {
+                if !state.paths.is_empty() {
+                    return UiCommand::FilesAddPathsConfirm;
+                }
+            }
+            TextInputAction::Submit => {
+                state.paths.push(state.input.value().trim().to_string());
+                state.input.clear();
             }
+            TextInputAction::Changed | TextInputAction::None => {}
         }
+        UiCommand::None
+    }
+
+    fn draw_add_paths(&self, f: &mut Frame, area: Rect) {
+        let Some(state) = &self.add_paths else {
+            return;
+        };
+        let inner = draw_modal_shell(f, 70, 60, area, "Add by path");
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3), Constraint::Length(1)])
+            .split(inner);
+
+        let lines: Vec<Line> = if state.paths.is_empty() {
+            vec![Line::styled("(no paths yet)", theme().muted())]
+        } else {
+            // Keep the newest lines in view.
+            let skip = state.paths.len().saturating_sub(chunks[0].height as usize);
+            state.paths.iter().skip(skip).map(|p| Line::from(p.as_str())).collect()
+        };
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+        state.input.draw(f, chunks[1], "Path", true);
+        let help = Paragraph::new("Enter add line | Enter on empty line adds all | Backspace edit last | Esc cancel")
+            .style(theme().muted());
+        f.render_widget(help, chunks[2]);
     }
 }
 
+const KEY_HELP: &[KeyHelp] = &[
+    key_help("j/k ↑/↓", "move"),
+    key_help("J/K PgDn/PgUp", "page"),
+    key_help("g/G Home/End", "first / last file"),
+    key_help("=<n> Enter/G", "go to row n"),
+    key_help("Tab/Space", "toggle selection"),
+    key_help("Ctrl/Shift+click", "toggle / extend selection"),
+    key_help("A / Ctrl+a", "select all"),
+    key_help("c", "clear selection"),
+    key_help("i", "invert selection"),
+    key_help("a", "add files"),
+    key_help("R", "reveal in file picker"),
+    key_help("O", "open containing folder"),
+    key_help("P", "add by typed path"),
+    key_help("L", "re-add a recently added path"),
+    key_help("v", "verify selected"),
+    key_help("x / Del", "remove selected"),
+    key_help("/", "fuzzy filter by path"),
+    key_help("Esc", "clear verify queue, then filter"),
+    key_help("C", "cancel running verify (keeps partial results)"),
+    key_help("Enter", "expand dir / open details viewer"),
+    key_help("T", "toggle tree view"),
+    key_help("l / h", "expand / collapse"),
+    key_help("s / S", "sort column / reverse"),
+    key_help("p", "pin/unpin file"),
+    key_help("Alt+↑/↓", "move pinned file"),
+    key_help("z", "focus details tree"),
+    key_help("W", "wrap details"),
+    key_help("< / >", "shrink / grow the table"),
+];
+
 impl Tab for FilesTab {
     fn id(&self) -> TabId {
         TabId::Files
     }
 
-    fn draw(&mut self, f: &mut Frame, area: Rect, _app: &mut App) {
+    fn key_help(&self) -> &'static [KeyHelp] {
+        KEY_HELP
+    }
+
+    fn footer_hint(&self) -> String {
+        if self.recent_menu.is_some() {
+            "recent adds: j/k move | Enter add again | Esc close".to_string()
+        } else if self.add_paths.is_some() {
+            "type a path | Enter add line | Enter on empty line adds all | Esc cancel".to_string()
+        } else if self.picker.is_open() {
+            "file picker: Tab toggle | Enter confirm/cd | / search | Esc cancel".to_string()
+        } else if self.info_viewer.is_some() {
+            "files.info: j/k scroll | g/G top/bottom | Esc close".to_string()
+        } else if self.filter_editing {
+            "type to filter files | Enter done | Esc clear".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    fn activity(&self) -> Option<&ActivityLog> {
+        Some(&self.activity)
+    }
+
+    fn is_busy(&self) -> bool {
+        self.verify_cancel.is_some() || !self.verify_queue.is_empty()
+    }
+
+    fn cancel_background(&mut self) {
+        self.verify_queue.clear();
+        // Rows that never ran lose their pending/running state.
+        self.row_ops.clear();
+        if let Some(cancel) = self.verify_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+            // Drop anything the worker still sends for this request.
+            self.verify_req_id = self.verify_req_id.wrapping_add(1);
+            self.verify_progress = None;
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Rect, app: &mut App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(8), Constraint::Length(10)].as_ref())
@@ -395,7 +1512,7 @@ impl Tab for FilesTab {
 
         let main = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
+            .constraints(split_constraints(self.split_percent).as_ref())
             .split(chunks[0]);
 
         let list_area = main[0];
@@ -404,51 +1521,129 @@ impl Tab for FilesTab {
         self.last_viewport_rows = list_area.height.saturating_sub(3).max(1) as usize;
 
         let header = Row::new(vec![
-            "Sel",
-            "Type",
-            "Size",
-            "Chunks",
-            "Root",
-            "Path",
+            "Sel".to_string(),
+            "Type".to_string(),
+            "OK".to_string(),
+            self.sort_label(FilesSort::Size, "Size"),
+            self.sort_label(FilesSort::Chunks, "Chunks"),
+            "Root".to_string(),
+            self.sort_label(FilesSort::Added, "Added"),
+            self.sort_label(FilesSort::Path, "Path"),
         ])
-        .style(Style::default().fg(Color::Yellow));
+        .style(theme().accent());
+
+        let show_scrollbar = self.entries.len() > self.last_viewport_rows;
+        let mut table_area = list_area;
+        if show_scrollbar {
+            table_area.width = table_area.width.saturating_sub(1);
+        }
+        let fixed: u16 = FILES_FIXED_COLUMNS.iter().sum();
+        // Borders plus one column of spacing between each of the 8 columns.
+        let path_width = table_area.width.saturating_sub(2 + fixed + 7) as usize;
 
-        let rows = self.entries.iter().map(|e| {
+        let spinner = ['|', '/', '-', '\\'][(now_ms() / 120 % 4) as usize];
+        let rows = self.entries.iter().enumerate().map(|(i, e)| {
             let mark = if self.selection.is_selected(&e.path) { "[x]" } else { "[ ]" };
-            let size = e.size.map(|s| s.to_string()).unwrap_or_else(|| "".to_string());
+            let size = e.size.map(format_bytes_short).unwrap_or_default();
             let chunks = e.chunks.map(|c| c.to_string()).unwrap_or_else(|| "".to_string());
             let root = e
                 .merkle_root
                 .as_deref()
                 .map(|s| if s.len() > 12 { s[..12].to_string() } else { s.to_string() })
                 .unwrap_or_else(|| "".to_string());
+            let added = e
+                .added_at
+                .map(format_relative_time)
+                .unwrap_or_else(|| "—".to_string());
+
+            let mut name = String::new();
+            if self.tree_view {
+                name.push_str(&"  ".repeat(e.tree.depth));
+                name.push_str(match (e.tree.has_children, self.expanded.contains(&e.path)) {
+                    (false, _) => "  ",
+                    (true, false) => "▸ ",
+                    (true, true) => "▾ ",
+                });
+            }
+            let (status, style) = match self.row_ops.get(&e.path) {
+                None if e.is_unhashed() => (String::new(), Style::default().fg(theme().dim)),
+                None => (String::new(), Style::default()),
+                Some(RowOpState::Pending) => ("· ".to_string(), Style::default().fg(theme().dim)),
+                Some(RowOpState::Running) => (format!("{} ", spinner), Style::default().fg(theme().info)),
+            };
+            name.push_str(&status);
+            if self.pins.is_pinned(&e.path) {
+                name.push_str("★ ");
+            }
+            let room = path_width.saturating_sub(name.chars().count()).max(1);
+            let path = match self.filter_matches.get(i) {
+                // Filtered rows are flat, so match positions index the full path.
+                Some(idx) => {
+                    let (label, idx) = fuzzy::truncate(&e.path, idx, room);
+                    let mut spans = vec![Span::raw(name)];
+                    spans.extend(fuzzy::highlight(&label, &idx).spans);
+                    Line::from(spans)
+                }
+                None => {
+                    name.push_str(&truncate_middle(&e.path[e.tree.name_start..], room));
+                    Line::from(name)
+                }
+            };
+
+            let verified = match self.model.verify(&e.path) {
+                Some(true) => Cell::from(Span::styled("✓", Style::default().fg(theme().ok))),
+                Some(false) => Cell::from(Span::styled("✗", Style::default().fg(theme().error))),
+                None => Cell::from(""),
+            };
 
-            Row::new(vec![mark.to_string(), e.typ.clone(), size, chunks, root, e.path.clone()])
+            Row::new(vec![
+                Cell::from(mark),
+                Cell::from(e.typ.clone()),
+                verified,
+                Cell::from(size),
+                Cell::from(chunks),
+                Cell::from(root),
+                Cell::from(added),
+                Cell::from(path),
+            ])
+            .style(style)
         });
 
+        let tracked_title = if self.filter_editing {
+            format!("Tracked (filter: {}_)", self.filter.value())
+        } else if self.filter_active() {
+            format!("Tracked (filter: {}) {}/{}", self.filter.value(), self.entries.len(), self.model.entries().len())
+        } else {
+            "Tracked".to_string()
+        };
         let table = Table::new(
             rows,
-            [
-                Constraint::Length(4),
-                Constraint::Length(5),
-                Constraint::Length(12),
-                Constraint::Length(8),
-                Constraint::Length(14),
-                Constraint::Min(10),
-            ],
+            FILES_FIXED_COLUMNS
+                .iter()
+                .map(|&w| Constraint::Length(w))
+                .chain([Constraint::Min(10)]),
         )
         .header(header)
-        .block(Block::default().title("Tracked").borders(Borders::ALL))
-        .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
-
-        let show_scrollbar = self.entries.len() > self.last_viewport_rows;
-        let mut table_area = list_area;
-        if show_scrollbar {
-            table_area.width = table_area.width.saturating_sub(1);
+        .block(Block::default().title(tracked_title).borders(Borders::ALL))
+        .row_highlight_style(theme().highlight());
+
+        render_table_viewport(
+            f,
+            table,
+            table_area,
+            &mut self.table_state,
+            self.viewport_detached,
+            self.last_viewport_rows,
+        );
+        if self.entries.is_empty() {
+            let msg = if self.filter_active() {
+                "No files match the filter — Esc clears it"
+            } else {
+                "No tracked files — press a to add, or P to type a path"
+            };
+            draw_empty_state(f, table_area.inner(Margin { vertical: 1, horizontal: 1 }), msg);
         }
 
-        f.render_stateful_widget(table, table_area, &mut self.table_state);
-
         let list_table_ctrl = MultiSelectTableController::new(TableHitTestSpec {
             checkbox_width: 4,
             checkbox_rel_x_bias: 1,
@@ -473,54 +1668,101 @@ impl Tab for FilesTab {
             ])
             .split(details_area);
 
+        // While a verify runs, a gauge takes the top of the details column; the summary in
+        // the model's verify report shows again once it finishes.
+        let mut details_area = detail_chunks[0];
+        if let Some((done, total)) = self.verify_progress {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(2)])
+                .split(details_area);
+            details_area = split[1];
+
+            let pct = progress_percent(done as u64, total as u64);
+            let mut label = format!(
+                "{}/{} ({}%) ({})",
+                done,
+                total,
+                pct,
+                format_elapsed(self.verify_started.elapsed())
+            );
+            if !self.verify_queue.is_empty() {
+                label.push_str(&format!(" | queued: {} (Esc clears)", self.verify_queue.len()));
+            }
+            let title = if self.verify_cancelling {
+                "Verifying (cancelling…)"
+            } else {
+                "Verifying (C cancel)"
+            };
+            let gauge = Gauge::default()
+                .block(Block::default().title(title).borders(Borders::ALL))
+                .gauge_style(Style::default().fg(theme().info).bg(theme().surface))
+                .ratio(pct as f64 / 100.0)
+                .label(Span::raw(label));
+            f.render_widget(gauge, split[0]);
+        }
+
         let mut info_lines: Vec<Line> = Vec::new();
+        if self.adds_running > 0 {
+            info_lines.push(Line::from(format!("adding… ({} in progress)", self.adds_running)));
+            info_lines.push(Line::from(""));
+        }
         if let Some(e) = &self.last_error {
             info_lines.push(Line::from(format!("Error: {}", e)));
             info_lines.push(Line::from(""));
         }
 
-        if let Some(v) = &self.last_info {
+        if let Some(line) = self.verify_history.trend_line(10) {
+            info_lines.push(line);
+            info_lines.push(Line::from(""));
+        }
+
+        let mut info_prefix_rows = 0;
+        if let Some(v) = self.focused_info() {
             info_lines.push(Line::from("info:"));
-            let s = serde_json::to_string_pretty(v).unwrap_or_else(|_| "{}".into());
-            info_lines.extend(Text::from(s).lines);
+            info_prefix_rows = info_lines.len();
+            info_lines.extend(self.info_tree.lines(v));
         }
 
-        if let Some(v) = &self.last_verify {
+        if let (Some(v), Some(summary)) = (&self.model.verify_report, self.model.verify_summary()) {
             info_lines.push(Line::from(""));
-            let ok = v
-                .get("summary")
-                .and_then(|s| s.get("ok"))
-                .and_then(|x| x.as_u64())
-                .unwrap_or(0);
-            let failed = v
-                .get("summary")
-                .and_then(|s| s.get("failed"))
-                .and_then(|x| x.as_u64())
-                .unwrap_or(0);
-            let total = v
-                .get("summary")
-                .and_then(|s| s.get("total"))
-                .and_then(|x| x.as_u64())
-                .unwrap_or(ok + failed);
+            info_lines.push(Line::from(summary));
+            let s = serde_json::to_string_pretty(v).unwrap_or_else(|_| "{}".into());
+            info_lines.extend(Text::from(s).lines);
+        }
 
+        if let Some(add) = self.last_add.as_ref().filter(|a| !a.failed.is_empty()) {
             info_lines.push(Line::from(format!(
-                "verify: {} ok, {} failed ({} total)",
-                ok, failed, total
+                "add: {}/{} added, failed:",
+                add.added, add.total
             )));
-
-            let s = serde_json::to_string_pretty(v).unwrap_or_else(|_| "{}".into());
-            info_lines.extend(Text::from(s).lines);
+            for (path, reason) in &add.failed {
+                info_lines.push(Line::styled(
+                    format!("  {}: {}", path, reason),
+                    Style::default().fg(theme().error),
+                ));
+            }
+            info_lines.push(Line::from(""));
         }
 
         if info_lines.is_empty() {
-            info_lines.push(Line::from(
-                "Keys: r refresh | a add | tab/space toggle | Ctrl+A all | c clear | i invert | v verify | x/Del remove | j/k move | Ctrl/Shift-click",
-            ));
+            info_lines.push(Line::from(key_help_line(KEY_HELP)));
         }
 
-        let details = Paragraph::new(Text::from(info_lines))
-            .block(Block::default().title("Details").borders(Borders::ALL));
-        f.render_widget(details, detail_chunks[0]);
+        let details_rows = details_area.height.saturating_sub(2) as usize;
+        let wrap_width = self
+            .wrap_details
+            .then(|| details_area.width.saturating_sub(2));
+        let scroll = self
+            .info_tree
+            .scroll_for(&info_lines, info_prefix_rows, details_rows, wrap_width);
+        let mut details = Paragraph::new(Text::from(info_lines))
+            .block(Block::default().title("Details").borders(Borders::ALL))
+            .scroll((scroll, 0));
+        if self.wrap_details {
+            details = details.wrap(Wrap { trim: false });
+        }
+        f.render_widget(details, details_area);
 
         let refresh_btn = Button {
             label: "Refresh".to_string(),
@@ -530,34 +1772,58 @@ impl Tab for FilesTab {
 
         let add_btn = Button {
             label: "Add".to_string(),
-            enabled: true,
+            enabled: !app.read_only,
         };
         add_btn.draw(f, detail_chunks[2], self.hovered == FilesHovered::Add);
 
         let verify_btn = Button {
             label: "Verify".to_string(),
-            enabled: self.table_state.selected().is_some(),
+            enabled: !app.read_only && self.table_state.selected().is_some(),
         };
         verify_btn.draw(f, detail_chunks[3], self.hovered == FilesHovered::Verify);
 
         let remove_btn = Button {
             label: "Remove".to_string(),
-            enabled: self.table_state.selected().is_some(),
+            enabled: !app.read_only && self.table_state.selected().is_some(),
         };
         remove_btn.draw(f, detail_chunks[4], self.hovered == FilesHovered::Remove);
 
-        let footer = Paragraph::new(
-            "Keys: r refresh | a add | tab/space toggle | Ctrl+A all | c clear | i invert | v verify | x/Del remove | j/k move | Ctrl/Shift-click",
-        )
-        .block(Block::default().title("Actions").borders(Borders::ALL));
+        let footer = Paragraph::new(key_help_line(KEY_HELP))
+            .block(Block::default().title("Actions").borders(Borders::ALL));
         f.render_widget(footer, chunks[1]);
 
         if self.picker.is_open() {
             self.picker.draw(f, area);
         }
+
+        self.draw_info_viewer(f, area);
+        self.draw_add_paths(f, area);
+        self.draw_recent(f, area);
+    }
+
+    fn on_paste(&mut self, text: &str, _app: &mut App) {
+        if let Some(state) = self.add_paths.as_mut() {
+            state.on_paste(text);
+            return;
+        }
+        if self.filter_editing {
+            if self.filter.insert_str(text) {
+                self.apply_filter();
+            }
+            return;
+        }
+        self.picker.on_paste(text);
     }
 
-    fn on_key(&mut self, key: KeyEvent, _app: &mut App) -> UiCommand {
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> UiCommand {
+        self.viewport_detached = false;
+        if self.recent_menu.is_some() {
+            return self.recent_on_key(key);
+        }
+        if self.add_paths.is_some() {
+            return self.add_paths_on_key(key);
+        }
+
         if self.picker.is_open() {
             return match self.picker.on_key(key) {
                 PickerAction::None => UiCommand::None,
@@ -566,7 +1832,57 @@ impl Tab for FilesTab {
             };
         }
 
+        if self.info_viewer.is_some() {
+            self.info_viewer_on_key(key);
+            return UiCommand::None;
+        }
+
+        if self.filter_editing {
+            match self.filter.handle_key(key) {
+                TextInputAction::Changed => self.apply_filter(),
+                TextInputAction::Submit => self.filter_editing = false,
+                TextInputAction::Cancel => self.clear_filter(),
+                TextInputAction::None => {}
+            }
+            return UiCommand::None;
+        }
+
+        // Field-wise borrow: `info_tree` is mutated while the focused info is read.
+        if let Some(v) = self.focused_path.as_deref().and_then(|p| self.model.info(p)) {
+            if self.info_tree.handle_key(key, v) {
+                return UiCommand::None;
+            }
+        }
+
+        match app.row_jump.handle_key(key) {
+            RowJumpAction::Ignored => {}
+            RowJumpAction::Pending => return UiCommand::None,
+            RowJumpAction::Jump(row) => {
+                if !self.entries.is_empty() {
+                    self.set_focus(Some(row.min(self.entries.len() - 1)));
+                }
+                return UiCommand::None;
+            }
+        }
+
         match key.code {
+            KeyCode::Char('z') => {
+                self.info_tree.focused = self.focused_info().is_some();
+            }
+            KeyCode::Char('W') => self.wrap_details = !self.wrap_details,
+            KeyCode::Char('<') => self.split_percent = adjust_split(self.split_percent, false),
+            KeyCode::Char('>') => self.split_percent = adjust_split(self.split_percent, true),
+            KeyCode::Esc if !self.verify_queue.is_empty() => self.verify_queue.clear(),
+            KeyCode::Esc if self.filter_active() => self.clear_filter(),
+            KeyCode::Char('/') => self.filter_editing = true,
+            KeyCode::Char('C') if self.verify_cancel.is_some() => self.cancel_verify(),
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::ALT) => self.move_pin_focused(1),
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::ALT) => self.move_pin_focused(-1),
+            KeyCode::Char('p') => self.toggle_pin_focused(),
+            KeyCode::Char('T') => self.toggle_tree_view(),
+            KeyCode::Enter => self.enter_focused(),
+            KeyCode::Char('l') => self.toggle_expand_focused(),
+            KeyCode::Char('h') => self.collapse_or_parent(),
             KeyCode::Char('j') | KeyCode::Down => {
                 let next = match self.table_state.selected() {
                     None => 0,
@@ -601,10 +1917,15 @@ impl Tab for FilesTab {
                     self.set_focus(Some(next));
                 }
             }
+            KeyCode::Char('g') | KeyCode::Home if !self.entries.is_empty() => self.set_focus(Some(0)),
+            KeyCode::Char('G') | KeyCode::End if !self.entries.is_empty() => {
+                self.set_focus(Some(self.entries.len() - 1));
+            }
             KeyCode::Tab | KeyCode::Char(' ') => {
                 self.toggle_selected_current();
             }
-            KeyCode::Char('r') => return UiCommand::Refresh,
+            KeyCode::Char('s') => self.cycle_sort(),
+            KeyCode::Char('S') => self.reverse_sort(),
             KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.select_all();
             }
@@ -612,6 +1933,10 @@ impl Tab for FilesTab {
                 self.select_all();
             }
             KeyCode::Char('a') => return UiCommand::FilesAddOpen,
+            KeyCode::Char('P') => return UiCommand::FilesAddPathsOpen,
+            KeyCode::Char('L') => return UiCommand::FilesRecentOpen,
+            KeyCode::Char('R') => self.reveal_focused(),
+            KeyCode::Char('O') => self.open_focused_folder(app),
             KeyCode::Char('c') => {
                 self.clear_selection();
             }
@@ -619,14 +1944,16 @@ impl Tab for FilesTab {
                 self.invert_selection();
             }
             KeyCode::Char('v') => return UiCommand::FilesVerifySelected,
-            KeyCode::Char('x') => return UiCommand::FilesRemoveSelected,
-            KeyCode::Delete => return UiCommand::FilesRemoveSelected,
+            KeyCode::Char('x') | KeyCode::Delete => return self.confirm_remove(),
             _ => {}
         }
         UiCommand::None
     }
 
-    fn on_mouse(&mut self, mouse: MouseEvent, area: Rect, _app: &mut App) -> UiCommand {
+    fn on_mouse(&mut self, mouse: MouseEvent, area: Rect, app: &mut App) -> UiCommand {
+        if self.add_paths.is_some() || self.recent_menu.is_some() {
+            return UiCommand::None;
+        }
         if self.picker.is_open() {
             return match self.picker.on_mouse(mouse, area) {
                 PickerAction::None => UiCommand::None,
@@ -635,6 +1962,15 @@ impl Tab for FilesTab {
             };
         }
 
+        if let Some(off) = self.info_viewer {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => self.info_viewer = Some(off + app.settings.scroll_step),
+                MouseEventKind::ScrollUp => self.info_viewer = Some(off.saturating_sub(app.settings.scroll_step)),
+                _ => {}
+            }
+            return UiCommand::None;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(8), Constraint::Length(10)].as_ref())
@@ -642,7 +1978,7 @@ impl Tab for FilesTab {
 
         let main = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
+            .constraints(split_constraints(self.split_percent).as_ref())
             .split(chunks[0]);
 
         let list_area = main[0];
@@ -684,6 +2020,7 @@ impl Tab for FilesTab {
 
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.viewport_detached = false;
                 if let Some(metrics) = scrollbar_metrics {
                     if crate::widgets::contains(metrics.scrollbar_col, mouse.column, mouse.row) {
                         match handle_scrollbar_down(metrics, mouse.row) {
@@ -696,6 +2033,7 @@ impl Tab for FilesTab {
                                 *self.table_state.offset_mut() = offset;
                                 self.table_state
                                     .select(Some(offset.min(self.entries.len().saturating_sub(1))));
+                                ensure_visible(&mut self.table_state, self.last_viewport_rows, self.entries.len());
                                 self.selection.set_anchor(self.table_state.selected());
                                 self.request_focused_info_if_needed();
                                 return UiCommand::None;
@@ -729,7 +2067,7 @@ impl Tab for FilesTab {
                     return UiCommand::FilesVerifySelected;
                 }
                 if mouse_in(detail_chunks[4], &mouse) {
-                    return UiCommand::FilesRemoveSelected;
+                    return self.confirm_remove();
                 }
             }
             MouseEventKind::Drag(MouseButton::Left) => {
@@ -739,6 +2077,7 @@ impl Tab for FilesTab {
                         *self.table_state.offset_mut() = target;
                         self.table_state
                             .select(Some(target.min(self.entries.len().saturating_sub(1))));
+                        ensure_visible(&mut self.table_state, self.last_viewport_rows, self.entries.len());
                         self.selection.set_anchor(self.table_state.selected());
                         self.request_focused_info_if_needed();
                         return UiCommand::None;
@@ -765,27 +2104,12 @@ impl Tab for FilesTab {
                 self.scrollbar_drag = None;
                 self.drag_select_start = None;
             }
-            MouseEventKind::ScrollDown => {
-                if mouse_in(list_area, &mouse) {
-                    let next = match self.table_state.selected() {
-                        None => 0,
-                        Some(i) => (i + 1).min(self.entries.len().saturating_sub(1)),
-                    };
-                    if !self.entries.is_empty() {
-                        self.set_focus(Some(next));
-                    }
-                }
-            }
-            MouseEventKind::ScrollUp => {
-                if mouse_in(list_area, &mouse) {
-                    let next = match self.table_state.selected() {
-                        None => 0,
-                        Some(i) => i.saturating_sub(1),
-                    };
-                    if !self.entries.is_empty() {
-                        self.set_focus(Some(next));
-                    }
-                }
+            // Only the viewport moves, so the focused row (and its `files.info`) stays put.
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp if mouse_in(list_area, &mouse) => {
+                let step = app.settings.scroll_step as isize;
+                let delta = if mouse.kind == MouseEventKind::ScrollUp { -step } else { step };
+                scroll_viewport(&mut self.table_state, delta, self.last_viewport_rows, self.entries.len());
+                self.viewport_detached = true;
             }
             _ => {}
         }
@@ -794,6 +2118,65 @@ impl Tab for FilesTab {
     }
 }
 
+/// Nests rows under the closest tracked dir whose path is a prefix, then flattens depth-first,
+/// descending only into `expanded` dirs. Sibling order follows the order of `all`.
+fn flatten_tree(all: &[FileEntryRow], expanded: &BTreeSet<String>) -> Vec<FileEntryRow> {
+    let dirs: Vec<&str> = all
+        .iter()
+        .filter(|e| e.typ == "d")
+        .map(|e| e.path.trim_end_matches('/'))
+        .collect();
+    let parent_of = |path: &str| -> Option<&str> {
+        dirs.iter()
+            .copied()
+            .filter(|d| {
+                path.len() > d.len() + 1 && path.starts_with(d) && path.as_bytes()[d.len()] == b'/'
+            })
+            .max_by_key(|d| d.len())
+    };
+
+    let mut roots: Vec<usize> = Vec::new();
+    let mut children: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, e) in all.iter().enumerate() {
+        match parent_of(&e.path) {
+            Some(p) => children.entry(p).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    fn push(
+        out: &mut Vec<FileEntryRow>,
+        all: &[FileEntryRow],
+        children: &HashMap<&str, Vec<usize>>,
+        expanded: &BTreeSet<String>,
+        i: usize,
+        depth: usize,
+        name_start: usize,
+    ) {
+        let e = &all[i];
+        let key = e.path.trim_end_matches('/');
+        let kids = children.get(key);
+        let mut row = e.clone();
+        row.tree = TreePos {
+            depth,
+            has_children: kids.is_some(),
+            name_start,
+        };
+        out.push(row);
+        if let Some(kids) = kids.filter(|_| expanded.contains(&e.path)) {
+            for &k in kids {
+                push(out, all, children, expanded, k, depth + 1, key.len() + 1);
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(all.len());
+    for i in roots {
+        push(&mut out, all, &children, expanded, i, 0, 0);
+    }
+    out
+}
+
 fn parse_files_list(v: &Value) -> Vec<FileEntryRow> {
     let mut out: Vec<FileEntryRow> = Vec::new();
 
@@ -803,12 +2186,14 @@ fn parse_files_list(v: &Value) -> Vec<FileEntryRow> {
                 out.push(FileEntryRow {
                     typ: "f".to_string(),
                     path: path.to_string(),
-                    size: f.get("size").and_then(|x| x.as_u64()),
-                    chunks: f.get("chunk_count").and_then(|x| x.as_u64()),
+                    size: f.get("size").and_then(value_as_u64),
+                    chunks: f.get("chunk_count").and_then(value_as_u64),
                     merkle_root: f
                         .get("merkle_root")
                         .and_then(|x| x.as_str())
                         .map(|s| s.to_string()),
+                    added_at: f.get("added_at").and_then(|x| x.as_i64()),
+                    tree: TreePos::default(),
                 });
             }
         }
@@ -826,6 +2211,8 @@ fn parse_files_list(v: &Value) -> Vec<FileEntryRow> {
                         .get("merkle_root")
                         .and_then(|x| x.as_str())
                         .map(|s| s.to_string()),
+                    added_at: d.get("added_at").and_then(|x| x.as_i64()),
+                    tree: TreePos::default(),
                 });
             }
         }
@@ -833,3 +2220,352 @@ fn parse_files_list(v: &Value) -> Vec<FileEntryRow> {
 
     out
 }
+
+/// Typed or pasted paths for the "Add by path" modal, one per line.
+#[derive(Debug, Default)]
+struct AddPathsState {
+    input: TextInput,
+    paths: Vec<String>,
+}
+
+impl AddPathsState {
+    /// Multi-line pastes become one path per line; a single line goes into the input.
+    fn on_paste(&mut self, text: &str) {
+        if !text.trim_end_matches(['\r', '\n']).contains(['\r', '\n']) {
+            self.input.insert_str(text);
+            return;
+        }
+        self.paths
+            .extend(text.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string));
+    }
+
+    /// Accepted lines plus whatever is still in the input.
+    fn all_paths(&self) -> Vec<String> {
+        let pending = self.input.value().trim();
+        let mut out = self.paths.clone();
+        if !pending.is_empty() {
+            out.push(pending.to_string());
+        }
+        out
+    }
+}
+
+/// Resolves a typed path the way a shell would: `~` is the home directory and relative paths
+/// are taken from the TUI's working directory (the daemon's may differ).
+fn resolve_typed_path(p: &str, cwd: &Path) -> PathBuf {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let expanded = match p.strip_prefix("~/") {
+        Some(rest) => home().map(|h| h.join(rest)),
+        None if p == "~" => home(),
+        None => None,
+    }
+    .unwrap_or_else(|| PathBuf::from(p));
+    if expanded.is_absolute() {
+        expanded
+    } else {
+        cwd.join(expanded)
+    }
+}
+
+/// Interprets a `files.add` response.
+///
+/// The daemon returns `{ ok, results: [{ ok, path, error? }] }`; responses without a
+/// `results` array are treated as a full success for all `requested` paths.
+fn parse_add_results(v: &Value, requested: usize) -> AddSummary {
+    let Some(results) = v.get("results").and_then(|x| x.as_array()) else {
+        return AddSummary {
+            added: requested,
+            total: requested,
+            failed: Vec::new(),
+        };
+    };
+
+    let mut added = 0;
+    let mut failed: Vec<(String, String)> = Vec::new();
+    for r in results {
+        if r.get("ok").and_then(|x| x.as_bool()) == Some(true) {
+            added += 1;
+            continue;
+        }
+        let path = r.get("path").and_then(|x| x.as_str()).unwrap_or("?").to_string();
+        let reason = r
+            .get("error")
+            .and_then(|x| x.as_str())
+            .unwrap_or("unknown error")
+            .to_string();
+        failed.push((path, reason));
+    }
+
+    AddSummary {
+        added,
+        total: results.len(),
+        failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn add_paths_split_pastes_and_resolve_relative_to_cwd() {
+        let mut state = AddPathsState::default();
+        state.on_paste("/a\n\n  b/c \n");
+        state.on_paste("d\n");
+        assert_eq!(state.all_paths(), ["/a", "b/c", "d"]);
+
+        let cwd = Path::new("/work");
+        assert_eq!(resolve_typed_path("b/c", cwd), PathBuf::from("/work/b/c"));
+        assert_eq!(resolve_typed_path("/abs", cwd), PathBuf::from("/abs"));
+    }
+
+    #[test]
+    fn add_results_all_ok() {
+        let v = json!({"ok": true, "results": [
+            {"ok": true, "path": "/a"},
+            {"ok": true, "path": "/b"}
+        ]});
+        let s = parse_add_results(&v, 2);
+        assert_eq!(s.added, 2);
+        assert!(s.failed.is_empty());
+        assert_eq!(s.status_line(), None);
+    }
+
+    #[test]
+    fn add_results_partial() {
+        let v = json!({"ok": true, "results": [
+            {"ok": true, "path": "/a"},
+            {"ok": false, "path": "/missing", "error": "not_found"}
+        ]});
+        let s = parse_add_results(&v, 2);
+        assert_eq!(s.added, 1);
+        assert_eq!(s.failed, vec![("/missing".to_string(), "not_found".to_string())]);
+        assert_eq!(s.status_line().as_deref(), Some("added 1/2, 1 failed (see details)"));
+    }
+
+    #[test]
+    fn add_results_all_failed() {
+        let v = json!({"ok": true, "results": [
+            {"ok": false, "path": "/x", "error": "not_file_or_directory"}
+        ]});
+        let s = parse_add_results(&v, 1);
+        assert_eq!(s.added, 0);
+        assert_eq!(s.status_line().as_deref(), Some("add failed for all 1 path(s) (see details)"));
+    }
+
+    #[test]
+    fn add_results_without_details_count_as_success() {
+        let s = parse_add_results(&json!({"ok": true}), 3);
+        assert_eq!(s.added, 3);
+        assert_eq!(s.total, 3);
+        assert!(s.failed.is_empty());
+    }
+
+    #[test]
+    fn finished_add_reports_missing_paths_and_marks_the_list_stale() {
+        let mut tab = FilesTab::new(String::new());
+        let stale = tab.finish_add(AddDone {
+            roots: vec!["/a".into()],
+            missing: vec![("/gone".into(), "no such file or directory".into())],
+            res: Ok(json!({"ok": true, "results": [{"ok": true, "path": "/a"}]})),
+        });
+        assert!(stale);
+        let summary = tab.last_add.as_ref().unwrap();
+        assert_eq!((summary.added, summary.total), (1, 2));
+        assert_eq!(tab.recent_adds(), vec!["/a".to_string()]);
+
+        assert!(!tab.finish_add(AddDone { roots: vec!["/b".into()], missing: vec![], res: Err("boom".into()) }));
+        assert_eq!(tab.last_error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn files_list_tolerates_float_and_string_numbers() {
+        let v = json!({"files": [
+            {"path": "/a", "size": 10, "chunk_count": 2},
+            {"path": "/b", "size": 10.0, "chunk_count": "2"},
+            {"path": "/c", "size": "10", "chunk_count": 2.0},
+        ]});
+        let rows = parse_files_list(&v);
+        assert_eq!(rows.len(), 3);
+        for r in rows {
+            assert_eq!(r.size, Some(10), "{}", r.path);
+            assert_eq!(r.chunks, Some(2), "{}", r.path);
+        }
+    }
+
+    #[test]
+    fn verify_history_is_bounded_and_summarizes_recent_runs() {
+        let mut h = VerifyHistory::default();
+        for i in 0..(VERIFY_HISTORY_MAX as i64 + 5) {
+            h.push(VerifyRecord {
+                ts: i,
+                ok: 4,
+                failed: (i % 2) as u64,
+            });
+        }
+        assert_eq!(h.records.len(), VERIFY_HISTORY_MAX);
+        assert_eq!(h.records.front().map(|r| r.ts), Some(5));
+
+        let line = h.trend_line(10).unwrap();
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.starts_with("last 10 runs: 5 with failures "), "{}", text);
+    }
+
+    fn row(path: &str, size: Option<u64>) -> FileEntryRow {
+        FileEntryRow {
+            typ: "file".to_string(),
+            path: path.to_string(),
+            size,
+            chunks: None,
+            merkle_root: None,
+            added_at: None,
+            tree: TreePos::default(),
+        }
+    }
+
+    #[test]
+    fn filter_narrows_rows_and_keeps_hidden_selections_by_path() {
+        let mut tab = FilesTab::new(String::new());
+        tab.set_entries(vec![row("/docs/readme.md", None), row("/src/main.rs", None), row("/src/lib.rs", None)]);
+        tab.selection.select_all(&["/docs/readme.md".to_string(), "/src/lib.rs".to_string()]);
+
+        tab.filter.set("src".to_string());
+        tab.apply_filter();
+        let visible: Vec<&str> = tab.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(visible, ["/src/main.rs", "/src/lib.rs"]);
+        assert_eq!(tab.filter_matches[0], vec![1, 2, 3]);
+        // Bulk actions only see the visible selection.
+        assert_eq!(tab.remove_targets(), ["/src/lib.rs"]);
+
+        tab.clear_filter();
+        assert_eq!(tab.entries.len(), 3);
+        assert!(tab.selection.is_selected(&"/docs/readme.md".to_string()));
+    }
+
+    #[test]
+    fn row_jump_digits_reach_the_tab_before_tab_switching() {
+        use crate::tabs::{global_keybind, row_jump_owns_keys};
+        // The order `main` routes keys in once no modal or text input is open.
+        fn route(c: char, app: &mut App, tab: &mut FilesTab) {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            if !row_jump_owns_keys(app) {
+                if let UiCommand::SwitchTab(t) = global_keybind(key, &app.settings.keybinds) {
+                    app.set_active_tab(t);
+                    return;
+                }
+            }
+            tab.on_key(key, app);
+        }
+        let mut app = App::new();
+        app.active_tab = TabId::Files;
+        let mut tab = FilesTab::new(String::new());
+        tab.set_entries((0..30).map(|i| row(&format!("/f{i:02}"), Some(1))).collect());
+
+        for c in "=12G".chars() {
+            route(c, &mut app, &mut tab);
+        }
+        assert_eq!(app.active_tab, TabId::Files);
+        assert_eq!(tab.selected_path().as_deref(), Some("/f11"));
+
+        // Without the prefix a digit still switches tabs.
+        route('3', &mut app, &mut tab);
+        assert_eq!(app.active_tab, TabId::Downloads);
+    }
+
+    #[test]
+    fn size_sort_keeps_unknown_sizes_last_in_both_directions() {
+        let mut tab = FilesTab::new(String::new());
+        tab.model.entries = vec![row("/a", None), row("/b", Some(10)), row("/c", Some(30))];
+        tab.sort = FilesSort::Size;
+
+        tab.sort_desc = true;
+        tab.apply_sort();
+        let order: Vec<&str> = tab.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(order, ["/c", "/b", "/a"]);
+
+        tab.sort_desc = false;
+        tab.apply_sort();
+        let order: Vec<&str> = tab.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(order, ["/b", "/c", "/a"]);
+    }
+
+    #[test]
+    fn recent_adds_dedupe_cap_and_prune_missing_paths() {
+        let mut tab = FilesTab::new(String::new());
+        let here = std::env::current_dir().unwrap().to_string_lossy().into_owned();
+        tab.remember_recent((0..RECENT_ADDS_MAX).map(|i| format!("/no/such/{i}")));
+        tab.remember_recent([here.clone(), "/no/such/3".to_string()].into_iter());
+        assert_eq!(tab.recent.len(), RECENT_ADDS_MAX);
+        assert_eq!(tab.recent[0], "/no/such/3");
+        assert_eq!(tab.recent[1], here);
+
+        tab.recent_open();
+        assert_eq!(tab.recent_adds(), [here]);
+        assert!(tab.recent_menu.is_some());
+    }
+
+    #[test]
+    fn refresh_keeps_a_wheel_scrolled_viewport() {
+        let mut tab = FilesTab::new(String::new());
+        let rows = || (0..30).map(|i| row(&format!("/f{i:02}"), Some(1))).collect::<Vec<_>>();
+        tab.set_entries(rows());
+        tab.last_viewport_rows = 10;
+        tab.focused_path = tab.selected_path();
+
+        scroll_viewport(&mut tab.table_state, 15, tab.last_viewport_rows, tab.entries.len());
+        tab.viewport_detached = true;
+        tab.set_entries(rows());
+        assert_eq!((tab.table_state.selected(), tab.table_state.offset()), (Some(0), 15));
+    }
+
+    #[test]
+    fn tree_nests_under_tracked_dirs_and_honors_collapsed_state() {
+        let mut dir = row("/data/photos", None);
+        dir.typ = "d".to_string();
+        let all = vec![
+            row("/data/photos/a.jpg", Some(1)),
+            row("/data/photos-old.txt", Some(2)),
+            dir,
+            row("/etc/hosts", Some(3)),
+        ];
+
+        let collapsed = flatten_tree(&all, &BTreeSet::new());
+        let paths: Vec<&str> = collapsed.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["/data/photos-old.txt", "/data/photos", "/etc/hosts"]);
+        assert!(collapsed[1].tree.has_children);
+
+        let expanded: BTreeSet<String> = ["/data/photos".to_string()].into();
+        let open = flatten_tree(&all, &expanded);
+        let paths: Vec<&str> = open.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["/data/photos-old.txt", "/data/photos", "/data/photos/a.jpg", "/etc/hosts"]);
+        assert_eq!(open[2].tree.depth, 1);
+        assert_eq!(&open[2].path[open[2].tree.name_start..], "a.jpg");
+    }
+
+    #[test]
+    fn model_keeps_results_only_for_unchanged_listed_rows() {
+        let mut model = FilesModel::default();
+        let mut b = row("/b", Some(1));
+        b.added_at = Some(1);
+        model.set_entries(vec![row("/a", Some(1)), b, row("/c", Some(1))]);
+        model.set_verify("/a", true);
+        model.set_verify("/b", false);
+        model.set_info("/a".to_string(), json!({"size": 1}));
+        model.set_info("/c".to_string(), json!({"size": 1}));
+
+        let mut b = row("/b", Some(1));
+        b.added_at = Some(2);
+        model.set_entries(vec![row("/a", Some(1)), b]);
+        assert_eq!(model.verify("/a"), Some(true));
+        assert_eq!(model.verify("/b"), None);
+        assert!(model.info("/a").is_some());
+        assert!(model.info("/c").is_none() && model.entry("/c").is_none());
+
+        model.clear_verify_under(&["/".to_string()]);
+        assert_eq!(model.verify("/a"), None);
+
+        model.verify_report = Some(json!({"summary": {"ok": 2, "failed": 1}}));
+        assert_eq!(model.verify_summary().as_deref(), Some("verify: 2 ok, 1 failed (3 total)"));
+    }
+}
@@ -1,8 +1,8 @@
-use crate::app::App;
-use crate::tabs::{Tab, TabId, UiCommand};
+use crate::app::{App, LogLevel};
+use crate::tabs::{draw_empty_state, key_help, KeyHelp, Tab, TabId, UiCommand};
 use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Color, Style},
     text::{Line, Text},
     widgets::{Block, Borders, Paragraph},
@@ -13,6 +13,7 @@ use crate::widgets::{
     compute_scrollbar_metrics, contains, handle_scrollbar_down, handle_scrollbar_drag,
     render_scrollbar, ScrollbarDownResult,
 };
+use crate::theme::theme;
 
 pub struct LogsTab {
     scroll: u16,
@@ -51,11 +52,22 @@ impl LogsTab {
     }
 }
 
+const KEY_HELP: &[KeyHelp] = &[
+    key_help("↑/↓", "scroll"),
+    key_help("PgUp/PgDn", "page"),
+    key_help("g / G", "top / bottom"),
+    key_help("Enter", "follow new logs"),
+];
+
 impl Tab for LogsTab {
     fn id(&self) -> TabId {
         TabId::Logs
     }
 
+    fn key_help(&self) -> &'static [KeyHelp] {
+        KEY_HELP
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Rect, app: &mut App) {
         self.update_follow_scroll(area, app);
 
@@ -64,7 +76,7 @@ impl Tab for LogsTab {
             .iter()
             .map(|e| {
                 let msg = format!("[{}] {}", e.level, e.message);
-                Line::styled(msg, Style::default().fg(Color::Gray))
+                Line::styled(msg, Style::default().fg(level_color(&e.level)))
             })
             .collect();
 
@@ -80,6 +92,9 @@ impl Tab for LogsTab {
             .scroll((self.scroll, 0));
 
         f.render_widget(p, text_area);
+        if app.logs.is_empty() {
+            draw_empty_state(f, text_area.inner(Margin { vertical: 1, horizontal: 1 }), "No log messages yet");
+        }
 
         if let Some(metrics) = compute_scrollbar_metrics(area, 0, app.logs.len(), self.scroll as usize) {
             render_scrollbar(f, metrics);
@@ -135,12 +150,14 @@ impl Tab for LogsTab {
             && mouse.row >= area.y
             && mouse.row < area.y + area.height;
 
-        if !inside {
+        // A thumb drag keeps tracking (and can be released) outside the pane.
+        if !inside && self.scrollbar_drag.is_none() {
             return UiCommand::None;
         }
 
         let viewport_rows = area.height.saturating_sub(2).max(1);
         let max_scroll = self.max_scroll(app, viewport_rows);
+        let scroll_step = app.settings.scroll_step.min(u16::MAX as usize) as u16;
         let scrollbar_metrics = compute_scrollbar_metrics(area, 0, app.logs.len(), self.scroll as usize);
 
         match mouse.kind {
@@ -174,11 +191,11 @@ impl Tab for LogsTab {
                 self.scrollbar_drag = None;
             }
             MouseEventKind::ScrollUp => {
-                self.scroll = self.scroll.saturating_sub(3);
+                self.scroll = self.scroll.saturating_sub(scroll_step);
                 self.follow = false;
             }
             MouseEventKind::ScrollDown => {
-                self.scroll = (self.scroll + 3).min(max_scroll);
+                self.scroll = self.scroll.saturating_add(scroll_step).min(max_scroll);
                 if self.scroll >= max_scroll {
                     self.follow = true;
                 }
@@ -189,3 +206,12 @@ impl Tab for LogsTab {
         UiCommand::None
     }
 }
+
+fn level_color(level: &LogLevel) -> Color {
+    match level {
+        LogLevel::Error => theme().error,
+        LogLevel::Warn => theme().accent,
+        LogLevel::Trace | LogLevel::Debug => theme().dim,
+        LogLevel::Info | LogLevel::Other(_) => theme().muted,
+    }
+}
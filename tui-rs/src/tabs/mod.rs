@@ -1,11 +1,13 @@
 use crate::app::App;
 use crate::ipc::NetworkEvent;
-use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use crate::keybinds::{GlobalAction, KeyBindings};
+use crate::tabs::common::ActivityLog;
+use crate::theme::theme;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Alignment, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
@@ -40,6 +42,11 @@ impl TabId {
         TabId::Logs,
     ];
 
+    /// Inverse of [`TabId::title`].
+    pub fn from_title(title: &str) -> Option<TabId> {
+        TabId::ALL.into_iter().find(|t| t.title() == title)
+    }
+
     pub fn title(self) -> &'static str {
         match self {
             TabId::Network => "Network",
@@ -77,15 +84,23 @@ pub enum UiCommand {
     Quit,
     SwitchTab(TabId),
     Refresh,
+    /// Resyncs the node info and every tab, e.g. after restarting the daemon.
+    RefreshAll,
     JoinSelected,
     LeaveSelected,
+    AutoJoinAll,
     TopicNewOpen,
+    TopicEditOpen,
     TopicNewSave,
     TopicNewCancel,
     TopicRemoveSelected,
     FilesVerifySelected,
-    FilesRemoveSelected,
+    FilesRemoveSelected { delete: bool },
     FilesAddOpen,
+    FilesAddPathsOpen,
+    FilesAddPathsConfirm,
+    FilesRecentOpen,
+    FilesRecentConfirm,
     FilesAddConfirm,
     FilesAddCancel,
     BrowseRefresh,
@@ -93,9 +108,88 @@ pub enum UiCommand {
     DownloadsAddOpenPrefill { topic: String, merkle_root: String },
     DownloadsRefresh,
     DownloadsResume,
+    DownloadsCancelSelected,
+    DownloadsTogglePauseSelected,
     DownloadsAddOpen,
     DownloadsAddConfirm,
     DownloadsAddCancel,
+    RepoSwitchOpen,
+    /// Asks for confirmation before dispatching `action`.
+    Confirm { prompt: String, action: Box<UiCommand> },
+    /// Like `Confirm`, but lets the user pick one of several labelled actions (first is default).
+    ConfirmModes { prompt: String, modes: Vec<(String, UiCommand)> },
+}
+
+impl UiCommand {
+    /// Confirmation for removing files: untrack only (default) or untrack and delete the tracked
+    /// files from disk.
+    pub fn confirm_files_remove(prompt: String) -> UiCommand {
+        UiCommand::ConfirmModes {
+            prompt,
+            modes: vec![
+                ("untrack".to_string(), UiCommand::FilesRemoveSelected { delete: false }),
+                ("untrack + delete tracked files".to_string(), UiCommand::FilesRemoveSelected { delete: true }),
+            ],
+        }
+    }
+
+    /// Whether dispatching this command changes daemon state (blocked in read-only mode).
+    pub fn is_mutating(&self) -> bool {
+        match self {
+            UiCommand::JoinSelected
+            | UiCommand::LeaveSelected
+            | UiCommand::AutoJoinAll
+            | UiCommand::TopicNewOpen
+            | UiCommand::TopicEditOpen
+            | UiCommand::TopicNewSave
+            | UiCommand::TopicRemoveSelected
+            | UiCommand::FilesVerifySelected
+            | UiCommand::FilesRemoveSelected { .. }
+            | UiCommand::FilesAddOpen
+            | UiCommand::FilesAddPathsOpen
+            | UiCommand::FilesAddPathsConfirm
+            | UiCommand::FilesRecentOpen
+            | UiCommand::FilesRecentConfirm
+            | UiCommand::FilesAddConfirm
+            | UiCommand::BrowseDownloadSelected
+            | UiCommand::DownloadsAddOpenPrefill { .. }
+            | UiCommand::DownloadsResume
+            | UiCommand::DownloadsCancelSelected
+            | UiCommand::DownloadsTogglePauseSelected
+            | UiCommand::DownloadsAddOpen
+            | UiCommand::DownloadsAddConfirm => true,
+            UiCommand::Confirm { action, .. } => action.is_mutating(),
+            UiCommand::ConfirmModes { modes, .. } => modes.iter().any(|(_, c)| c.is_mutating()),
+            UiCommand::None
+            | UiCommand::Quit
+            | UiCommand::SwitchTab(_)
+            | UiCommand::Refresh
+            | UiCommand::RefreshAll
+            | UiCommand::TopicNewCancel
+            | UiCommand::FilesAddCancel
+            | UiCommand::BrowseRefresh
+            | UiCommand::DownloadsRefresh
+            | UiCommand::DownloadsAddCancel
+            | UiCommand::RepoSwitchOpen => false,
+        }
+    }
+}
+
+/// One row of the help overlay: a key (or key group) and what it does.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyHelp {
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+pub const fn key_help(keys: &'static str, action: &'static str) -> KeyHelp {
+    KeyHelp { keys, action }
+}
+
+/// One-line "Keys: ..." summary of a tab's `KEY_HELP`, for its footer.
+pub fn key_help_line(help: &[KeyHelp]) -> String {
+    let keys: Vec<String> = help.iter().map(|k| format!("{} {}", k.keys, k.action)).collect();
+    format!("Keys: {}", keys.join(" | "))
 }
 
 pub trait Tab {
@@ -108,6 +202,27 @@ pub trait Tab {
         UiCommand::None
     }
     fn on_network_event(&mut self, _evt: NetworkEvent, _app: &mut App) {}
+    /// Bracketed paste; tabs route it into whichever text input has focus.
+    fn on_paste(&mut self, _text: &str, _app: &mut App) {}
+    /// Signals in-flight background work owned by this tab to stop.
+    fn cancel_background(&mut self) {}
+    /// True while the tab runs work that quitting the TUI would abort.
+    fn is_busy(&self) -> bool {
+        false
+    }
+    /// Keys handled by this tab, listed in the help overlay.
+    fn key_help(&self) -> &'static [KeyHelp] {
+        &[]
+    }
+    /// Footer text while a modal, picker or text field of this tab owns the keyboard (global
+    /// keys like `q` do not apply then). Empty keeps the global footer.
+    fn footer_hint(&self) -> String {
+        String::new()
+    }
+    /// Recent actions taken from this tab, shown in a strip above the footer.
+    fn activity(&self) -> Option<&ActivityLog> {
+        None
+    }
 }
 
 pub fn draw_placeholder(f: &mut Frame, area: Rect, title: &str) {
@@ -116,10 +231,25 @@ pub fn draw_placeholder(f: &mut Frame, area: Rect, title: &str) {
     f.render_widget(p, area);
 }
 
+/// Centers `message` in `area`, e.g. the inside of a table that has no rows, so an empty list
+/// says why it is empty and what to do about it.
+pub fn draw_empty_state(f: &mut Frame, area: Rect, message: &str) {
+    if area.height == 0 || area.width == 0 {
+        return;
+    }
+    let height = if message.chars().count() > area.width as usize { 2 } else { 1 }.min(area.height);
+    let line_area = Rect { y: area.y + (area.height - height) / 2, height, ..area };
+    let p = Paragraph::new(message)
+        .style(theme().muted())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(p, line_area);
+}
+
 pub fn tab_label(tab: TabId, active: bool) -> Line<'static> {
     let text = format!("{} {}", tab.number(), tab.title());
     if active {
-        Line::from(Span::styled(text, Style::default().fg(Color::Yellow)))
+        Line::from(Span::styled(text, theme().accent()))
     } else {
         Line::from(Span::raw(text))
     }
@@ -150,17 +280,28 @@ pub fn top_row_char_to_number(c: char) -> Option<usize> {
     }
 }
 
-pub fn global_keybind(key: KeyEvent) -> UiCommand {
-    match key.code {
-        KeyCode::Char('q') => UiCommand::Quit,
-        KeyCode::Char(c) => {
-            if let Some(n) = top_row_char_to_number(c) {
-                if let Some(tab) = TabId::from_number(n) {
-                    return UiCommand::SwitchTab(tab);
-                }
-            }
-            UiCommand::None
-        }
-        _ => UiCommand::None,
+/// Whether the active tab must see keys before the global keybinds: a pending row jump owns the
+/// digits that would otherwise switch tabs.
+pub fn row_jump_owns_keys(app: &App) -> bool {
+    app.row_jump.pending().is_some() && matches!(app.active_tab, TabId::Files | TabId::Network)
+}
+
+pub fn global_keybind(key: KeyEvent, bindings: &KeyBindings) -> UiCommand {
+    match bindings.lookup(&key) {
+        Some(GlobalAction::Quit) => UiCommand::Quit,
+        Some(GlobalAction::Refresh) => UiCommand::Refresh,
+        Some(GlobalAction::RefreshAll) => UiCommand::RefreshAll,
+        Some(GlobalAction::SwitchTab(tab)) => UiCommand::SwitchTab(tab),
+        // Overlay toggles are handled directly in `main.rs`.
+        Some(
+            GlobalAction::Attention
+            | GlobalAction::Help
+            | GlobalAction::Palette
+            | GlobalAction::RepoSwitch
+            | GlobalAction::Status
+            | GlobalAction::DismissBanner
+            | GlobalAction::CycleAutoRefresh,
+        )
+        | None => UiCommand::None,
     }
 }
@@ -1,25 +1,38 @@
-use crate::app::App;
-use crate::ipc::IpcClient;
-use crate::tabs::{Tab, TabId, UiCommand};
+use crate::app::{App, LogLevel, NetworkStats};
+use crate::ipc::{IpcClient, LONG_RPC_TIMEOUT};
+use crate::fuzzy;
+use crate::tabs::common::{
+    adjust_split, clamp_split, copy_to_clipboard, format_elapsed, format_relative_time, now_ms, split_constraints,
+    value_as_u64, ActivityLog, FocusAnchor, PinList, SPLIT_DEFAULT,
+};
+use crate::tabs::{draw_empty_state, key_help, key_help_line, KeyHelp, Tab, TabId, UiCommand};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::{Line, Text},
-    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Row, Sparkline, Table, TableState, Wrap},
     Frame,
 };
 use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
 use std::thread;
-use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use crate::theme::theme;
 use crate::widgets::{
-    contains, cycle_focus_next, cycle_focus_prev, draw_modal_shell, handle_scrollbar_down,
-    handle_scrollbar_drag, modal_geometry, mouse_in, render_scrollbar, Button, MultiSelectState,
-    MultiSelectTableController, ScrollbarDownResult, TableHitTestSpec,
+    contains, cycle_focus_next, cycle_focus_prev, draw_modal_shell, ensure_visible, format_bytes_short, format_rate,
+    handle_scrollbar_down, handle_scrollbar_drag, modal_geometry, mouse_in, render_scrollbar, render_table_viewport,
+    scroll_viewport, Button, MultiSelectState, JsonTreeState, MultiSelectTableController, RowJumpAction,
+    ScrollbarDownResult, TableHitTestSpec, TextInput, TextInputAction,
 };
 
+/// How long a focused topic's peer list is shown before it is fetched again.
+const PEERS_STALE_AFTER: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone)]
 pub struct TopicRow {
     pub name: String,
@@ -30,21 +43,49 @@ pub struct TopicRow {
     pub peers: u64,
 }
 
+impl TopicRow {
+    /// Joined but with nobody to exchange data with; drawn as a warning.
+    pub fn is_alone(&self) -> bool {
+        self.joined && self.peers == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PeerRow {
+    pub id: String,
+    pub address: Option<String>,
+    pub last_seen: Option<i64>,
+}
+
 pub struct NetworkTab {
+    all_topics: Vec<TopicRow>,
+    /// `all_topics` narrowed by `filter`; all row indices refer to this list.
     topics: Vec<TopicRow>,
+    filter: TextInput,
+    filter_editing: bool,
     table_state: TableState,
     selection: MultiSelectState<String>,
     last_error: Option<String>,
+    /// Outcome of the last batch join/leave.
+    last_status: Option<String>,
     hovered: Hovered,
 
     endpoint: String,
 
     join_leave_rx: Receiver<(u64, JoinLeaveMsg)>,
     join_leave_req_id: u64,
-    join_leave_busy: Option<String>,
+    /// What the running join/leave is doing, and when it started.
+    join_leave_busy: Option<(String, Instant)>,
+    join_leave_cancel: Option<Arc<AtomicBool>>,
+    // Join/leave requests made while one is in flight; run in order afterwards.
+    join_leave_queue: VecDeque<JoinLeaveOp>,
 
     // Cached viewport size (in rows) from the last draw. Used for scrollbar math.
     last_viewport_rows: usize,
+    // The wheel scrolled the table away from the selection; cleared by keys and clicks.
+    viewport_detached: bool,
+    // Table share of the table/details split; `<`/`>` adjust it.
+    split_percent: u16,
     // Scrollbar thumb drag grab offset.
     scrollbar_drag: Option<usize>,
 
@@ -52,6 +93,25 @@ pub struct NetworkTab {
     drag_select_start: Option<usize>,
 
     topic_new: TopicNewState,
+
+    stats_tree: JsonTreeState,
+    wrap_stats: bool,
+
+    // Show the selected topic's full key instead of a truncated prefix.
+    reveal_key: bool,
+
+    // Peers of the focused topic, fetched on focus change and again once stale.
+    peers_rx: Receiver<(u64, String, Result<Vec<PeerRow>, String>)>,
+    peers_req_id: u64,
+    peers_focused: Option<String>,
+    peers_requested_at: Option<Instant>,
+    peers_cache: HashMap<String, Vec<PeerRow>>,
+    peers_error: Option<String>,
+
+    // Manually ordered pinned topic names, shown above everything else.
+    pins: PinList,
+
+    activity: ActivityLog,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,16 +144,42 @@ enum TopicNewHovered {
 struct TopicNewState {
     open: bool,
     focus: TopicNewFocus,
-    name: String,
+    name: TextInput,
     auto_join: bool,
     password_enabled: bool,
-    password: String,
+    password: TextInput,
+    /// Render the password in plaintext; masked by default (Ctrl+R toggles).
+    password_visible: bool,
     hovered: TopicNewHovered,
+    /// Original topic name when the modal edits an existing topic.
+    editing: Option<String>,
+}
+
+impl TopicNewState {
+    /// Typed input that closing would discard; an edited topic's prefilled name doesn't count.
+    fn is_dirty(&self) -> bool {
+        let name_typed = self.editing.is_none() && !self.name.value().trim().is_empty();
+        name_typed || !self.password.value().is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum JoinLeaveOp {
+    Join(Vec<String>),
+    Leave(Vec<String>),
 }
 
 #[derive(Debug, Clone)]
 enum JoinLeaveMsg {
-    Done { overview: Value },
+    /// Updated busy label, e.g. "joining 2/5 topic(s)".
+    Progress(String),
+    /// Every topic was attempted; `failed` holds `(name, error)` for the ones the daemon refused.
+    Done {
+        overview: Value,
+        verb: &'static str,
+        succeeded: usize,
+        failed: Vec<(String, String)>,
+    },
     Error { message: String },
 }
 
@@ -103,59 +189,132 @@ impl NetworkTab {
         table_state.select(Some(0));
 
         let (_tx, rx) = mpsc::channel::<(u64, JoinLeaveMsg)>();
+        let (_ptx, prx) = mpsc::channel::<(u64, String, Result<Vec<PeerRow>, String>)>();
         Self {
+            all_topics: Vec::new(),
             topics: Vec::new(),
+            filter: TextInput::new(),
+            filter_editing: false,
             table_state,
             selection: MultiSelectState::default(),
+            last_status: None,
             last_error: None,
             hovered: Hovered::None,
             endpoint,
             join_leave_rx: rx,
             join_leave_req_id: 0,
             join_leave_busy: None,
+            join_leave_cancel: None,
+            join_leave_queue: VecDeque::new(),
             last_viewport_rows: 10,
+            viewport_detached: false,
+            split_percent: SPLIT_DEFAULT,
             scrollbar_drag: None,
             drag_select_start: None,
             topic_new: TopicNewState {
                 open: false,
                 focus: TopicNewFocus::Name,
-                name: String::new(),
+                name: TextInput::new(),
                 auto_join: true,
                 password_enabled: false,
-                password: String::new(),
+                password: TextInput::new(),
+                password_visible: false,
                 hovered: TopicNewHovered::None,
+                editing: None,
             },
+            stats_tree: JsonTreeState::default(),
+            wrap_stats: false,
+            reveal_key: false,
+            peers_rx: prx,
+            peers_req_id: 0,
+            peers_focused: None,
+            peers_requested_at: None,
+            peers_cache: HashMap::new(),
+            peers_error: None,
+            pins: PinList::default(),
+            activity: ActivityLog::default(),
+        }
+    }
+
+    /// Focuses topic `idx` (clamped) and scrolls just enough to keep it visible, so the
+    /// scrollbar thumb matches the jump before the next draw.
+    fn jump_to(&mut self, idx: usize) {
+        if self.topics.is_empty() {
+            return;
         }
+        let idx = idx.min(self.topics.len() - 1);
+        self.table_state.select(Some(idx));
+        self.selection.set_anchor(Some(idx));
+        ensure_visible(&mut self.table_state, self.last_viewport_rows, self.topics.len());
+    }
+
+    pub fn split_percent(&self) -> u16 {
+        self.split_percent
+    }
+
+    pub fn set_split_percent(&mut self, percent: u16) {
+        self.split_percent = clamp_split(percent);
+    }
+
+    /// Points the tab at another daemon, dropping everything cached from the old one.
+    pub fn set_endpoint(&mut self, endpoint: String) {
+        self.cancel_background();
+        let split_percent = self.split_percent;
+        *self = Self::new(endpoint);
+        self.split_percent = split_percent;
     }
 
     pub fn poll_async(&mut self) {
+        if self.peers_requested_at.is_some_and(|at| at.elapsed() >= PEERS_STALE_AFTER) {
+            self.request_focused_peers_if_needed();
+        }
+        while let Ok((req_id, name, res)) = self.peers_rx.try_recv() {
+            if req_id != self.peers_req_id {
+                continue;
+            }
+            match res {
+                Ok(peers) => {
+                    self.peers_cache.insert(name, peers);
+                    self.peers_error = None;
+                }
+                Err(e) => self.peers_error = Some(e),
+            }
+        }
+
         while let Ok((req_id, msg)) = self.join_leave_rx.try_recv() {
             if req_id != self.join_leave_req_id {
                 continue;
             }
 
             match msg {
-                JoinLeaveMsg::Done { overview } => {
-                    self.topics = parse_overview_topics(&overview);
-
-                    let existing: BTreeSet<String> =
-                        self.topics.iter().map(|t| t.name.clone()).collect();
-                    self.selection.retain_existing(&existing);
-
-                    if self.topics.is_empty() {
-                        self.table_state.select(None);
-                    } else if self.table_state.selected().is_none() {
-                        self.table_state.select(Some(0));
-                    } else if let Some(sel) = self.table_state.selected() {
-                        self.table_state
-                            .select(Some(sel.min(self.topics.len().saturating_sub(1))));
+                JoinLeaveMsg::Progress(label) => {
+                    if let Some((busy, _)) = &mut self.join_leave_busy {
+                        *busy = label;
                     }
+                }
+                JoinLeaveMsg::Done {
+                    overview,
+                    verb,
+                    succeeded,
+                    failed,
+                } => {
+                    self.set_topics(parse_overview_topics(&overview));
                     self.join_leave_busy = None;
-                    self.last_error = None;
+                    self.join_leave_cancel = None;
+                    self.last_status = Some(format!("{} {} topic(s)", verb, succeeded));
+                    self.last_error = join_leave_failures(verb, &failed);
+                    match &self.last_error {
+                        Some(e) => self.activity.push(format!("{} {} topic(s); {}", verb, succeeded, e)),
+                        None => self.activity.push(format!("{} {} topic(s)", verb, succeeded)),
+                    }
+                    self.start_next_join_leave();
                 }
                 JoinLeaveMsg::Error { message } => {
                     self.join_leave_busy = None;
+                    self.join_leave_cancel = None;
+                    self.activity.push(format!("join/leave failed: {}", message));
                     self.last_error = Some(message);
+                    self.start_next_join_leave();
                 }
             }
         }
@@ -165,23 +324,105 @@ impl NetworkTab {
         self.topic_new.open
     }
 
+    pub fn is_text_input_active(&self) -> bool {
+        self.filter_editing
+    }
+
+    fn set_topics(&mut self, topics: Vec<TopicRow>) {
+        let anchor = FocusAnchor::capture(&self.table_state, self.focused_topic_name());
+        let detached_offset = self.viewport_detached.then(|| self.table_state.offset());
+        let existing: BTreeSet<String> = topics.iter().map(|t| t.name.clone()).collect();
+        self.selection.retain_existing(&existing);
+        self.all_topics = topics;
+        self.pins.apply(&mut self.all_topics, |t| &t.name);
+        self.apply_filter();
+        if let Some(anchor) = anchor {
+            let names = self.topics.iter().map(|t| t.name.as_str());
+            let idx = anchor.restore(&mut self.table_state, names);
+            self.selection.set_anchor(idx);
+        }
+        // A refresh must not yank a wheel-scrolled view back to the focused row.
+        if let Some(offset) = detached_offset {
+            *self.table_state.offset_mut() = offset;
+            scroll_viewport(&mut self.table_state, 0, self.last_viewport_rows, self.topics.len());
+        }
+        self.request_focused_peers_if_needed();
+    }
+
+    pub fn pins(&self) -> Vec<String> {
+        self.pins.keys().to_vec()
+    }
+
+    pub fn set_pins(&mut self, keys: Vec<String>) {
+        self.pins = PinList::new(keys);
+        self.reorder_keeping_focus();
+    }
+
+    fn focused_topic_name(&self) -> Option<String> {
+        let i = self.table_state.selected()?;
+        self.topics.get(i).map(|t| t.name.clone())
+    }
+
+    fn reorder_keeping_focus(&mut self) {
+        let focused = self.focused_topic_name();
+        self.pins.apply(&mut self.all_topics, |t| &t.name);
+        self.apply_filter();
+        if let Some(name) = focused {
+            if let Some(idx) = self.topics.iter().position(|t| t.name == name) {
+                self.table_state.select(Some(idx));
+                self.selection.set_anchor(Some(idx));
+            }
+        }
+        self.request_focused_peers_if_needed();
+    }
+
+    fn toggle_pin_focused(&mut self) {
+        if let Some(name) = self.focused_topic_name() {
+            self.pins.toggle(&name);
+            self.reorder_keeping_focus();
+        }
+    }
+
+    fn move_pin_focused(&mut self, delta: isize) {
+        let Some(name) = self.focused_topic_name() else {
+            return;
+        };
+        let visible: Vec<&str> = self.topics.iter().map(|t| t.name.as_str()).collect();
+        if self.pins.move_by(&name, delta, &visible) {
+            self.reorder_keeping_focus();
+        }
+    }
+
+    /// Rebuilds the visible `topics` from `all_topics` using the name filter.
+    fn apply_filter(&mut self) {
+        let q = self.filter.value().trim();
+        self.topics = self
+            .all_topics
+            .iter()
+            .filter(|t| q.is_empty() || fuzzy::score(&t.name, q).is_some())
+            .cloned()
+            .collect();
+
+        if self.topics.is_empty() {
+            self.table_state.select(None);
+        } else if self.table_state.selected().is_none() {
+            self.table_state.select(Some(0));
+        } else if let Some(sel) = self.table_state.selected() {
+            self.table_state
+                .select(Some(sel.min(self.topics.len().saturating_sub(1))));
+        }
+    }
+
+    fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.filter_editing = false;
+        self.apply_filter();
+    }
+
     pub fn refresh(&mut self, ipc: &mut IpcClient) {
         match ipc.rpc("network.overview", serde_json::json!({})) {
             Ok(v) => {
-                self.topics = parse_overview_topics(&v);
-
-                let existing: BTreeSet<String> =
-                    self.topics.iter().map(|t| t.name.clone()).collect();
-                self.selection.retain_existing(&existing);
-
-                if self.topics.is_empty() {
-                    self.table_state.select(None);
-                } else if self.table_state.selected().is_none() {
-                    self.table_state.select(Some(0));
-                } else if let Some(sel) = self.table_state.selected() {
-                    self.table_state
-                        .select(Some(sel.min(self.topics.len().saturating_sub(1))));
-                }
+                self.set_topics(parse_overview_topics(&v));
                 self.last_error = None;
             }
             Err(e) => {
@@ -190,14 +431,54 @@ impl NetworkTab {
         }
     }
 
+    /// Applies a `state.topics` payload without a `network.overview` round-trip.
+    ///
+    /// Returns false for shapes it cannot apply locally (e.g. `create`, which needs the new
+    /// topic's key), so the caller falls back to [`NetworkTab::refresh`].
+    pub fn apply_state(&mut self, data: &Value) -> bool {
+        let Some(op) = data.get("op").and_then(|x| x.as_str()) else {
+            return false;
+        };
+        let Some(name) = data.get("name").and_then(|x| x.as_str()) else {
+            return false;
+        };
+        let mut topics = self.all_topics.clone();
+        let Some(idx) = topics.iter().position(|t| t.name == name) else {
+            return false;
+        };
+        match op {
+            "join" => {
+                topics[idx].joined = true;
+                topics[idx].last_joined_at = Some(now_ms() as i64);
+            }
+            "leave" => {
+                topics[idx].joined = false;
+                topics[idx].peers = 0;
+            }
+            "rm" => {
+                topics.remove(idx);
+            }
+            _ => return false,
+        }
+        self.set_topics(topics);
+        true
+    }
+
     fn selected_topic_name(&self) -> Option<String> {
         let idx = self.table_state.selected()?;
         self.topics.get(idx).map(|t| t.name.clone())
     }
 
     fn selected_topic_names_or_focused(&self) -> Vec<String> {
-        if !self.selection.selected().is_empty() {
-            return self.selection.selected().iter().cloned().collect();
+        // Rows hidden by the filter stay selected but are not acted on.
+        let visible: Vec<String> = self
+            .topics
+            .iter()
+            .filter(|t| self.selection.is_selected(&t.name))
+            .map(|t| t.name.clone())
+            .collect();
+        if !visible.is_empty() {
+            return visible;
         }
         self.selected_topic_name().into_iter().collect()
     }
@@ -207,6 +488,52 @@ impl NetworkTab {
         self.topics.get(idx)
     }
 
+    /// Fetches the focused topic's peers when the focus moved or the last fetch is stale.
+    fn request_focused_peers_if_needed(&mut self) {
+        let Some(name) = self.selected_topic_name() else {
+            self.peers_focused = None;
+            self.peers_requested_at = None;
+            return;
+        };
+        let stale = self.peers_requested_at.is_none_or(|at| at.elapsed() >= PEERS_STALE_AFTER);
+        if self.peers_focused.as_deref() == Some(name.as_str()) && !stale {
+            return;
+        }
+        // Keep showing the cached list (if any) until the fresh one arrives.
+        self.peers_focused = Some(name.clone());
+        self.peers_requested_at = Some(Instant::now());
+        self.peers_error = None;
+
+        let endpoint = self.endpoint.clone();
+        let (tx, rx) = mpsc::channel();
+        self.peers_rx = rx;
+
+        self.peers_req_id = self.peers_req_id.wrapping_add(1);
+        let req_id = self.peers_req_id;
+
+        thread::spawn(move || {
+            let res = (|| {
+                let mut c = crate::ipc::IpcClient::connect(endpoint).map_err(|e| e.to_string())?;
+                let v = c
+                    .rpc("topic.peers", serde_json::json!({"name": name.clone()}))
+                    .map_err(|e| e.to_string())?;
+                Ok(parse_peers(&v))
+            })();
+            let _ = tx.send((req_id, name, res));
+        });
+    }
+
+    fn copy_selected_key(&mut self, app: &mut App) {
+        let Some(key) = self.selected_topic().and_then(|t| t.key.clone()) else {
+            app.toast(LogLevel::Warn, "selected topic has no key");
+            return;
+        };
+        match copy_to_clipboard(&key) {
+            Ok(()) => app.toast(LogLevel::Info, "topic key copied to clipboard"),
+            Err(_) => app.toast(LogLevel::Info, format!("topic key: {}", key)),
+        }
+    }
+
     pub fn topic_new_open(&mut self) {
         self.topic_new.open = true;
         self.topic_new.focus = TopicNewFocus::Name;
@@ -214,44 +541,113 @@ impl NetworkTab {
         self.topic_new.auto_join = true;
         self.topic_new.password_enabled = false;
         self.topic_new.password.clear();
+        self.topic_new.password_visible = false;
+        self.topic_new.hovered = TopicNewHovered::None;
+        self.topic_new.editing = None;
+        self.last_error = None;
+    }
+
+    pub fn topic_edit_open(&mut self) {
+        let Some(t) = self.selected_topic().cloned() else {
+            return;
+        };
+        self.topic_new.open = true;
+        self.topic_new.focus = TopicNewFocus::AutoJoin;
+        self.topic_new.name.set(t.name.clone());
+        self.topic_new.auto_join = t.auto_join.unwrap_or(true);
+        self.topic_new.password_enabled = false;
+        self.topic_new.password.clear();
+        self.topic_new.password_visible = false;
         self.topic_new.hovered = TopicNewHovered::None;
+        self.topic_new.editing = Some(t.name);
         self.last_error = None;
     }
 
+    fn topic_new_focus_order(&self) -> &'static [TopicNewFocus] {
+        const CREATE: [TopicNewFocus; 6] = [
+            TopicNewFocus::Name,
+            TopicNewFocus::AutoJoin,
+            TopicNewFocus::PasswordToggle,
+            TopicNewFocus::Password,
+            TopicNewFocus::Save,
+            TopicNewFocus::Abort,
+        ];
+        // The name is read-only while editing, so skip it.
+        const EDIT: [TopicNewFocus; 5] = [
+            TopicNewFocus::AutoJoin,
+            TopicNewFocus::PasswordToggle,
+            TopicNewFocus::Password,
+            TopicNewFocus::Save,
+            TopicNewFocus::Abort,
+        ];
+        if self.topic_new.editing.is_some() {
+            &EDIT
+        } else {
+            &CREATE
+        }
+    }
+
+    /// Closes the topic modal, asking first when it holds typed input that would be lost.
+    fn topic_new_cancel_cmd(&self) -> UiCommand {
+        if !self.topic_new.is_dirty() {
+            return UiCommand::TopicNewCancel;
+        }
+        let prompt = if self.topic_new.editing.is_some() { "Discard topic changes?" } else { "Discard new topic?" };
+        UiCommand::Confirm {
+            prompt: prompt.to_string(),
+            action: Box::new(UiCommand::TopicNewCancel),
+        }
+    }
+
     pub fn topic_new_cancel(&mut self) {
         self.topic_new.open = false;
     }
 
-    pub fn topic_new_save(&mut self, ipc: &mut IpcClient) {
+    pub fn topic_new_save(&mut self, ipc: &mut IpcClient, app: &mut App) {
         if !self.topic_new.open {
             return;
         }
-        let name = self.topic_new.name.trim().to_string();
+        let name = self.topic_new.name.value().trim().to_string();
         if name.is_empty() {
-            self.last_error = Some("topic name required".to_string());
+            app.toast(LogLevel::Warn, "topic name required");
             return;
         }
 
         let password = if self.topic_new.password_enabled {
-            let p = self.topic_new.password.clone();
+            let p = self.topic_new.password.value().to_string();
             Some(p)
         } else {
             None
         };
 
-        let params = serde_json::json!({
-            "name": name,
-            "autoJoin": self.topic_new.auto_join,
-            "password": password,
-        });
+        let res = if let Some(original) = self.topic_new.editing.clone() {
+            // A blank password keeps the topic's existing key.
+            let password = password.filter(|p| !p.is_empty());
+            let params = serde_json::json!({
+                "name": original,
+                "autoJoin": self.topic_new.auto_join,
+                "password": password,
+            });
+            ipc.rpc("topic.update", params)
+        } else {
+            let params = serde_json::json!({
+                "name": name,
+                "autoJoin": self.topic_new.auto_join,
+                "password": password,
+            });
+            ipc.rpc("topic.create", params)
+        };
 
-        match ipc.rpc("topic.create", params) {
+        let verb = if self.topic_new.editing.is_some() { "update" } else { "create" };
+        match res {
             Ok(_) => {
+                self.activity.push(format!("{}d topic {}", verb, name));
                 self.topic_new.open = false;
                 self.last_error = None;
                 self.refresh(ipc);
             }
             Err(e) => {
+                self.activity.push(format!("{} topic failed: {}", verb, e));
                 self.last_error = Some(e.to_string());
             }
         }
@@ -260,51 +656,50 @@ impl NetworkTab {
     pub fn join_selected(&mut self, ipc: &mut IpcClient) {
         let _ = ipc;
         let names = self.selected_topic_names_or_focused();
-        if names.is_empty() {
-            return;
+        if !names.is_empty() {
+            self.enqueue_join_leave(JoinLeaveOp::Join(names));
         }
-
-        let endpoint = self.endpoint.clone();
-        let (tx, rx): (Sender<(u64, JoinLeaveMsg)>, Receiver<(u64, JoinLeaveMsg)>) = mpsc::channel();
-        self.join_leave_rx = rx;
-
-        self.join_leave_req_id = self.join_leave_req_id.wrapping_add(1);
-        let req_id = self.join_leave_req_id;
-
-        self.join_leave_busy = Some(format!("joining {} topic(s)", names.len()));
-        self.last_error = None;
-
-        thread::spawn(move || {
-            let res = (|| {
-                let mut c = crate::ipc::IpcClient::connect(endpoint).map_err(|e| e.to_string())?;
-                for name in names {
-                    c.rpc("topic.join", serde_json::json!({"name": name}))
-                        .map_err(|e| e.to_string())?;
-                }
-                let overview = c
-                    .rpc("network.overview", serde_json::json!({}))
-                    .map_err(|e| e.to_string())?;
-                Ok::<Value, String>(overview)
-            })();
-
-            match res {
-                Ok(overview) => {
-                    let _ = tx.send((req_id, JoinLeaveMsg::Done { overview }));
-                }
-                Err(message) => {
-                    let _ = tx.send((req_id, JoinLeaveMsg::Error { message }));
-                }
-            }
-        });
     }
 
     pub fn leave_selected(&mut self, ipc: &mut IpcClient) {
         let _ = ipc;
         let names = self.selected_topic_names_or_focused();
+        if !names.is_empty() {
+            self.enqueue_join_leave(JoinLeaveOp::Leave(names));
+        }
+    }
+
+    /// Joins every auto-join topic that is not joined yet, ignoring the filter and selection.
+    pub fn auto_join_all(&mut self) {
+        let names: Vec<String> = self
+            .all_topics
+            .iter()
+            .filter(|t| t.auto_join == Some(true) && !t.joined)
+            .map(|t| t.name.clone())
+            .collect();
         if names.is_empty() {
+            self.last_status = Some("all auto-join topics are already joined".to_string());
             return;
         }
+        self.enqueue_join_leave(JoinLeaveOp::Join(names));
+    }
+
+    /// Runs `op` now, or after the in-flight join/leave finishes.
+    fn enqueue_join_leave(&mut self, op: JoinLeaveOp) {
+        if self.join_leave_cancel.is_some() {
+            self.join_leave_queue.push_back(op);
+        } else {
+            self.start_join_leave(op);
+        }
+    }
 
+    fn start_next_join_leave(&mut self) {
+        if let Some(op) = self.join_leave_queue.pop_front() {
+            self.start_join_leave(op);
+        }
+    }
+
+    fn start_join_leave(&mut self, op: JoinLeaveOp) {
         let endpoint = self.endpoint.clone();
         let (tx, rx): (Sender<(u64, JoinLeaveMsg)>, Receiver<(u64, JoinLeaveMsg)>) = mpsc::channel();
         self.join_leave_rx = rx;
@@ -312,25 +707,50 @@ impl NetworkTab {
         self.join_leave_req_id = self.join_leave_req_id.wrapping_add(1);
         let req_id = self.join_leave_req_id;
 
-        self.join_leave_busy = Some(format!("leaving {} topic(s)", names.len()));
+        let (method, verb, done_verb, names) = match op {
+            JoinLeaveOp::Join(names) => ("topic.join", "joining", "joined", names),
+            JoinLeaveOp::Leave(names) => ("topic.leave", "leaving", "left", names),
+        };
+        self.join_leave_busy = Some((format!("{} {} topic(s)", verb, names.len()), Instant::now()));
         self.last_error = None;
+        self.last_status = None;
+        let cancel = self.new_join_leave_cancel();
 
         thread::spawn(move || {
             let res = (|| {
-                let mut c = crate::ipc::IpcClient::connect(endpoint).map_err(|e| e.to_string())?;
-                for name in names {
-                    c.rpc("topic.leave", serde_json::json!({"name": name}))
-                        .map_err(|e| e.to_string())?;
+                let mut c = crate::ipc::IpcClient::connect(endpoint)
+                    .map_err(|e| e.to_string())?
+                    .with_timeout(LONG_RPC_TIMEOUT);
+                // One refused topic should not stop the rest of the batch.
+                let mut succeeded = 0;
+                let mut failed = Vec::new();
+                let total = names.len();
+                for (i, name) in names.into_iter().enumerate() {
+                    if cancel.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    match c.rpc(method, serde_json::json!({"name": name})) {
+                        Ok(_) => succeeded += 1,
+                        Err(e) => failed.push((name, e.to_string())),
+                    }
+                    let label = format!("{} {}/{} topic(s)", verb, i + 1, total);
+                    let _ = tx.send((req_id, JoinLeaveMsg::Progress(label)));
                 }
                 let overview = c
                     .rpc("network.overview", serde_json::json!({}))
                     .map_err(|e| e.to_string())?;
-                Ok::<Value, String>(overview)
+                Ok::<_, String>((overview, succeeded, failed))
             })();
 
             match res {
-                Ok(overview) => {
-                    let _ = tx.send((req_id, JoinLeaveMsg::Done { overview }));
+                Ok((overview, succeeded, failed)) => {
+                    let msg = JoinLeaveMsg::Done {
+                        overview,
+                        verb: done_verb,
+                        succeeded,
+                        failed,
+                    };
+                    let _ = tx.send((req_id, msg));
                 }
                 Err(message) => {
                     let _ = tx.send((req_id, JoinLeaveMsg::Error { message }));
@@ -339,36 +759,163 @@ impl NetworkTab {
         });
     }
 
+    fn new_join_leave_cancel(&mut self) -> Arc<AtomicBool> {
+        if let Some(prev) = self.join_leave_cancel.take() {
+            prev.store(true, Ordering::Relaxed);
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.join_leave_cancel = Some(cancel.clone());
+        cancel
+    }
+
+    fn confirm_remove(&self) -> UiCommand {
+        let n = self.selected_topic_names_or_focused().len();
+        if n == 0 {
+            return UiCommand::None;
+        }
+        UiCommand::Confirm {
+            prompt: format!("Remove {} topic(s)?", n),
+            action: Box::new(UiCommand::TopicRemoveSelected),
+        }
+    }
+
     pub fn remove_selected(&mut self, ipc: &mut IpcClient) {
         let names = self.selected_topic_names_or_focused();
         if names.is_empty() {
             return;
         }
 
+        let total = names.len();
         for name in names {
             match ipc.rpc("topic.rm", serde_json::json!({"name": name})) {
                 Ok(_) => {}
                 Err(e) => {
+                    self.activity.push(format!("remove topic {} failed: {}", name, e));
                     self.last_error = Some(e.to_string());
                     return;
                 }
             }
         }
 
+        self.activity.push(format!("removed {} topic(s)", total));
         self.last_error = None;
         self.refresh(ipc);
     }
 
-    pub fn on_network_event(&mut self, _evt: crate::ipc::NetworkEvent) {
-        // For now we rely on network.stats snapshots.
+    /// Applies per-topic peer changes between `network.overview` refreshes. `network.stats`
+    /// snapshots and events of any other shape are ignored.
+    pub fn on_network_event(&mut self, evt: crate::ipc::NetworkEvent) {
+        let crate::ipc::NetworkEvent::Other { name, data } = evt else {
+            return;
+        };
+        let Some((topic, change)) = parse_peer_event(&name, &data) else {
+            return;
+        };
+        let matches = |t: &TopicRow| t.name == topic || t.key.as_deref() == Some(topic.as_str());
+        for t in self.all_topics.iter_mut().chain(self.topics.iter_mut()).filter(|t| matches(t)) {
+            t.peers = match change {
+                PeerChange::Set(n) => n,
+                PeerChange::Connected => t.peers.saturating_add(1),
+                PeerChange::Disconnected => t.peers.saturating_sub(1),
+            };
+        }
+    }
+}
+
+/// Lays out the known stats fields as label/value cells, two cells per row.
+fn stats_grid_lines(stats: &NetworkStats, bandwidth: Option<(f64, f64)>) -> Vec<Line<'static>> {
+    let count = |n: Option<u64>| n.map(|n| n.to_string()).unwrap_or_else(|| "?".to_string());
+    let mut cells = vec![
+        ("Peers", stats.peers.to_string()),
+        ("Connections", count(stats.connections)),
+        ("Topics joined", stats.topics_joined.to_string()),
+    ];
+    if stats.bytes_up.is_some() || stats.bytes_down.is_some() {
+        let bytes = |n: Option<u64>| n.map(format_bytes_short).unwrap_or_else(|| "?".to_string());
+        cells.push(("Bytes up", bytes(stats.bytes_up)));
+        cells.push(("Bytes down", bytes(stats.bytes_down)));
     }
+    if let Some((up, down)) = bandwidth {
+        cells.push(("Upload", format_rate(up)));
+        cells.push(("Download", format_rate(down)));
+    }
+    cells
+        .chunks(2)
+        .map(|row| {
+            let mut spans = Vec::new();
+            for (label, value) in row {
+                spans.push(Span::styled(format!("{:<15}", label), theme().muted()));
+                spans.push(Span::raw(format!("{:<12}", value)));
+            }
+            Line::from(spans)
+        })
+        .collect()
 }
 
+const KEY_HELP: &[KeyHelp] = &[
+    key_help("j/k ↑/↓", "move"),
+    key_help("J/K PgDn/PgUp", "page"),
+    key_help("g/G Home/End", "first / last topic"),
+    key_help("=<n> Enter/G", "go to row n"),
+    key_help("Tab/Space", "toggle selection"),
+    key_help("Ctrl/Shift+click", "toggle / extend selection"),
+    key_help("Ctrl+a", "select all"),
+    key_help("c", "clear selection"),
+    key_help("Enter", "join selected topics"),
+    key_help("Backspace", "leave selected topics"),
+    key_help("A", "join all auto-join topics"),
+    key_help("n", "new topic"),
+    key_help("e", "edit topic"),
+    key_help("x / Del", "remove topic"),
+    key_help("/", "filter topics"),
+    key_help("Esc", "clear join/leave queue, then filter"),
+    key_help("p", "pin/unpin topic"),
+    key_help("Alt+↑/↓", "move pinned topic"),
+    key_help("v", "reveal/hide topic key"),
+    key_help("y", "copy topic key"),
+    key_help("z", "raw stats tree"),
+    key_help("W", "wrap stats"),
+    key_help("< / >", "shrink / grow the table"),
+    key_help("m", "cycle sparkline metric"),
+];
+
 impl Tab for NetworkTab {
     fn id(&self) -> TabId {
         TabId::Network
     }
 
+    fn key_help(&self) -> &'static [KeyHelp] {
+        KEY_HELP
+    }
+
+    fn footer_hint(&self) -> String {
+        if self.topic_new.open {
+            "Tab/Shift+Tab next field | Enter toggle/save | Ctrl+R show password | Esc cancel".to_string()
+        } else if self.filter_editing {
+            "type to filter topics | Enter done | Esc clear".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    fn activity(&self) -> Option<&ActivityLog> {
+        Some(&self.activity)
+    }
+
+    fn is_busy(&self) -> bool {
+        self.join_leave_busy.is_some() || !self.join_leave_queue.is_empty()
+    }
+
+    fn cancel_background(&mut self) {
+        self.join_leave_queue.clear();
+        if let Some(cancel) = self.join_leave_cancel.take() {
+            // Topics already joined/left stay that way; the next refresh shows the result.
+            cancel.store(true, Ordering::Relaxed);
+            self.join_leave_req_id = self.join_leave_req_id.wrapping_add(1);
+            self.join_leave_busy = None;
+        }
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Rect, app: &mut App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -377,7 +924,7 @@ impl Tab for NetworkTab {
 
         let main = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
+            .constraints(split_constraints(self.split_percent).as_ref())
             .split(chunks[0]);
 
         let list_area = main[0];
@@ -387,8 +934,16 @@ impl Tab for NetworkTab {
         // Table viewport = inside borders minus 1 header row.
         self.last_viewport_rows = list_area.height.saturating_sub(3).max(1) as usize;
 
+        let topics_title = if self.filter_editing {
+            format!("Topics (filter: {}_)", self.filter.value())
+        } else if !self.filter.value().is_empty() {
+            format!("Topics (filter: {}) {}/{}", self.filter.value(), self.topics.len(), self.all_topics.len())
+        } else {
+            "Topics".to_string()
+        };
+
         let header =
-            Row::new(vec!["Sel", "Name", "Peers", "Auto"]).style(Style::default().fg(Color::Yellow));
+            Row::new(vec!["Sel", "Name", "Peers", "Auto"]).style(theme().accent());
         let rows = self.topics.iter().map(|t| {
             let mark = if self.selection.is_selected(&t.name) {
                 "[x]"
@@ -396,12 +951,19 @@ impl Tab for NetworkTab {
                 "[ ]"
             };
             let auto = t.auto_join.map(|b| if b { "yes" } else { "no" }).unwrap_or("?");
+            let name = if self.pins.is_pinned(&t.name) {
+                format!("★ {}", t.name)
+            } else {
+                t.name.clone()
+            };
+            let style = if t.is_alone() { Style::default().fg(theme().accent) } else { Style::default() };
             Row::new(vec![
                 mark.to_string(),
-                t.name.clone(),
+                name,
                 t.peers.to_string(),
                 auto.to_string(),
             ])
+            .style(style)
         });
 
         let table = Table::new(
@@ -414,15 +976,30 @@ impl Tab for NetworkTab {
             ],
         )
         .header(header)
-        .block(Block::default().title("Topics").borders(Borders::ALL))
-        .row_highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        .block(Block::default().title(topics_title).borders(Borders::ALL))
+        .row_highlight_style(theme().highlight());
 
         let show_scrollbar = self.topics.len() > self.last_viewport_rows;
         let mut table_area = list_area;
         if show_scrollbar {
             table_area.width = table_area.width.saturating_sub(1);
         }
-        f.render_stateful_widget(table, table_area, &mut self.table_state);
+        render_table_viewport(
+            f,
+            table,
+            table_area,
+            &mut self.table_state,
+            self.viewport_detached,
+            self.last_viewport_rows,
+        );
+        if self.topics.is_empty() {
+            let msg = if self.all_topics.is_empty() {
+                "No topics yet — press n to create one"
+            } else {
+                "No topics match the filter — Esc clears it"
+            };
+            draw_empty_state(f, table_area.inner(Margin { vertical: 1, horizontal: 1 }), msg);
+        }
 
         let list_table_ctrl = MultiSelectTableController::new(TableHitTestSpec::bordered(1));
         if let Some(metrics) = list_table_ctrl.scrollbar_metrics(
@@ -447,9 +1024,15 @@ impl Tab for NetworkTab {
             .split(details_area);
 
         let selected = self.selected_topic();
-        let detail_lines = if let Some(t) = selected {
+        let mut detail_lines = if let Some(t) = selected {
+            let key = match t.key.as_deref() {
+                None => "?".to_string(),
+                Some(k) if self.reveal_key || k.chars().count() <= 16 => k.to_string(),
+                Some(k) => format!("{}… (v reveal)", k.chars().take(16).collect::<String>()),
+            };
             vec![
                 Line::from(format!("name: {}", t.name)),
+                Line::from(format!("key: {}", key)),
                 Line::from(format!("joined: {}", if t.joined { "yes" } else { "no" })),
                 Line::from(format!("peers: {}", t.peers)),
                 Line::from(format!(
@@ -461,55 +1044,125 @@ impl Tab for NetworkTab {
             vec![Line::from("(no topic selected)")]
         };
 
+        if let Some(t) = selected {
+            match (self.peers_cache.get(&t.name), &self.peers_error) {
+                (_, Some(e)) => detail_lines.push(Line::styled(
+                    format!("peer list unavailable: {}", e),
+                    Style::default().fg(theme().dim),
+                )),
+                (None, None) => detail_lines.push(Line::styled(
+                    "loading peers…",
+                    Style::default().fg(theme().dim),
+                )),
+                (Some(peers), None) => {
+                    for p in peers {
+                        let id: String = p.id.chars().take(12).collect();
+                        let seen = p
+                            .last_seen
+                            .map(format_relative_time)
+                            .unwrap_or_else(|| "?".to_string());
+                        detail_lines.push(Line::from(format!(
+                            "  {} {} {}",
+                            id,
+                            p.address.as_deref().unwrap_or("-"),
+                            seen
+                        )));
+                    }
+                }
+            }
+        }
+
         let details = Paragraph::new(Text::from(detail_lines))
+            .wrap(Wrap { trim: false })
             .block(Block::default().title("Selected").borders(Borders::ALL));
         f.render_widget(details, detail_chunks[0]);
 
         let join_btn = Button {
             label: "Join".to_string(),
-            enabled: selected.map(|t| !t.joined).unwrap_or(false),
+            enabled: !app.read_only && selected.map(|t| !t.joined).unwrap_or(false),
         };
         join_btn.draw(f, detail_chunks[1], self.hovered == Hovered::Join);
 
         let leave_btn = Button {
             label: "Leave".to_string(),
-            enabled: selected.map(|t| t.joined).unwrap_or(false),
+            enabled: !app.read_only && selected.map(|t| t.joined).unwrap_or(false),
         };
         leave_btn.draw(f, detail_chunks[2], self.hovered == Hovered::Leave);
 
         let remove_btn = Button {
             label: "Remove".to_string(),
-            enabled: selected.is_some(),
+            enabled: !app.read_only && selected.is_some(),
         };
         remove_btn.draw(f, detail_chunks[3], self.hovered == Hovered::Remove);
 
         let new_btn = Button {
             label: "New".to_string(),
-            enabled: true,
+            enabled: !app.read_only,
         };
         new_btn.draw(f, detail_chunks[4], self.hovered == Hovered::New);
 
-        let stats_txt = if let Some(v) = &app.network.stats_json {
-            serde_json::to_string_pretty(v).unwrap_or_else(|_| "{}".into())
-        } else {
-            "(no network stats yet)".to_string()
-        };
-
-        let mut lines = vec![Line::from(
-            "Keys: r refresh | n new | x/Del remove | Enter join | Backspace leave | tab/space toggle | Ctrl-click toggle | Shift-click range | drag-select resets | Ctrl+A all | c clear | j/k move",
-        )];
+        let mut lines = vec![Line::from(key_help_line(KEY_HELP))];
+        if let Some((busy, started)) = &self.join_leave_busy {
+            let mut s = format!("busy: {} ({})", busy, format_elapsed(started.elapsed()));
+            if !self.join_leave_queue.is_empty() {
+                s.push_str(&format!(" | queued: {} (Esc clears)", self.join_leave_queue.len()));
+            }
+            lines.push(Line::styled(s, Style::default().fg(theme().info)));
+        } else if let Some(status) = &self.last_status {
+            lines.push(Line::styled(status.clone(), Style::default().fg(theme().ok)));
+        }
         if let Some(e) = &self.last_error {
             lines.push(Line::from(format!("Error: {}", e)));
         }
         lines.push(Line::from(""));
-        lines.extend(Text::from(stats_txt).lines);
+        let prefix_rows = lines.len();
+        match (&app.network.stats, &app.network.stats_json) {
+            (Some(stats), _) if !self.stats_tree.focused => {
+                lines.extend(stats_grid_lines(stats, app.network.bandwidth))
+            }
+            (_, Some(v)) => lines.extend(self.stats_tree.lines(v)),
+            _ => lines.push(Line::from("(no network stats yet)")),
+        }
 
-        let stats = Paragraph::new(Text::from(lines))
-            .block(Block::default().title("Network").borders(Borders::ALL));
-        f.render_widget(stats, chunks[1]);
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(20), Constraint::Length(36)].as_ref())
+            .split(chunks[1]);
+
+        let viewport_rows = bottom[0].height.saturating_sub(2) as usize;
+        let wrap_width = self.wrap_stats.then(|| bottom[0].width.saturating_sub(2));
+        let scroll = self
+            .stats_tree
+            .scroll_for(&lines, prefix_rows, viewport_rows, wrap_width);
+        let mut stats = Paragraph::new(Text::from(lines))
+            .block(Block::default().title("Network").borders(Borders::ALL))
+            .scroll((scroll, 0));
+        if self.wrap_stats {
+            stats = stats.wrap(Wrap { trim: false });
+        }
+        f.render_widget(stats, bottom[0]);
+
+        let history = &app.network.stats_history;
+        // Keep the newest samples that fit; the sparkline draws one column per sample.
+        let fit = bottom[1].width.saturating_sub(2) as usize;
+        let samples: Vec<u64> = history
+            .iter()
+            .skip(history.len().saturating_sub(fit))
+            .map(|(_, v)| v.max(0.0).round() as u64)
+            .collect();
+        let title = match history.back() {
+            Some((_, v)) => format!("{}: {} (m metric)", app.network.stats_metric, v),
+            None => format!("{} (m metric)", app.network.stats_metric),
+        };
+        let spark = Sparkline::default()
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .data(&samples)
+            .style(Style::default().fg(theme().accent));
+        f.render_widget(spark, bottom[1]);
 
         if self.topic_new.open {
-            let inner = draw_modal_shell(f, 60, 60, area, "New topic");
+            let title = if self.topic_new.editing.is_some() { "Edit topic" } else { "New topic" };
+            let inner = draw_modal_shell(f, 60, 60, area, title);
             let pchunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
@@ -532,20 +1185,23 @@ impl Tab for NetworkTab {
             }
 
             let name_border = if self.topic_new.focus == TopicNewFocus::Name {
-                Style::default().fg(Color::Yellow)
+                theme().accent()
             } else {
                 Style::default()
             };
-            let name_p = Paragraph::new(Line::from(self.topic_new.name.clone())).block(
-                Block::default()
-                    .title("Name")
-                    .borders(Borders::ALL)
-                    .border_style(name_border),
-            );
-            f.render_widget(name_p, pchunks[0]);
+            let name_title = if self.topic_new.editing.is_some() { "Name (read-only)" } else { "Name" };
+            let name_block = Block::default()
+                .title(name_title)
+                .borders(Borders::ALL)
+                .border_style(name_border);
+            let name_editable =
+                self.topic_new.focus == TopicNewFocus::Name && self.topic_new.editing.is_none();
+            self.topic_new
+                .name
+                .draw_in(f, pchunks[0], name_block, Style::default(), name_editable);
 
             let auto_border = if self.topic_new.focus == TopicNewFocus::AutoJoin {
-                Style::default().fg(Color::Yellow)
+                theme().accent()
             } else {
                 Style::default()
             };
@@ -561,7 +1217,7 @@ impl Tab for NetworkTab {
             f.render_widget(auto_p, pchunks[1]);
 
             let pw_toggle_border = if self.topic_new.focus == TopicNewFocus::PasswordToggle {
-                Style::default().fg(Color::Yellow)
+                theme().accent()
             } else {
                 Style::default()
             };
@@ -577,22 +1233,31 @@ impl Tab for NetworkTab {
             f.render_widget(pw_toggle_p, pchunks[2]);
 
             let pw_border = if self.topic_new.focus == TopicNewFocus::Password {
-                Style::default().fg(Color::Yellow)
+                theme().accent()
             } else {
                 Style::default()
             };
-            let pw_val = if self.topic_new.password_enabled {
-                self.topic_new.password.clone()
-            } else {
-                "".to_string()
+            let pw_title = match (self.topic_new.editing.is_some(), self.topic_new.password_visible) {
+                (true, false) => "New password (blank keeps existing key, Ctrl+R show)",
+                (true, true) => "New password (blank keeps existing key, Ctrl+R hide)",
+                (false, false) => "Password (optional, Ctrl+R show)",
+                (false, true) => "Password (optional, Ctrl+R hide)",
             };
-            let pw_p = Paragraph::new(Line::from(pw_val)).block(
-                Block::default()
-                    .title("Password (optional)")
-                    .borders(Borders::ALL)
-                    .border_style(pw_border),
-            );
-            f.render_widget(pw_p, pchunks[3]);
+            let pw_block = Block::default()
+                .title(pw_title)
+                .borders(Borders::ALL)
+                .border_style(pw_border);
+            if self.topic_new.password_enabled {
+                let focused = self.topic_new.focus == TopicNewFocus::Password;
+                let pw = &self.topic_new.password;
+                if self.topic_new.password_visible {
+                    pw.draw_in(f, pchunks[3], pw_block, Style::default(), focused);
+                } else {
+                    pw.draw_masked_in(f, pchunks[3], pw_block, Style::default(), focused);
+                }
+            } else {
+                f.render_widget(pw_block, pchunks[3]);
+            }
 
             let save_btn = Button { label: "Save".to_string(), enabled: true };
             save_btn.draw(
@@ -612,31 +1277,47 @@ impl Tab for NetworkTab {
         }
     }
 
-    fn on_key(&mut self, key: KeyEvent, _app: &mut App) -> UiCommand {
+    fn on_paste(&mut self, text: &str, _app: &mut App) {
+        if self.topic_new.open {
+            if self.topic_new.focus == TopicNewFocus::Name && self.topic_new.editing.is_none() {
+                self.topic_new.name.insert_str(text);
+            } else if self.topic_new.focus == TopicNewFocus::Password && self.topic_new.password_enabled {
+                self.topic_new.password.insert_str(text);
+            }
+        } else if self.filter_editing && self.filter.insert_str(text) {
+            self.apply_filter();
+        }
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> UiCommand {
+        let cmd = self.handle_key(key, app);
+        self.request_focused_peers_if_needed();
+        cmd
+    }
+
+    fn on_mouse(&mut self, mouse: MouseEvent, area: Rect, app: &mut App) -> UiCommand {
+        let cmd = self.handle_mouse(mouse, area, app);
+        self.request_focused_peers_if_needed();
+        cmd
+    }
+}
+
+impl NetworkTab {
+    fn handle_key(&mut self, key: KeyEvent, app: &mut App) -> UiCommand {
+        self.viewport_detached = false;
         if self.topic_new.open {
             match key.code {
-                KeyCode::Esc => return UiCommand::TopicNewCancel,
+                KeyCode::Esc => return self.topic_new_cancel_cmd(),
                 KeyCode::Tab => {
-                    const ORDER: [TopicNewFocus; 6] = [
-                        TopicNewFocus::Name,
-                        TopicNewFocus::AutoJoin,
-                        TopicNewFocus::PasswordToggle,
-                        TopicNewFocus::Password,
-                        TopicNewFocus::Save,
-                        TopicNewFocus::Abort,
-                    ];
-                    self.topic_new.focus = cycle_focus_next(self.topic_new.focus, &ORDER);
+                    self.topic_new.focus =
+                        cycle_focus_next(self.topic_new.focus, self.topic_new_focus_order());
                 }
                 KeyCode::BackTab => {
-                    const ORDER: [TopicNewFocus; 6] = [
-                        TopicNewFocus::Name,
-                        TopicNewFocus::AutoJoin,
-                        TopicNewFocus::PasswordToggle,
-                        TopicNewFocus::Password,
-                        TopicNewFocus::Save,
-                        TopicNewFocus::Abort,
-                    ];
-                    self.topic_new.focus = cycle_focus_prev(self.topic_new.focus, &ORDER);
+                    self.topic_new.focus =
+                        cycle_focus_prev(self.topic_new.focus, self.topic_new_focus_order());
+                }
+                KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.topic_new.password_visible = !self.topic_new.password_visible;
                 }
                 KeyCode::Enter => {
                     match self.topic_new.focus {
@@ -645,50 +1326,84 @@ impl Tab for NetworkTab {
                             self.topic_new.password_enabled = !self.topic_new.password_enabled
                         }
                         TopicNewFocus::Save => return UiCommand::TopicNewSave,
-                        TopicNewFocus::Abort => return UiCommand::TopicNewCancel,
+                        TopicNewFocus::Abort => return self.topic_new_cancel_cmd(),
                         _ => {}
                     }
                 }
-                KeyCode::Backspace => {
-                    if self.topic_new.focus == TopicNewFocus::Name {
-                        self.topic_new.name.pop();
+                _ => {
+                    if self.topic_new.focus == TopicNewFocus::Name && self.topic_new.editing.is_none() {
+                        self.topic_new.name.handle_key(key);
                     } else if self.topic_new.focus == TopicNewFocus::Password && self.topic_new.password_enabled {
-                        self.topic_new.password.pop();
+                        self.topic_new.password.handle_key(key);
                     }
                 }
-                KeyCode::Char(c) => {
-                    if self.topic_new.focus == TopicNewFocus::Name {
-                        self.topic_new.name.push(c);
-                    } else if self.topic_new.focus == TopicNewFocus::Password && self.topic_new.password_enabled {
-                        self.topic_new.password.push(c);
-                    }
-                }
-                _ => {}
             }
             return UiCommand::None;
         }
 
+        if self.filter_editing {
+            match self.filter.handle_key(key) {
+                TextInputAction::Changed => self.apply_filter(),
+                TextInputAction::Submit => self.filter_editing = false,
+                TextInputAction::Cancel => self.clear_filter(),
+                TextInputAction::None => {}
+            }
+            return UiCommand::None;
+        }
+
+        if let Some(v) = &app.network.stats_json {
+            if self.stats_tree.handle_key(key, v) {
+                return UiCommand::None;
+            }
+        }
+
+        match app.row_jump.handle_key(key) {
+            RowJumpAction::Ignored => {}
+            RowJumpAction::Pending => return UiCommand::None,
+            RowJumpAction::Jump(row) => {
+                self.jump_to(row);
+                return UiCommand::None;
+            }
+        }
+
         match key.code {
+            KeyCode::Char('z') => {
+                self.stats_tree.focused = app.network.stats_json.is_some();
+            }
+            KeyCode::Char('W') => self.wrap_stats = !self.wrap_stats,
+            KeyCode::Char('<') => self.split_percent = adjust_split(self.split_percent, false),
+            KeyCode::Char('>') => self.split_percent = adjust_split(self.split_percent, true),
+            KeyCode::Char('m') => app.network.cycle_stats_metric(),
+            KeyCode::Char('/') => self.filter_editing = true,
+            KeyCode::Esc if !self.join_leave_queue.is_empty() => self.join_leave_queue.clear(),
+            KeyCode::Esc if !self.filter.value().is_empty() => self.clear_filter(),
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::ALT) => self.move_pin_focused(1),
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::ALT) => self.move_pin_focused(-1),
+            KeyCode::Char('p') => self.toggle_pin_focused(),
             KeyCode::Char('j') | KeyCode::Down => {
                 let next = match self.table_state.selected() {
                     None => 0,
                     Some(i) => (i + 1).min(self.topics.len().saturating_sub(1)),
                 };
-                if !self.topics.is_empty() {
-                    self.table_state.select(Some(next));
-                    self.selection.set_anchor(Some(next));
-                }
+                self.jump_to(next);
             }
             KeyCode::Char('k') | KeyCode::Up => {
                 let next = match self.table_state.selected() {
                     None => 0,
                     Some(i) => i.saturating_sub(1),
                 };
-                if !self.topics.is_empty() {
-                    self.table_state.select(Some(next));
-                    self.selection.set_anchor(Some(next));
-                }
+                self.jump_to(next);
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => {
+                let cur = self.table_state.selected().unwrap_or(0);
+                self.jump_to(cur.saturating_add(self.last_viewport_rows));
+            }
+            KeyCode::PageUp | KeyCode::Char('K') => {
+                let cur = self.table_state.selected().unwrap_or(0);
+                self.jump_to(cur.saturating_sub(self.last_viewport_rows));
             }
+            KeyCode::Char('g') | KeyCode::Home => self.jump_to(0),
+            KeyCode::Char('G') | KeyCode::End => self.jump_to(usize::MAX),
             KeyCode::Tab | KeyCode::Char(' ') => {
                 if let Some(i) = self.table_state.selected() {
                     if let Some(t) = self.topics.get(i) {
@@ -703,21 +1418,20 @@ impl Tab for NetworkTab {
                 let keys: Vec<String> = self.topics.iter().map(|t| t.name.clone()).collect();
                 self.selection.select_all(&keys);
             }
-            KeyCode::Char('A') => {
-                let keys: Vec<String> = self.topics.iter().map(|t| t.name.clone()).collect();
-                self.selection.select_all(&keys);
-            }
-            KeyCode::Char('r') => return UiCommand::Refresh,
+            KeyCode::Char('A') => return UiCommand::AutoJoinAll,
             KeyCode::Enter => return UiCommand::JoinSelected,
             KeyCode::Backspace => return UiCommand::LeaveSelected,
             KeyCode::Char('n') => return UiCommand::TopicNewOpen,
-            KeyCode::Char('x') | KeyCode::Delete => return UiCommand::TopicRemoveSelected,
+            KeyCode::Char('e') => return UiCommand::TopicEditOpen,
+            KeyCode::Char('v') => self.reveal_key = !self.reveal_key,
+            KeyCode::Char('y') => self.copy_selected_key(app),
+            KeyCode::Char('x') | KeyCode::Delete => return self.confirm_remove(),
             _ => {}
         }
         UiCommand::None
     }
 
-    fn on_mouse(&mut self, mouse: MouseEvent, area: Rect, _app: &mut App) -> UiCommand {
+    fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect, app: &mut App) -> UiCommand {
         if self.topic_new.open {
             let (popup, inner) = modal_geometry(60, 60, area);
             let pchunks = Layout::default()
@@ -747,10 +1461,12 @@ impl Tab for NetworkTab {
             match mouse.kind {
                 MouseEventKind::Down(MouseButton::Left) => {
                     if !contains(popup, mouse.column, mouse.row) {
-                        return UiCommand::TopicNewCancel;
+                        return self.topic_new_cancel_cmd();
                     }
                     if mouse_in(pchunks[0], &mouse) {
-                        self.topic_new.focus = TopicNewFocus::Name;
+                        if self.topic_new.editing.is_none() {
+                            self.topic_new.focus = TopicNewFocus::Name;
+                        }
                     } else if mouse_in(pchunks[1], &mouse) {
                         self.topic_new.focus = TopicNewFocus::AutoJoin;
                         self.topic_new.auto_join = !self.topic_new.auto_join;
@@ -764,7 +1480,7 @@ impl Tab for NetworkTab {
                         return UiCommand::TopicNewSave;
                     } else if mouse_in(btns[1], &mouse) {
                         self.topic_new.focus = TopicNewFocus::Abort;
-                        return UiCommand::TopicNewCancel;
+                        return self.topic_new_cancel_cmd();
                     }
                 }
                 _ => {}
@@ -779,7 +1495,7 @@ impl Tab for NetworkTab {
 
         let main = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
+            .constraints(split_constraints(self.split_percent).as_ref())
             .split(chunks[0]);
 
         let list_area = main[0];
@@ -823,6 +1539,7 @@ impl Tab for NetworkTab {
 
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.viewport_detached = false;
                 // Scrollbar interactions.
                 if let Some(metrics) = scrollbar_metrics {
                     if contains(metrics.scrollbar_col, mouse.column, mouse.row) {
@@ -836,6 +1553,7 @@ impl Tab for NetworkTab {
                                 *self.table_state.offset_mut() = offset;
                                 self.table_state
                                     .select(Some(offset.min(self.topics.len().saturating_sub(1))));
+                                ensure_visible(&mut self.table_state, self.last_viewport_rows, self.topics.len());
                                 return UiCommand::None;
                             }
                         }
@@ -860,7 +1578,7 @@ impl Tab for NetworkTab {
                 } else if mouse_in(detail_chunks[2], &mouse) {
                     cmd = UiCommand::LeaveSelected;
                 } else if mouse_in(detail_chunks[3], &mouse) {
-                    cmd = UiCommand::TopicRemoveSelected;
+                    cmd = self.confirm_remove();
                 } else if mouse_in(detail_chunks[4], &mouse) {
                     cmd = UiCommand::TopicNewOpen;
                 }
@@ -872,6 +1590,7 @@ impl Tab for NetworkTab {
                     *self.table_state.offset_mut() = target;
                     self.table_state
                         .select(Some(target.min(self.topics.len().saturating_sub(1))));
+                    ensure_visible(&mut self.table_state, self.last_viewport_rows, self.topics.len());
                     return UiCommand::None;
                 }
 
@@ -891,27 +1610,11 @@ impl Tab for NetworkTab {
                 self.scrollbar_drag = None;
                 self.drag_select_start = None;
             }
-            MouseEventKind::ScrollDown => {
-                if mouse_in(list_area, &mouse) {
-                    let next = match self.table_state.selected() {
-                        None => 0,
-                        Some(i) => (i + 1).min(self.topics.len().saturating_sub(1)),
-                    };
-                    if !self.topics.is_empty() {
-                        self.table_state.select(Some(next));
-                    }
-                }
-            }
-            MouseEventKind::ScrollUp => {
-                if mouse_in(list_area, &mouse) {
-                    let next = match self.table_state.selected() {
-                        None => 0,
-                        Some(i) => i.saturating_sub(1),
-                    };
-                    if !self.topics.is_empty() {
-                        self.table_state.select(Some(next));
-                    }
-                }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp if mouse_in(list_area, &mouse) => {
+                let step = app.settings.scroll_step as isize;
+                let delta = if mouse.kind == MouseEventKind::ScrollUp { -step } else { step };
+                scroll_viewport(&mut self.table_state, delta, self.last_viewport_rows, self.topics.len());
+                self.viewport_detached = true;
             }
             _ => {}
         }
@@ -920,6 +1623,31 @@ impl Tab for NetworkTab {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeerChange {
+    Set(u64),
+    Connected,
+    Disconnected,
+}
+
+/// Reads a peer change from a `network.*` event: an absolute count (`peers`/`peerCount`) for a
+/// topic, or a `peer_connected`/`peer_disconnected` notice. The topic comes back as a name or
+/// a hex key, whichever the event carries.
+fn parse_peer_event(name: &str, data: &Value) -> Option<(String, PeerChange)> {
+    let topic = ["topicName", "topic", "name", "topicKey"]
+        .iter()
+        .find_map(|k| data.get(*k).and_then(|x| x.as_str()))?
+        .to_string();
+    let count = data.get("peers").or_else(|| data.get("peerCount")).and_then(value_as_u64);
+    let change = match (count, name.strip_prefix("network.")?) {
+        (Some(n), _) => PeerChange::Set(n),
+        (None, "peer_connected") => PeerChange::Connected,
+        (None, "peer_disconnected") => PeerChange::Disconnected,
+        _ => return None,
+    };
+    Some((topic, change))
+}
+
 fn parse_overview_topics(v: &Value) -> Vec<TopicRow> {
     let arr = match v.get("topics").and_then(|x| x.as_array()) {
         Some(a) => a,
@@ -937,8 +1665,151 @@ fn parse_overview_topics(v: &Value) -> Vec<TopicRow> {
                 auto_join: t.get("autoJoin").and_then(|x| x.as_bool()),
                 last_joined_at: t.get("lastJoinedAt").and_then(|x| x.as_i64()),
                 joined: t.get("joined").and_then(|x| x.as_bool()).unwrap_or(false),
-                peers: t.get("peers").and_then(|x| x.as_u64()).unwrap_or(0),
+                peers: t.get("peers").and_then(value_as_u64).unwrap_or(0),
             })
         })
         .collect()
 }
+
+/// Parses a `topic.peers` response: `{ joined, peers: [{ id, address, lastSeen }] }`.
+fn parse_peers(v: &Value) -> Vec<PeerRow> {
+    let Some(arr) = v.get("peers").and_then(|p| p.as_array()) else {
+        return Vec::new();
+    };
+
+    arr.iter()
+        .filter_map(|p| {
+            let id = p.get("id").and_then(|x| x.as_str())?.to_string();
+            let address = p.get("address").and_then(|x| x.as_str()).map(|s| s.to_string());
+            let last_seen = p.get("lastSeen").and_then(|x| x.as_i64());
+            Some(PeerRow { id, address, last_seen })
+        })
+        .collect()
+}
+
+/// Aggregates per-topic join/leave failures into one error line.
+fn join_leave_failures(verb: &str, failed: &[(String, String)]) -> Option<String> {
+    match failed {
+        [] => None,
+        [(name, e)] => Some(format!("{}: {}", name, e)),
+        [(name, e), rest @ ..] => Some(format!(
+            "not {} {} topic(s); first: {}: {} (+{} more)",
+            verb,
+            failed.len(),
+            name,
+            e,
+            rest.len()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn overview_peers_tolerates_numeric_representations() {
+        let v = json!({"topics": [
+            {"name": "int", "peers": 3},
+            {"name": "float", "peers": 3.0},
+            {"name": "string", "peers": "3"},
+        ]});
+        let topics = parse_overview_topics(&v);
+        assert_eq!(topics.len(), 3);
+        assert!(topics.iter().all(|t| t.peers == 3));
+    }
+
+    #[test]
+    fn topic_peers_response_parses_id_address_and_last_seen() {
+        let v = json!({"joined": true, "peers": [
+            {"id": "aa", "address": "10.0.0.1:4000", "lastSeen": 1700000000000i64},
+            {"id": "bb", "address": null, "lastSeen": null},
+            {"address": "no id"},
+        ]});
+        let peers = parse_peers(&v);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].address.as_deref(), Some("10.0.0.1:4000"));
+        assert_eq!(peers[0].last_seen, Some(1700000000000));
+        assert_eq!((peers[1].address.as_ref(), peers[1].last_seen), (None, None));
+        assert!(parse_peers(&json!({"joined": false, "peers": []})).is_empty());
+    }
+
+    #[test]
+    fn only_joined_topics_without_peers_are_alone() {
+        let topics = parse_overview_topics(&json!({"topics": [
+            {"name": "alone", "joined": true, "peers": 0},
+            {"name": "busy", "joined": true, "peers": 2},
+            {"name": "idle", "joined": false, "peers": 0},
+        ]}));
+        let alone: Vec<&str> = topics.iter().filter(|t| t.is_alone()).map(|t| t.name.as_str()).collect();
+        assert_eq!(alone, ["alone"]);
+    }
+
+    #[test]
+    fn closing_a_topic_modal_with_typed_input_asks_first() {
+        let mut tab = NetworkTab::new(String::new());
+        tab.topic_new_open();
+        assert!(matches!(tab.topic_new_cancel_cmd(), UiCommand::TopicNewCancel));
+        tab.topic_new.name.set("invite".to_string());
+        assert!(matches!(tab.topic_new_cancel_cmd(), UiCommand::Confirm { .. }));
+
+        // An edit starts with the name filled in; only a typed password makes it dirty.
+        tab.topic_new.editing = Some("invite".to_string());
+        assert!(matches!(tab.topic_new_cancel_cmd(), UiCommand::TopicNewCancel));
+        tab.topic_new.password.set("secret".to_string());
+        assert!(matches!(tab.topic_new_cancel_cmd(), UiCommand::Confirm { .. }));
+    }
+
+    #[test]
+    fn network_events_update_peer_counts_in_place() {
+        use crate::ipc::NetworkEvent;
+        let mut tab = NetworkTab::new(String::new());
+        tab.set_topics(parse_overview_topics(&json!({"topics": [
+            {"name": "a", "topicKey": "aa11", "peers": 2},
+            {"name": "b", "peers": 0},
+        ]})));
+        let peers = |tab: &NetworkTab, name: &str| tab.topics.iter().find(|t| t.name == name).unwrap().peers;
+
+        tab.on_network_event(NetworkEvent::from_event_name("network.peer_connected", json!({"topicKey": "aa11"})));
+        assert_eq!(peers(&tab, "a"), 3);
+        tab.on_network_event(NetworkEvent::from_event_name("network.peers", json!({"topic": "b", "peerCount": 5})));
+        assert_eq!(peers(&tab, "b"), 5);
+        tab.on_network_event(NetworkEvent::from_event_name("network.peer_disconnected", json!({"topicKey": null})));
+        tab.on_network_event(NetworkEvent::from_event_name("network.topic_joined", json!({"topicName": "b"})));
+        assert_eq!((peers(&tab, "a"), peers(&tab, "b")), (3, 5));
+    }
+
+    #[test]
+    fn apply_state_updates_known_topics_and_defers_the_rest() {
+        let mut tab = NetworkTab::new(String::new());
+        tab.set_topics(parse_overview_topics(&json!({"topics": [
+            {"name": "a", "joined": false},
+            {"name": "b", "joined": true, "peers": 2},
+        ]})));
+
+        assert!(tab.apply_state(&json!({"op": "join", "name": "a"})));
+        assert!(tab.all_topics.iter().find(|t| t.name == "a").unwrap().joined);
+        assert!(tab.apply_state(&json!({"op": "leave", "name": "b"})));
+        let b = tab.all_topics.iter().find(|t| t.name == "b").unwrap();
+        assert_eq!((b.joined, b.peers), (false, 0));
+        assert!(tab.apply_state(&json!({"op": "rm", "name": "b"})));
+        assert_eq!(tab.all_topics.len(), 1);
+
+        assert!(!tab.apply_state(&json!({"op": "create", "name": "c"})));
+        assert!(!tab.apply_state(&json!({"op": "join", "name": "unknown"})));
+        assert!(!tab.apply_state(&json!({"unexpected": true})));
+    }
+
+    #[test]
+    fn join_leave_failures_summarize_the_batch() {
+        assert_eq!(join_leave_failures("joined", &[]), None);
+        let one = [("a".to_string(), "timeout".to_string())];
+        assert_eq!(join_leave_failures("joined", &one).as_deref(), Some("a: timeout"));
+        let two = [one[0].clone(), ("b".to_string(), "unknown topic".to_string())];
+        assert_eq!(
+            join_leave_failures("joined", &two).as_deref(),
+            Some("not joined 2 topic(s); first: a: timeout (+1 more)")
+        );
+    }
+}
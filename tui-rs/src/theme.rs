@@ -0,0 +1,158 @@
+use ratatui::style::{Color, Style};
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Color palette used by every widget, read from the optional top-level `"theme"` object in
+/// `swarmfs.config.json`:
+///
+/// ```json
+/// { "theme": { "preset": "high-contrast", "accent": "#ffaf00", "error": "lightred" } }
+/// ```
+///
+/// Colors accept ratatui names (`"yellow"`, `"darkgray"`), `#rrggbb` or a 0-255 index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Headers, key hints and titles of focused panes.
+    pub accent: Color,
+    /// Selected row background.
+    pub highlight_bg: Color,
+    /// Selected row text.
+    pub highlight_fg: Color,
+    pub error: Color,
+    /// Secondary text such as hints and footers.
+    pub muted: Color,
+    /// Disabled or paused items.
+    pub dim: Color,
+    pub ok: Color,
+    /// In-progress work (spinners, gauges).
+    pub info: Color,
+    /// Emphasized text, e.g. fuzzy-match characters.
+    pub text: Color,
+    /// Background of popups and gauge tracks.
+    pub surface: Color,
+    /// Background of hovered buttons.
+    pub hover_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            accent: Color::Yellow,
+            highlight_bg: Color::Yellow,
+            highlight_fg: Color::Black,
+            error: Color::Red,
+            muted: Color::Gray,
+            dim: Color::DarkGray,
+            ok: Color::Green,
+            info: Color::Cyan,
+            text: Color::White,
+            surface: Color::Black,
+            hover_bg: Color::Blue,
+        }
+    }
+}
+
+impl Theme {
+    /// Bright foregrounds on black; avoids dark gray, which is unreadable on many terminals.
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightYellow,
+            highlight_bg: Color::White,
+            highlight_fg: Color::Black,
+            error: Color::LightRed,
+            muted: Color::White,
+            dim: Color::Gray,
+            ok: Color::LightGreen,
+            info: Color::LightCyan,
+            text: Color::White,
+            surface: Color::Black,
+            hover_bg: Color::LightBlue,
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "default" => Some(Self::default()),
+            "high-contrast" | "high_contrast" | "highcontrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Builds a theme from a `theme` object. Unknown presets and unparseable colors are ignored.
+    pub fn from_config(v: Option<&Value>) -> Self {
+        let Some(v) = v else {
+            return Self::default();
+        };
+        let mut t = v
+            .get("preset")
+            .and_then(|p| p.as_str())
+            .and_then(Self::preset)
+            .unwrap_or_default();
+        let slots: [(&str, &mut Color); 11] = [
+            ("accent", &mut t.accent),
+            ("highlightBg", &mut t.highlight_bg),
+            ("highlightFg", &mut t.highlight_fg),
+            ("error", &mut t.error),
+            ("muted", &mut t.muted),
+            ("dim", &mut t.dim),
+            ("ok", &mut t.ok),
+            ("info", &mut t.info),
+            ("text", &mut t.text),
+            ("surface", &mut t.surface),
+            ("hoverBg", &mut t.hover_bg),
+        ];
+        for (name, slot) in slots {
+            if let Some(c) = v.get(name).and_then(|c| c.as_str()).and_then(|s| Color::from_str(s).ok()) {
+                *slot = c;
+            }
+        }
+        t
+    }
+
+    pub fn highlight(&self) -> Style {
+        Style::default().fg(self.highlight_fg).bg(self.highlight_bg)
+    }
+
+    pub fn accent(&self) -> Style {
+        Style::default().fg(self.accent)
+    }
+
+    pub fn muted(&self) -> Style {
+        Style::default().fg(self.muted)
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Installs the process-wide theme. Only the first call has an effect.
+pub fn install(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The active theme. Widgets are drawn without access to `App`, so the palette is global.
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn preset_and_overrides() {
+        assert_eq!(Theme::from_config(None), Theme::default());
+        let t = Theme::from_config(Some(&json!({
+            "preset": "high-contrast",
+            "accent": "#ff8800",
+            "error": "not-a-color"
+        })));
+        assert_eq!(t.accent, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(t.error, Theme::high_contrast().error);
+        assert_eq!(t.highlight_bg, Color::White);
+        let t = Theme::from_config(Some(&json!({ "preset": "neon", "muted": "darkgray" })));
+        assert_eq!(t.accent, Color::Yellow);
+        assert_eq!(t.muted, Color::DarkGray);
+    }
+}
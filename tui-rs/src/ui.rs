@@ -1,36 +1,90 @@
-use crate::app::{App, TabHitbox};
-use crate::tabs::TabId;
+use crate::app::{App, Health, LogLevel, TabHitbox};
+use crate::keybinds::describe;
+use crate::tabs::common::{format_relative_time, ActivityLog};
+use crate::tabs::{KeyHelp, TabId};
+use crate::theme::theme;
+use crate::widgets::{compute_scrollbar_metrics_with_margin, draw_modal_shell, render_scrollbar, truncate_middle};
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub struct LayoutAreas {
     pub tab_bar: Rect,
+    /// Zero-height unless `layout` was asked for a banner row.
+    pub banner: Rect,
     pub content: Rect,
+    /// The active tab's recent actions; zero-height while it has none.
+    pub activity: Rect,
     pub footer: Rect,
 }
 
-pub fn layout(area: Rect) -> LayoutAreas {
+/// Most rows the activity strip takes above the footer.
+const ACTIVITY_ROWS: usize = 2;
+
+/// Height of the activity strip for `log`, for passing to [`layout`].
+pub fn activity_rows(log: Option<&ActivityLog>) -> u16 {
+    log.map_or(0, |l| l.recent(ACTIVITY_ROWS).count() as u16)
+}
+
+pub fn layout(area: Rect, banner: bool, activity_rows: u16) -> LayoutAreas {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),
+            Constraint::Length(u16::from(banner)),
             Constraint::Min(1),
+            Constraint::Length(activity_rows),
             Constraint::Length(1),
         ])
         .split(area);
 
     LayoutAreas {
         tab_bar: chunks[0],
-        content: chunks[1],
-        footer: chunks[2],
+        banner: chunks[1],
+        content: chunks[2],
+        activity: chunks[3],
+        footer: chunks[4],
     }
 }
 
+/// The active tab's last few actions above the footer, newest at the bottom.
+pub fn draw_activity(f: &mut Frame, area: Rect, log: Option<&ActivityLog>) {
+    let Some(log) = log else {
+        return;
+    };
+    let mut lines: Vec<Line> = log
+        .recent(area.height as usize)
+        .map(|(ts, text)| {
+            Line::from(vec![
+                Span::styled(" · ", theme().muted()),
+                Span::raw(text.clone()),
+                Span::styled(format!("  {}", format_relative_time(*ts)), theme().muted()),
+            ])
+        })
+        .collect();
+    lines.reverse();
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// One-line warning under the tab bar (e.g. daemon version mismatch).
+pub fn draw_banner(f: &mut Frame, area: Rect, app: &App) {
+    let Some(msg) = &app.version_banner else {
+        return;
+    };
+    let line = Line::from(vec![
+        Span::styled(format!(" ! {} ", msg), Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)),
+        Span::styled("(X dismiss)", theme().muted()),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
+}
+
 pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &mut App) {
     // Simple, explicit renderer so we can compute hitboxes.
     let mut spans: Vec<Span> = Vec::new();
@@ -48,9 +102,9 @@ pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &mut App) {
 
         let label = format!("{} {}", tab.number(), tab.title());
         let style = if *tab == app.active_tab {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
-            Style::default().fg(Color::Gray)
+            theme().muted()
         };
 
         let w = label.len() as u16;
@@ -68,13 +122,409 @@ pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &mut App) {
 
     app.ui.tab_hitboxes = hitboxes;
 
+    let (dot, label) = match app.health() {
+        Health::Good => (theme().ok, "connected"),
+        Health::Degraded => (theme().accent, "degraded"),
+        Health::Down => (theme().error, "disconnected"),
+    };
+    spans.push(Span::styled("   ●", Style::default().fg(dot)));
+    let peers = app.peer_count().map_or_else(|| "? peers".to_string(), |n| format!("{} peer(s)", n));
+    spans.push(Span::styled(format!(" {} · {}", label, peers), theme().muted()));
+
+    if !app.ipc_connected {
+        spans.push(Span::styled(
+            "   reconnecting…",
+            theme().highlight(),
+        ));
+    }
+
+    if app.read_only {
+        spans.push(Span::styled(
+            "   READ-ONLY",
+            Style::default().fg(theme().highlight_fg).bg(theme().error),
+        ));
+    }
+
+    if !app.attention.is_empty() {
+        let has_error = app.attention.iter().any(|a| a.level == LogLevel::Error);
+        let color = if has_error { theme().error } else { theme().accent };
+        spans.push(Span::styled(
+            format!("   ⚠ {} (w)", app.attention.len()),
+            Style::default().fg(color),
+        ));
+    }
+
     let p = Paragraph::new(Line::from(spans));
     f.render_widget(p, area);
 }
 
-pub fn draw_footer(f: &mut Frame, area: Rect, app: &mut App) {
-    let text = format!("Tab {} | q quit", app.active_tab.title());
-    let p = Paragraph::new(text)
-        .block(Block::default().borders(Borders::TOP));
-    f.render_widget(p, area);
+/// Room for the daemon endpoint in the footer; the help overlay shows it in full.
+const ENDPOINT_FOOTER_CHARS: usize = 32;
+
+/// Footer hint for a global overlay that captures the keyboard.
+fn overlay_hint(app: &App) -> Option<&'static str> {
+    if app.confirm.is_open() {
+        Some("y confirm | any other key cancels")
+    } else if app.palette.is_open() {
+        Some("type to filter | ↑/↓ move | Enter run | Esc close")
+    } else if app.ui.help_open {
+        Some("j/k scroll | Esc/? close")
+    } else if app.ui.attention_open || app.ui.status_open || app.ui.repos_open {
+        Some("Esc close")
+    } else {
+        None
+    }
+}
+
+/// Bottom line: a pending row jump, the newest toast, or key hints for whatever currently owns
+/// the keyboard (an overlay, then the active tab's `footer_hint`, then the global keys).
+pub fn draw_footer(f: &mut Frame, area: Rect, app: &mut App, tab_hint: &str) {
+    let auto = match app.auto_refresh {
+        Some(d) => format!("auto-refresh {}s", d.as_secs()),
+        None => "auto-refresh off".to_string(),
+    };
+    let text = match overlay_hint(app) {
+        Some(hint) => hint.to_string(),
+        None if !tab_hint.is_empty() => tab_hint.to_string(),
+        None => {
+            let mut text =
+                format!("Tab {} | t {} | : commands | ? help | I status | q quit", app.active_tab.title(), auto);
+            if let Some(since) = app.connected_since {
+                text.push_str(&format!(" | connected {}", format_clock(since.elapsed().as_secs())));
+            }
+            if !app.endpoint.is_empty() {
+                text.push_str(&format!(" | {}", truncate_middle(&app.endpoint, ENDPOINT_FOOTER_CHARS)));
+            }
+            text
+        }
+    };
+    if let Some(digits) = app.row_jump.pending() {
+        let prompt = format!("go to row: ={}_ (Enter/G jump, Esc cancel)", digits);
+        f.render_widget(Paragraph::new(Line::styled(prompt, theme().accent())), area);
+        return;
+    }
+    let line = match app.current_toast() {
+        Some((t, others)) => {
+            let color = match t.level {
+                LogLevel::Error => theme().error,
+                LogLevel::Warn => theme().accent,
+                _ => theme().info,
+            };
+            let mut msg = t.message.clone();
+            if others > 0 {
+                msg.push_str(&format!(" (+{})", others));
+            }
+            Line::styled(msg, Style::default().fg(color))
+        }
+        None => Line::styled(text, theme().muted()),
+    };
+    f.render_widget(Paragraph::new(line), area);
+}
+
+/// Shown before the main UI while the daemon's socket is missing or refusing connections.
+pub fn draw_waiting_screen(f: &mut Frame, endpoint: &str, repo_root: &Path, waited: Duration, last_error: Option<&str>) {
+    let spinner = ['|', '/', '-', '\\'][(waited.as_millis() / 250 % 4) as usize];
+    let mut lines = vec![
+        Line::styled(format!("{} Waiting for the SwarmFS daemon…", spinner), theme().accent()),
+        Line::from(""),
+        Line::from(format!("repo:     {}", repo_root.display())),
+        Line::from(format!("endpoint: {}", endpoint)),
+        Line::from(format!("waited:   {}s", waited.as_secs())),
+    ];
+    if let Some(e) = last_error {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(format!("last attempt: {}", e), theme().error));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Start the daemon in this repo; the TUI connects automatically. q/Esc to quit.",
+        theme().muted(),
+    ));
+    let p = Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: false })
+        .block(Block::default().title("SwarmFS").borders(Borders::ALL));
+    f.render_widget(p, f.area());
+}
+
+pub fn draw_attention_overlay(f: &mut Frame, area: Rect, app: &mut App) {
+    let inner = draw_modal_shell(f, 80, 60, area, "Attention (warnings & errors)");
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if app.attention.is_empty() {
+        lines.push(Line::from("(nothing to acknowledge)"));
+    }
+    for (i, a) in app.attention.iter().enumerate() {
+        let color = if a.level == LogLevel::Error { theme().error } else { theme().accent };
+        let mut style = Style::default().fg(color);
+        if i == app.ui.attention_selected {
+            style = Style::default().fg(theme().highlight_fg).bg(color);
+        }
+        let count = if a.count > 1 { format!(" (x{})", a.count) } else { String::new() };
+        lines.push(Line::styled(format!("[{}] {}{}", a.level, a.message, count), style));
+    }
+
+    // Keep the selected item in view.
+    let rows = chunks[0].height.max(1) as usize;
+    let scroll = app.ui.attention_selected.saturating_sub(rows.saturating_sub(1));
+    let p = Paragraph::new(Text::from(lines)).scroll((scroll as u16, 0));
+    f.render_widget(p, chunks[0]);
+
+    let help = Paragraph::new("j/k move | Enter/a acknowledge | A acknowledge all | Esc/w close")
+        .style(theme().muted());
+    f.render_widget(help, chunks[1]);
+}
+
+pub(crate) fn format_uptime(secs: u64) -> String {
+    let (d, h, m, s) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if d > 0 {
+        format!("{}d {}h {}m", d, h, m)
+    } else if h > 0 {
+        format!("{}h {}m {}s", h, m, s)
+    } else {
+        format!("{}m {}s", m, s)
+    }
+}
+
+/// `HH:MM:SS`; hours keep growing past a day.
+fn format_clock(secs: u64) -> String {
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+pub fn draw_status_overlay(f: &mut Frame, area: Rect, app: &mut App) {
+    let inner = draw_modal_shell(f, 70, 50, area, "Node status");
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let unknown = "?".to_string();
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let mut lines: Vec<Line> = Vec::new();
+    match &app.node_status {
+        None => lines.push(Line::from("(node.status unavailable)")),
+        Some(s) => {
+            lines.push(Line::from(format!("Peer id:   {}", s.peer_id.as_ref().unwrap_or(&unknown))));
+            lines.push(Line::from(format!("Version:   {}", s.version.as_ref().unwrap_or(&unknown))));
+            lines.push(Line::from(format!(
+                "Uptime:    {}",
+                s.uptime_secs.map(format_uptime).unwrap_or_else(|| unknown.clone())
+            )));
+            lines.push(Line::from(format!("Data dir:  {}", s.data_dir.as_ref().unwrap_or(&unknown))));
+            lines.push(Line::from(format!(
+                "DB: {} | network: {} | protocol: {}",
+                yes_no(s.db_open),
+                yes_no(s.network_running),
+                yes_no(s.protocol_running)
+            )));
+            lines.push(Line::from(format!(
+                "Peers:     {}",
+                s.peer_count.map(|n| n.to_string()).unwrap_or_else(|| unknown.clone())
+            )));
+            lines.push(Line::from("Listening:"));
+            if s.listen_addresses.is_empty() {
+                lines.push(Line::from("  ?"));
+            }
+            for a in &s.listen_addresses {
+                lines.push(Line::from(format!("  {}", a)));
+            }
+        }
+    }
+    if let Some(t) = app.status_fetched_at {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            format!("updated {}s ago", t.elapsed().as_secs()),
+            theme().muted(),
+        ));
+    }
+    f.render_widget(Paragraph::new(Text::from(lines)), chunks[0]);
+
+    let help = Paragraph::new("Esc/I close").style(theme().muted());
+    f.render_widget(help, chunks[1]);
+}
+
+/// Handles a key while the node status overlay is open.
+pub fn status_on_key(key: KeyEvent, app: &mut App) {
+    if matches!(key.code, KeyCode::Esc | KeyCode::Char('I') | KeyCode::Char('q')) {
+        app.ui.status_open = false;
+    }
+}
+
+/// Keys handled in `main.rs` before any tab sees them.
+fn global_key_help(app: &App) -> Vec<(String, &'static str)> {
+    let kb = &app.settings.keybinds;
+    let mut rows = vec![
+        (describe(&kb.quit), "quit"),
+        (describe(&kb.refresh), "refresh the active tab"),
+        (describe(&kb.refresh_all), "refresh all tabs"),
+    ];
+    for (i, tab) in TabId::ALL.iter().enumerate() {
+        let keys = if kb.tabs[i].is_empty() {
+            tab.number().to_string()
+        } else {
+            describe(&kb.tabs[i])
+        };
+        rows.push((keys, tab.title()));
+    }
+    rows.push((describe(&kb.auto_refresh), "cycle auto-refresh interval"));
+    rows.push((describe(&kb.attention), "warnings & errors"));
+    rows.push((describe(&kb.status), "node status"));
+    rows.push((describe(&kb.repos), "switch repo"));
+    rows.push((describe(&kb.palette), "command palette"));
+    rows.push((describe(&kb.help), "this help"));
+    if app.version_banner.is_some() {
+        rows.push((describe(&kb.dismiss_banner), "dismiss the version warning"));
+    }
+    rows
+}
+
+pub fn draw_help_overlay(f: &mut Frame, area: Rect, app: &mut App, tab_keys: &[KeyHelp]) {
+    let inner = draw_modal_shell(f, 95, 95, area, "Keys");
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let tab_rows: Vec<(String, &str)> = tab_keys.iter().map(|k| (k.keys.to_string(), k.action)).collect();
+    let sections = [("Global", global_key_help(app)), (app.active_tab.title(), tab_rows)];
+    let width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(k, _)| k.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = vec![Line::styled("Connected to", theme().accent())];
+    for (label, value) in [("repo", app.repo_root.display().to_string()), ("endpoint", app.endpoint.clone())] {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<width$}", label, width = width), theme().muted()),
+            Span::raw(format!("  {}", value)),
+        ]));
+    }
+    for (title, rows) in &sections {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(title.to_string(), theme().accent()));
+        if rows.is_empty() {
+            lines.push(Line::styled("  (no tab-specific keys)", theme().muted()));
+        }
+        for (keys, action) in rows {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<width$}", keys, width = width), theme().accent()),
+                Span::raw(format!("  {}", action)),
+            ]));
+        }
+    }
+
+    let rows = chunks[0].height.max(1) as usize;
+    let max_scroll = lines.len().saturating_sub(rows);
+    app.ui.help_scroll = app.ui.help_scroll.min(max_scroll);
+    let metrics = compute_scrollbar_metrics_with_margin(
+        chunks[0],
+        0,
+        Margin { horizontal: 0, vertical: 0 },
+        lines.len(),
+        app.ui.help_scroll,
+    );
+    f.render_widget(Paragraph::new(Text::from(lines)).scroll((app.ui.help_scroll as u16, 0)), chunks[0]);
+    if let Some(m) = metrics {
+        render_scrollbar(f, m);
+    }
+
+    let help = Paragraph::new("j/k scroll | PgUp/PgDn page | g/G top/bottom | Esc/? close").style(theme().muted());
+    f.render_widget(help, chunks[1]);
+}
+
+/// Handles a key while the help overlay is open. Scrolling is clamped on the next draw.
+pub fn help_on_key(key: KeyEvent, app: &mut App) {
+    let page = 10;
+    let s = &mut app.ui.help_scroll;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => app.ui.help_open = false,
+        KeyCode::Char('j') | KeyCode::Down => *s = s.saturating_add(1),
+        KeyCode::Char('k') | KeyCode::Up => *s = s.saturating_sub(1),
+        KeyCode::PageDown | KeyCode::Char(' ') => *s = s.saturating_add(page),
+        KeyCode::PageUp => *s = s.saturating_sub(page),
+        KeyCode::Char('g') | KeyCode::Home => *s = 0,
+        KeyCode::Char('G') | KeyCode::End => *s = usize::MAX,
+        _ => {}
+    }
+}
+
+pub fn draw_repos_overlay(f: &mut Frame, area: Rect, app: &mut App) {
+    let inner = draw_modal_shell(f, 70, 50, area, "Switch repo");
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, repo) in app.repos.iter().enumerate() {
+        let marker = if *repo == app.repo_root { "● " } else { "  " };
+        let text = format!("{}{}", marker, repo.display());
+        if i == app.ui.repos_selected {
+            lines.push(Line::styled(text, theme().highlight()));
+        } else {
+            lines.push(Line::from(text));
+        }
+    }
+    if app.repos.len() <= 1 {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            "Add more with a \"repos\" array in swarmfs.config.json or SWARMFS_REPOS.",
+            theme().muted(),
+        ));
+    }
+
+    let rows = chunks[0].height.max(1) as usize;
+    let scroll = app.ui.repos_selected.saturating_sub(rows.saturating_sub(1));
+    f.render_widget(Paragraph::new(Text::from(lines)).scroll((scroll as u16, 0)), chunks[0]);
+
+    let help = Paragraph::new("j/k move | Enter connect | Esc/o close").style(theme().muted());
+    f.render_widget(help, chunks[1]);
+}
+
+/// Handles a key while the repo switcher is open; returns the repo to connect to.
+pub fn repos_on_key(key: KeyEvent, app: &mut App) -> Option<PathBuf> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('o') | KeyCode::Char('q') => app.ui.repos_open = false,
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.ui.repos_selected = (app.ui.repos_selected + 1).min(app.repos.len().saturating_sub(1));
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.ui.repos_selected = app.ui.repos_selected.saturating_sub(1);
+        }
+        KeyCode::Enter => {
+            app.ui.repos_open = false;
+            let repo = app.repos.get(app.ui.repos_selected)?;
+            if *repo != app.repo_root {
+                return Some(repo.clone());
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Handles a key while the attention overlay is open.
+pub fn attention_on_key(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('w') | KeyCode::Char('q') => app.ui.attention_open = false,
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.ui.attention_selected =
+                (app.ui.attention_selected + 1).min(app.attention.len().saturating_sub(1));
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.ui.attention_selected = app.ui.attention_selected.saturating_sub(1);
+        }
+        KeyCode::Enter | KeyCode::Char('a') => {
+            let idx = app.ui.attention_selected;
+            app.acknowledge_attention(idx);
+        }
+        KeyCode::Char('A') => app.acknowledge_all_attention(),
+        _ => {}
+    }
 }
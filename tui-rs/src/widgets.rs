@@ -1,14 +1,16 @@
+use crate::theme::theme;
 use crossterm::event::MouseEvent;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::Rect,
     layout::Margin,
-    style::{Color, Style},
-    text::{Line, Text},
+    style::Style,
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
-use ratatui::widgets::TableState;
+use ratatui::widgets::{Table, TableState};
+use serde_json::Value;
 use std::collections::BTreeSet;
 
 pub fn contains(rect: Rect, col: u16, row: u16) -> bool {
@@ -311,11 +313,56 @@ pub fn nav_next_index(current: Option<usize>, len: usize, delta: isize) -> Optio
     Some(next as usize)
 }
 
+/// Scrolls `state` just enough that its selected row is inside a `viewport_rows`-tall window, and
+/// never past the last full page. Call after any programmatic change to the selection or offset.
+pub fn ensure_visible(state: &mut TableState, viewport_rows: usize, content_len: usize) {
+    let rows = viewport_rows.max(1);
+    let max_offset = content_len.saturating_sub(rows);
+    let mut offset = state.offset().min(max_offset);
+    if let Some(sel) = state.selected().filter(|_| content_len > 0) {
+        let sel = sel.min(content_len - 1);
+        offset = offset.clamp((sel + 1).saturating_sub(rows), sel);
+    }
+    *state.offset_mut() = offset;
+}
+
 pub fn nav_page_delta(viewport_rows: usize, forward: bool) -> isize {
     let d = viewport_rows.max(1) as isize;
     if forward { d } else { -d }
 }
 
+/// Rows moved per mouse-wheel notch unless `tui.scrollStep` says otherwise.
+pub const DEFAULT_SCROLL_STEP: usize = 3;
+
+/// Mouse-wheel scrolling: moves the viewport by `delta` rows and leaves the selection where it is.
+pub fn scroll_viewport(state: &mut TableState, delta: isize, viewport_rows: usize, content_len: usize) {
+    let max_offset = content_len.saturating_sub(viewport_rows.max(1)) as isize;
+    *state.offset_mut() = (state.offset() as isize + delta).clamp(0, max_offset) as usize;
+}
+
+/// Renders `table`, honoring a viewport that [`scroll_viewport`] moved away from the selection
+/// (`detached`). ratatui would otherwise scroll back to the selected row, so an off-screen
+/// selection is left out of that frame.
+pub fn render_table_viewport(
+    f: &mut Frame,
+    table: Table,
+    area: Rect,
+    state: &mut TableState,
+    detached: bool,
+    viewport_rows: usize,
+) {
+    let offset = state.offset();
+    let off_screen = state
+        .selected()
+        .is_some_and(|sel| sel < offset || sel >= offset + viewport_rows.max(1));
+    if detached && off_screen {
+        let mut view = TableState::default().with_offset(offset);
+        f.render_stateful_widget(table, area, &mut view);
+    } else {
+        f.render_stateful_widget(table, area, state);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextInputAction {
     None,
@@ -324,52 +371,130 @@ pub enum TextInputAction {
     Cancel,
 }
 
+/// Single-line text entry with a cursor.
+///
+/// Supports Left/Right, Home/End, Backspace/Delete at the cursor and Ctrl+W to delete the word
+/// before it. `cursor` counts chars, not bytes.
 #[derive(Debug, Clone, Default)]
 pub struct TextInput {
     value: String,
+    cursor: usize,
 }
 
 impl TextInput {
     pub fn new() -> Self {
-        Self { value: String::new() }
+        Self::default()
     }
 
     pub fn from(value: String) -> Self {
-        Self { value }
+        let cursor = value.chars().count();
+        Self { value, cursor }
     }
 
     pub fn value(&self) -> &str {
         &self.value
     }
 
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replaces the value and puts the cursor at its end.
     pub fn set(&mut self, value: String) {
-        self.value = value;
+        *self = Self::from(value);
     }
 
     pub fn clear(&mut self) {
         self.value.clear();
+        self.cursor = 0;
     }
 
     pub fn pop(&mut self) -> bool {
-        self.value.pop().is_some()
+        let popped = self.value.pop().is_some();
+        self.cursor = self.cursor.min(self.value.chars().count());
+        popped
+    }
+
+    fn byte_at(&self, char_idx: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_idx)
+            .map(|(b, _)| b)
+            .unwrap_or(self.value.len())
+    }
+
+    /// Inserts pasted text at the cursor (see [`sanitize_paste`]). Returns whether anything
+    /// was inserted.
+    pub fn insert_str(&mut self, text: &str) -> bool {
+        let clean = sanitize_paste(text);
+        let at = self.byte_at(self.cursor);
+        self.value.insert_str(at, &clean);
+        self.cursor += clean.chars().count();
+        !clean.is_empty()
+    }
+
+    fn insert(&mut self, c: char) {
+        let at = self.byte_at(self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+    }
+
+    /// Removes chars `from..to` (char indices) and leaves the cursor at `from`.
+    fn remove_range(&mut self, from: usize, to: usize) -> bool {
+        if from >= to {
+            return false;
+        }
+        let (a, b) = (self.byte_at(from), self.byte_at(to));
+        self.value.replace_range(a..b, "");
+        self.cursor = from;
+        true
+    }
+
+    /// Start of the word before the cursor, skipping whitespace first (like a shell's Ctrl+W).
+    fn word_start_before_cursor(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().take(self.cursor).collect();
+        let mut i = chars.len();
+        while i > 0 && chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        while i > 0 && !chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        i
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> TextInputAction {
+        let len = self.value.chars().count();
+        let changed = |c: bool| if c { TextInputAction::Changed } else { TextInputAction::None };
         match key.code {
             KeyCode::Esc => TextInputAction::Cancel,
             KeyCode::Enter => TextInputAction::Submit,
-            KeyCode::Backspace => {
-                if self.value.pop().is_some() {
-                    TextInputAction::Changed
-                } else {
-                    TextInputAction::None
-                }
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                TextInputAction::None
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(len);
+                TextInputAction::None
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                TextInputAction::None
+            }
+            KeyCode::End => {
+                self.cursor = len;
+                TextInputAction::None
+            }
+            KeyCode::Backspace => changed(self.remove_range(self.cursor.saturating_sub(1), self.cursor)),
+            KeyCode::Delete => changed(self.remove_range(self.cursor, (self.cursor + 1).min(len))),
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                changed(self.remove_range(self.word_start_before_cursor(), self.cursor))
             }
             KeyCode::Char(c) => {
                 if key.modifiers.contains(KeyModifiers::CONTROL) || c.is_control() {
                     return TextInputAction::None;
                 }
-                self.value.push(c);
+                self.insert(c);
                 TextInputAction::Changed
             }
             _ => TextInputAction::None,
@@ -378,15 +503,125 @@ impl TextInput {
 
     pub fn draw(&self, f: &mut Frame, area: Rect, title: &str, focused: bool) {
         let style = if focused {
-            Style::default().fg(Color::Yellow)
+            theme().accent()
         } else {
             Style::default()
         };
+        let block = Block::default().title(title).borders(Borders::ALL);
+        self.draw_in(f, area, block, style, focused);
+    }
+
+    /// Renders the value inside `block`, scrolled so the cursor stays visible. When `focused`,
+    /// the terminal cursor is placed at the edit position.
+    pub fn draw_in(&self, f: &mut Frame, area: Rect, block: Block, style: Style, focused: bool) {
+        self.render(f, area, block, style, focused, self.value.clone());
+    }
+
+    /// Like [`TextInput::draw_in`], but shows one `•` per char instead of the value.
+    pub fn draw_masked_in(&self, f: &mut Frame, area: Rect, block: Block, style: Style, focused: bool) {
+        let masked = "•".repeat(self.value.chars().count());
+        self.render(f, area, block, style, focused, masked);
+    }
 
-        let p = Paragraph::new(Line::from(self.value.clone()))
+    /// `shown` must have the same char count as `value` so the cursor lines up.
+    fn render(&self, f: &mut Frame, area: Rect, block: Block, style: Style, focused: bool, shown: String) {
+        let inner = block.inner(area);
+        let before: String = shown.chars().take(self.cursor).collect();
+        let cursor_x = Line::from(before).width() as u16;
+        let scroll = (cursor_x + 1).saturating_sub(inner.width);
+
+        let p = Paragraph::new(Line::from(shown))
             .style(style)
-            .block(Block::default().title(title).borders(Borders::ALL));
+            .block(block)
+            .scroll((0, scroll));
         f.render_widget(p, area);
+        if focused && inner.width > 0 && inner.height > 0 {
+            f.set_cursor_position((inner.x + cursor_x - scroll, inner.y));
+        }
+    }
+}
+
+/// Makes pasted text safe for a single-line input: a trailing newline is dropped, inner
+/// newlines and tabs become spaces, and other control chars (e.g. ESC) are removed so they
+/// cannot reach the terminal.
+pub fn sanitize_paste(text: &str) -> String {
+    text.trim_end_matches(['\r', '\n'])
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowJumpAction {
+    /// Not a jump key; the caller should handle it.
+    Ignored,
+    /// Consumed while collecting digits (or cancelled with Esc).
+    Pending,
+    /// Jump to this 0-based row.
+    Jump(usize),
+}
+
+/// Numeric row jump: `=`, a 1-based row number, then Enter or `G`.
+///
+/// The `=` prefix keeps bare digits free for tab switching; while an entry is pending the
+/// caller routes digits here before the global keybinds.
+#[derive(Debug, Clone, Default)]
+pub struct RowJump {
+    digits: Option<String>,
+}
+
+impl RowJump {
+    /// The digits typed so far (possibly none), while an entry is in progress.
+    pub fn pending(&self) -> Option<&str> {
+        self.digits.as_deref()
+    }
+
+    pub fn clear(&mut self) {
+        self.digits = None;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> RowJumpAction {
+        if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return RowJumpAction::Ignored;
+        }
+        let Some(digits) = &mut self.digits else {
+            if key.code != KeyCode::Char('=') {
+                return RowJumpAction::Ignored;
+            }
+            self.digits = Some(String::new());
+            return RowJumpAction::Pending;
+        };
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if digits.len() < 9 {
+                    digits.push(c);
+                }
+                RowJumpAction::Pending
+            }
+            KeyCode::Enter | KeyCode::Char('G') => {
+                let row = digits.parse::<usize>().unwrap_or(1);
+                self.digits = None;
+                RowJumpAction::Jump(row.saturating_sub(1))
+            }
+            KeyCode::Backspace => {
+                if digits.pop().is_none() {
+                    self.digits = None;
+                }
+                RowJumpAction::Pending
+            }
+            KeyCode::Esc => {
+                self.digits = None;
+                RowJumpAction::Pending
+            }
+            _ => {
+                self.digits = None;
+                RowJumpAction::Ignored
+            }
+        }
     }
 }
 
@@ -628,7 +863,7 @@ pub fn render_scrollbar(f: &mut Frame, metrics: ScrollbarMetrics) {
         };
         lines.push(Line::from(ch));
     }
-    let sb = Paragraph::new(Text::from(lines)).style(Style::default().bg(Color::Black));
+    let sb = Paragraph::new(Text::from(lines)).style(Style::default().bg(theme().surface));
     f.render_widget(sb, metrics.scrollbar_col);
 }
 
@@ -693,13 +928,13 @@ impl Button {
 
     pub fn draw(&self, f: &mut Frame, area: Rect, hovered: bool) {
         let base = if self.enabled {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme().text)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(theme().dim)
         };
 
         let style = if hovered && self.enabled {
-            base.bg(Color::Blue)
+            base.bg(theme().hover_bg)
         } else {
             base
         };
@@ -710,3 +945,550 @@ impl Button {
         f.render_widget(p, area);
     }
 }
+
+/// Collapsible, keyboard-navigable view over a `serde_json::Value`.
+///
+/// Nodes are addressed by JSON pointer, so expansion survives value refreshes as long as the
+/// shape stays the same. Top-level keys start collapsed.
+#[derive(Debug, Clone, Default)]
+pub struct JsonTreeState {
+    expanded: BTreeSet<String>,
+    selected: usize,
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone)]
+struct JsonTreeRow {
+    path: String,
+    depth: usize,
+    label: String,
+    expandable: bool,
+    expanded: bool,
+}
+
+impl JsonTreeState {
+    pub fn reset(&mut self) {
+        self.expanded.clear();
+        self.selected = 0;
+    }
+
+    fn rows(&self, v: &Value) -> Vec<JsonTreeRow> {
+        let mut out = Vec::new();
+        match v {
+            Value::Object(_) | Value::Array(_) => self.walk_children(v, "", 0, &mut out),
+            _ => out.push(JsonTreeRow {
+                path: String::new(),
+                depth: 0,
+                label: v.to_string(),
+                expandable: false,
+                expanded: false,
+            }),
+        }
+        out
+    }
+
+    fn walk_children(&self, v: &Value, path: &str, depth: usize, out: &mut Vec<JsonTreeRow>) {
+        let children: Vec<(String, String, &Value)> = match v {
+            Value::Object(m) => m
+                .iter()
+                .map(|(k, child)| {
+                    let seg = k.replace('~', "~0").replace('/', "~1");
+                    (format!("{}/{}", path, seg), k.clone(), child)
+                })
+                .collect(),
+            Value::Array(a) => a
+                .iter()
+                .enumerate()
+                .map(|(i, child)| (format!("{}/{}", path, i), format!("[{}]", i), child))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for (child_path, key, child) in children {
+            let (expandable, summary) = match child {
+                Value::Object(m) => (true, format!("{{{}}}", m.len())),
+                Value::Array(a) => (true, format!("[{}]", a.len())),
+                other => (false, other.to_string()),
+            };
+            let expanded = expandable && self.expanded.contains(&child_path);
+            let label = if expandable {
+                format!("{} {} {}", if expanded { "▾" } else { "▸" }, key, summary)
+            } else {
+                format!("  {}: {}", key, summary)
+            };
+            out.push(JsonTreeRow {
+                path: child_path.clone(),
+                depth,
+                label,
+                expandable,
+                expanded,
+            });
+            if expanded {
+                self.walk_children(child, &child_path, depth + 1, out);
+            }
+        }
+    }
+
+    /// Handles navigation keys while focused. Returns false when the key was not consumed.
+    pub fn handle_key(&mut self, key: KeyEvent, v: &Value) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let rows = self.rows(v);
+        self.selected = self.selected.min(rows.len().saturating_sub(1));
+        let current = rows.get(self.selected);
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('z') => self.focused = false,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(rows.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some(row) = current.filter(|r| r.expandable) {
+                    if row.expanded {
+                        self.expanded.remove(&row.path);
+                    } else {
+                        self.expanded.insert(row.path.clone());
+                    }
+                }
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(row) = current.filter(|r| r.expandable) {
+                    self.expanded.insert(row.path.clone());
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                if let Some(row) = current {
+                    if row.expanded {
+                        self.expanded.remove(&row.path);
+                    } else if let Some(parent) = row.path.rsplit_once('/').map(|(p, _)| p) {
+                        // Collapse the parent and move the cursor onto it.
+                        if let Some(idx) = rows.iter().position(|r| r.path == parent) {
+                            self.expanded.remove(parent);
+                            self.selected = idx;
+                        }
+                    }
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn lines(&self, v: &Value) -> Vec<Line<'static>> {
+        self.rows(v)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let text = format!("{}{}", "  ".repeat(row.depth), row.label);
+                let style = if self.focused && i == self.selected {
+                    theme().highlight()
+                } else if row.expandable {
+                    Style::default().fg(theme().info)
+                } else {
+                    Style::default()
+                };
+                Line::styled(text, style)
+            })
+            .collect()
+    }
+
+    /// Vertical scroll that keeps the selected row visible below `prefix_rows` header lines.
+    ///
+    /// `lines` is everything rendered in the panel; pass `wrap_width` when the panel wraps.
+    pub fn scroll_for(
+        &self,
+        lines: &[Line],
+        prefix_rows: usize,
+        viewport_rows: usize,
+        wrap_width: Option<u16>,
+    ) -> u16 {
+        if !self.focused {
+            return 0;
+        }
+        scroll_to_line(lines, prefix_rows + self.selected, viewport_rows, wrap_width)
+    }
+}
+
+/// Yes/no confirmation popup guarding a destructive action.
+///
+/// While open it should capture all key input; only `y`/`Y` yields the pending action. A dialog
+/// opened with several modes lets Tab/←/→ pick one first; the first mode is the default.
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog<T> {
+    prompt: String,
+    /// Labelled alternatives; a plain yes/no dialog has a single unlabelled entry.
+    modes: Vec<(String, T)>,
+    chosen: usize,
+}
+
+impl<T> Default for ConfirmDialog<T> {
+    fn default() -> Self {
+        Self {
+            prompt: String::new(),
+            modes: Vec::new(),
+            chosen: 0,
+        }
+    }
+}
+
+impl<T> ConfirmDialog<T> {
+    pub fn open(&mut self, prompt: String, action: T) {
+        self.open_modes(prompt, vec![(String::new(), action)]);
+    }
+
+    /// Opens with a choice between `modes`, defaulting to the first.
+    pub fn open_modes(&mut self, prompt: String, modes: Vec<(String, T)>) {
+        self.prompt = prompt;
+        self.modes = modes;
+        self.chosen = 0;
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.modes.is_empty()
+    }
+
+    /// Returns the chosen action on `y`; Tab/←/→ switch modes and any other key dismisses.
+    pub fn on_key(&mut self, key: KeyEvent) -> Option<T> {
+        let n = self.modes.len();
+        match key.code {
+            KeyCode::Tab | KeyCode::Right if n > 1 => self.chosen = (self.chosen + 1) % n,
+            KeyCode::BackTab | KeyCode::Left if n > 1 => self.chosen = (self.chosen + n - 1) % n,
+            KeyCode::Char('y') | KeyCode::Char('Y') if n > 0 => {
+                let chosen = self.chosen.min(n - 1);
+                return self.modes.drain(..).nth(chosen).map(|(_, action)| action);
+            }
+            _ => self.modes.clear(),
+        }
+        None
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open() {
+            return;
+        }
+        let inner = draw_modal_shell(f, 50, 20, area, "Confirm");
+        let mut lines = vec![Line::from(format!("{} [y/N]", self.prompt)), Line::from("")];
+        if self.modes.len() > 1 {
+            let mut spans = vec![Span::raw("mode: ")];
+            for (i, (label, _)) in self.modes.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw("  "));
+                }
+                spans.push(if i == self.chosen {
+                    Span::styled(format!("[{}]", label), theme().highlight())
+                } else {
+                    Span::styled(format!(" {} ", label), theme().muted())
+                });
+            }
+            lines.push(Line::from(spans));
+            lines.push(Line::from(""));
+            lines.push(Line::styled("Tab switch mode | y confirm | any other key cancels", theme().muted()));
+        } else {
+            lines.push(Line::styled("y confirm | any other key cancels", theme().muted()));
+        }
+        f.render_widget(Paragraph::new(Text::from(lines)), inner);
+    }
+}
+
+/// Rendered rows taken by `line`, wrapping at `wrap_width` columns when set.
+pub fn wrapped_rows(line: &Line, wrap_width: Option<u16>) -> usize {
+    match wrap_width {
+        Some(w) if w > 0 => line.width().max(1).div_ceil(w as usize),
+        _ => 1,
+    }
+}
+
+/// Scroll offset (in rendered rows) that puts logical line `target` at the bottom of the
+/// viewport once it would otherwise fall below it.
+pub fn scroll_to_line(
+    lines: &[Line],
+    target: usize,
+    viewport_rows: usize,
+    wrap_width: Option<u16>,
+) -> u16 {
+    let before: usize = lines
+        .iter()
+        .take(target)
+        .map(|l| wrapped_rows(l, wrap_width))
+        .sum();
+    let end = before
+        + lines
+            .get(target)
+            .map(|l| wrapped_rows(l, wrap_width))
+            .unwrap_or(1);
+    end.saturating_sub(viewport_rows).min(u16::MAX as usize) as u16
+}
+
+/// Where [`truncate_middle`] cuts `s` to fit `max` chars: `(head, tail)` char counts kept on
+/// either side of the ellipsis, or `None` when `s` already fits. The tail favours the last
+/// path component so the file name stays readable.
+pub fn middle_cut(s: &str, max: usize) -> Option<(usize, usize)> {
+    let len = s.chars().count();
+    if len <= max {
+        return None;
+    }
+    let budget = max.saturating_sub(1);
+    let name_len = s.rfind('/').map_or(0, |i| s[i..].chars().count());
+    let min_head = (budget / 4).max(1).min(budget);
+    let tail = name_len.max(budget - budget / 2).min(budget - min_head);
+    Some((budget - tail, tail))
+}
+
+/// Shortens `s` to at most `max` chars by replacing its middle with `…`
+/// (`src/very/long/dir/file.rs` -> `src/v…/file.rs`).
+pub fn truncate_middle(s: &str, max: usize) -> String {
+    let Some((head, tail)) = middle_cut(s, max) else {
+        return s.to_string();
+    };
+    if max == 0 {
+        return String::new();
+    }
+    let len = s.chars().count();
+    let mut out: String = s.chars().take(head).collect();
+    out.push('…');
+    out.extend(s.chars().skip(len - tail));
+    out
+}
+
+/// Compact binary size ("512B", "1.5K", "3.2G") for narrow table columns.
+pub fn format_bytes_short(n: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    const TB: f64 = GB * 1024.0;
+
+    let n_f = n as f64;
+    if n_f < KB {
+        format!("{}B", n)
+    } else if n_f < MB {
+        format!("{:.1}K", n_f / KB)
+    } else if n_f < GB {
+        format!("{:.1}M", n_f / MB)
+    } else if n_f < TB {
+        format!("{:.1}G", n_f / GB)
+    } else {
+        format!("{:.1}T", n_f / TB)
+    }
+}
+
+/// Human-readable transfer rate ("512 B/s", "1.2 MB/s"), binary units, at most one decimal.
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    const UNITS: [&str; 5] = ["B/s", "KB/s", "MB/s", "GB/s", "TB/s"];
+    let shown = |v: f64, unit: usize| if unit == 0 { v.round() } else { (v * 10.0).round() / 10.0 };
+
+    let mut v = if bytes_per_sec.is_finite() { bytes_per_sec.max(0.0) } else { 0.0 };
+    let mut unit = 0;
+    // Compare the rounded value so 1023.96 KB/s shows as "1.0 MB/s" rather than "1024.0 KB/s".
+    while unit + 1 < UNITS.len() && shown(v, unit) >= 1024.0 {
+        v /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", v, UNITS[0])
+    } else {
+        format!("{:.1} {}", v, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirm_dialog_switches_modes_before_confirming() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut d = ConfirmDialog::default();
+        d.open_modes("Remove?".to_string(), vec![("keep".to_string(), 1), ("delete".to_string(), 2)]);
+        assert_eq!(d.on_key(key(KeyCode::Tab)), None);
+        assert!(d.is_open());
+        assert_eq!(d.on_key(key(KeyCode::Char('y'))), Some(2));
+        assert!(!d.is_open());
+
+        d.open("Quit?".to_string(), 3);
+        assert_eq!(d.on_key(key(KeyCode::Tab)), None);
+        assert!(!d.is_open());
+    }
+
+    #[test]
+    fn truncate_middle_keeps_the_start_and_file_name() {
+        assert_eq!(truncate_middle("src/main.rs", 20), "src/main.rs");
+        assert_eq!(truncate_middle("src/very/long/dir/file.rs", 14), "src/v…/file.rs");
+        // A file name longer than the budget still keeps some of the start.
+        assert_eq!(truncate_middle("a/averyveryverylongname.txt", 10), "a/…ame.txt");
+        assert_eq!(truncate_middle("abcdefghij", 5), "ab…ij");
+        assert_eq!(truncate_middle("abcdef", 1), "…");
+        assert_eq!(truncate_middle("abcdef", 0), "");
+        assert_eq!(truncate_middle("ééééééé", 4).chars().count(), 4);
+    }
+
+    #[test]
+    fn scroll_viewport_keeps_selection_and_clamps() {
+        let mut state = TableState::default();
+        state.select(Some(2));
+        scroll_viewport(&mut state, 3, 5, 20);
+        assert_eq!((state.selected(), state.offset()), (Some(2), 3));
+        scroll_viewport(&mut state, 100, 5, 20);
+        assert_eq!(state.offset(), 15);
+        scroll_viewport(&mut state, -100, 5, 20);
+        assert_eq!(state.offset(), 0);
+        // Content shorter than the viewport never scrolls.
+        scroll_viewport(&mut state, 3, 5, 4);
+        assert_eq!(state.offset(), 0);
+    }
+
+    #[test]
+    fn ensure_visible_scrolls_to_selection() {
+        let mut state = TableState::default();
+        // Selection below the window: scroll so it is the last visible row.
+        state.select(Some(12));
+        ensure_visible(&mut state, 5, 20);
+        assert_eq!(state.offset(), 8);
+        // Selection above the window: scroll so it is the first visible row.
+        state.select(Some(3));
+        ensure_visible(&mut state, 5, 20);
+        assert_eq!(state.offset(), 3);
+        // Already visible: untouched.
+        state.select(Some(5));
+        ensure_visible(&mut state, 5, 20);
+        assert_eq!(state.offset(), 3);
+        // An offset past the last page (e.g. after a scrollbar drag) is pulled back.
+        *state.offset_mut() = 18;
+        state.select(Some(19));
+        ensure_visible(&mut state, 5, 20);
+        assert_eq!(state.offset(), 15);
+        // No selection or content.
+        state.select(None);
+        ensure_visible(&mut state, 5, 0);
+        assert_eq!(state.offset(), 0);
+    }
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) -> TextInputAction {
+        input.handle_key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn text_input_cursor_stays_in_bounds() {
+        let mut t = TextInput::from("héllo".to_string());
+        assert_eq!(t.cursor(), 5);
+        press(&mut t, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(t.cursor(), 5);
+        press(&mut t, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut t, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(t.cursor(), 0);
+        assert_eq!(press(&mut t, KeyCode::Backspace, KeyModifiers::NONE), TextInputAction::None);
+
+        press(&mut t, KeyCode::Right, KeyModifiers::NONE);
+        press(&mut t, KeyCode::Char('X'), KeyModifiers::SHIFT);
+        assert_eq!((t.value(), t.cursor()), ("hXéllo", 2));
+        press(&mut t, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(t.value(), "hXllo");
+        press(&mut t, KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(press(&mut t, KeyCode::Delete, KeyModifiers::NONE), TextInputAction::None);
+    }
+
+    #[test]
+    fn text_input_ctrl_w_deletes_the_previous_word() {
+        let mut t = TextInput::from("join my topic  ".to_string());
+        press(&mut t, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!((t.value(), t.cursor()), ("join my ", 8));
+
+        // Mid-string: only the word left of the cursor goes.
+        press(&mut t, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut t, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!((t.value(), t.cursor()), ("join  ", 5));
+        press(&mut t, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        press(&mut t, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!((t.value(), t.cursor()), (" ", 0));
+    }
+
+    #[test]
+    fn pasted_text_is_sanitized_and_inserted_at_cursor() {
+        assert_eq!(sanitize_paste("abc\x1b[31m\tdef\r\n"), "abc[31m def");
+        let mut t = TextInput::from("ad".to_string());
+        press(&mut t, KeyCode::Left, KeyModifiers::NONE);
+        assert!(t.insert_str("bc\n"));
+        assert_eq!((t.value(), t.cursor()), ("abcd", 3));
+        assert!(!t.insert_str("\x07"));
+    }
+
+    #[test]
+    fn row_jump_collects_digits_until_enter_or_g() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut j = RowJump::default();
+        assert_eq!(j.handle_key(key(KeyCode::Enter)), RowJumpAction::Ignored);
+        // Bare digits are not a jump; they belong to tab switching.
+        assert_eq!(j.handle_key(key(KeyCode::Char('4'))), RowJumpAction::Ignored);
+
+        assert_eq!(j.handle_key(key(KeyCode::Char('='))), RowJumpAction::Pending);
+        assert_eq!(j.pending(), Some(""));
+        j.handle_key(key(KeyCode::Char('4')));
+        j.handle_key(key(KeyCode::Char('2')));
+        assert_eq!(j.pending(), Some("42"));
+        assert_eq!(j.handle_key(key(KeyCode::Enter)), RowJumpAction::Jump(41));
+        assert_eq!(j.pending(), None);
+
+        j.handle_key(key(KeyCode::Char('=')));
+        j.handle_key(key(KeyCode::Char('7')));
+        assert_eq!(j.handle_key(key(KeyCode::Char('G'))), RowJumpAction::Jump(6));
+
+        j.handle_key(key(KeyCode::Char('=')));
+        j.handle_key(key(KeyCode::Char('3')));
+        assert_eq!(j.handle_key(key(KeyCode::Esc)), RowJumpAction::Pending);
+        assert_eq!(j.pending(), None);
+
+        j.handle_key(key(KeyCode::Char('=')));
+        j.handle_key(key(KeyCode::Backspace));
+        assert_eq!(j.pending(), None);
+    }
+
+    #[test]
+    fn json_tree_expands_collapses_and_lists_visible_rows() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let v = serde_json::json!({ "peers": { "a": 1, "b": [true] }, "topics": 2 });
+        let rows = |t: &JsonTreeState| -> Vec<String> {
+            t.lines(&v).iter().map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect()).collect()
+        };
+        let mut t = JsonTreeState::default();
+        assert_eq!(rows(&t), ["▸ peers {2}", "  topics: 2"]);
+        assert!(!t.handle_key(key(KeyCode::Enter), &v), "keys are ignored until focused");
+
+        t.focused = true;
+        assert!(t.handle_key(key(KeyCode::Enter), &v));
+        assert_eq!(rows(&t), ["▾ peers {2}", "    a: 1", "  ▸ b [1]", "  topics: 2"]);
+
+        t.handle_key(key(KeyCode::Down), &v);
+        t.handle_key(key(KeyCode::Down), &v);
+        t.handle_key(key(KeyCode::Char('l')), &v);
+        assert_eq!(rows(&t), ["▾ peers {2}", "    a: 1", "  ▾ b [1]", "      [0]: true", "  topics: 2"]);
+
+        // `h` on an expanded node collapses it; on a leaf it collapses the parent and moves there.
+        t.handle_key(key(KeyCode::Char('h')), &v);
+        assert_eq!(rows(&t).len(), 4);
+        t.handle_key(key(KeyCode::Up), &v);
+        t.handle_key(key(KeyCode::Char('h')), &v);
+        assert_eq!(rows(&t), ["▸ peers {2}", "  topics: 2"]);
+        assert_eq!(t.selected, 0);
+    }
+
+    #[test]
+    fn format_rate_handles_zero_and_sub_kb() {
+        assert_eq!(format_rate(0.0), "0 B/s");
+        assert_eq!(format_rate(512.4), "512 B/s");
+        assert_eq!(format_rate(-5.0), "0 B/s");
+        assert_eq!(format_rate(f64::NAN), "0 B/s");
+    }
+
+    #[test]
+    fn format_rate_switches_units_at_boundaries() {
+        assert_eq!(format_rate(1023.0), "1023 B/s");
+        assert_eq!(format_rate(1024.0), "1.0 KB/s");
+        assert_eq!(format_rate(1536.0), "1.5 KB/s");
+        assert_eq!(format_rate(1024.0 * 1024.0 - 1.0), "1.0 MB/s");
+        assert_eq!(format_rate(1024.0 * 1024.0), "1.0 MB/s");
+        assert_eq!(format_rate(1.2 * 1024.0 * 1024.0), "1.2 MB/s");
+    }
+}